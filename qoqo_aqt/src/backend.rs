@@ -10,19 +10,38 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::devices::convert_into_device;
+use crate::devices::convert_into_any_device;
+use crate::JobHandleWrapper;
 use bincode::{deserialize, serialize};
 use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyByteArray;
-use qoqo::convert_into_circuit;
+use pyo3::types::{PyByteArray, PyDict};
+use qoqo::measurements::{
+    CheatedPauliZProductWrapper, CheatedWrapper, ClassicalRegisterWrapper, PauliZProductWrapper,
+};
 use qoqo::QoqoBackendError;
-use roqoqo::prelude::*;
+use qoqo::{convert_into_circuit, convert_into_quantum_program, CircuitWrapper};
+use roqoqo::measurements::{
+    Cheated, CheatedPauliZProduct, ClassicalRegister, Measure, MeasureExpectationValues,
+    PauliZProduct,
+};
 use roqoqo::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
 use roqoqo::Circuit;
-use roqoqo_aqt::devices::AqtDevice;
-use roqoqo_aqt::Backend;
+use roqoqo::QuantumProgram;
+use roqoqo::RoqoqoBackendError;
+use roqoqo::RoqoqoError;
+use roqoqo_aqt::counts::{AqtCounts, BitOrdering, PackedBitOrder, SampleLayout};
+use roqoqo_aqt::devices::AnyAqtDevice;
+use roqoqo_aqt::http_client::HttpClientConfig;
+use roqoqo_aqt::journal::JobJournal;
+use roqoqo_aqt::noise_amplification::ZeroNoiseExtrapolation;
+use roqoqo_aqt::readout_mitigation::MitigationMethod;
+use roqoqo_aqt::report::UntranslatedOperation;
+use roqoqo_aqt::result_cache::{CachePolicy, ResultCache};
+use roqoqo_aqt::{AqtJobHandle, AqtRunData, Backend, JobMetadata, LegacyBackend};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// AQT backend
 ///
@@ -31,7 +50,7 @@ use std::collections::HashMap;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BackendWrapper {
     /// Internal storage of [roqoqo_aqt::Backend]
-    pub internal: Backend<AqtDevice>,
+    pub internal: Backend<AnyAqtDevice>,
 }
 
 /// Type of registers returned from a run of a Circuit.
@@ -41,22 +60,29 @@ pub type Registers = (
     HashMap<String, ComplexOutputRegister>,
 );
 
+/// Type of counts returned from a run of a Circuit: the counts and the raw integer samples of each readout register.
+pub type Counts = (HashMap<String, PyObject>, HashMap<String, Vec<u32>>);
+
 #[pymethods]
 impl BackendWrapper {
     /// Create a new AQT Backend.
     ///
     /// Args:
-    ///     device (Device): AQT Device providing information about the endpoint running Circuits.
+    ///     device (Union[AqtDevice, SimulatorDevice, EmulatorDevice]): AQT Device providing information about the endpoint running Circuits,
+    ///                                                                or a local device simulating the Circuits.
     ///     access_token (Optional[str]): Optional access token to AQT endpoints.
-    ///                                   When None access token is read from $AQT_ACCESS_TOKEN environmental variable
+    ///                                   When None access token is read from $AQT_ACCESS_TOKEN environmental variable,
+    ///                                   local devices do not need an access token
     ///
     /// Raises:
-    ///     TypeError: Device Parameter is not AqtDevice
+    ///     TypeError: Device Parameter is not an AqtDevice, SimulatorDevice or EmulatorDevice
     ///     RuntimeError: No access token found
     #[new]
     pub fn new(device: &Bound<PyAny>, access_token: Option<String>) -> PyResult<Self> {
-        let device: AqtDevice = convert_into_device(device).map_err(|err| {
-            PyTypeError::new_err(format!("Device Parameter is not AqtDevice {err:?}"))
+        let device: AnyAqtDevice = convert_into_any_device(device).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Device Parameter is not an AqtDevice, SimulatorDevice or EmulatorDevice {err:?}"
+            ))
         })?;
         Ok(Self {
             internal: Backend::new(device, access_token)
@@ -86,282 +112,1786 @@ impl BackendWrapper {
             .map_err(|err| PyRuntimeError::new_err(format!("Translating Circuit failed {err:?}")))
     }
 
-    /// Return a copy of the Backend (copy here produces a deepcopy).
+    /// Analyse the translation of a circuit without stopping at the first unsupported operation.
     ///
-    /// Returns:
-    ///     Backend: A deep copy of self.
-    pub fn __copy__(&self) -> BackendWrapper {
-        self.clone()
-    }
-
-    /// Return a deep copy of the Backend.
+    /// Args:
+    ///     circuit (Circuit): The circuit that is analysed.
     ///
     /// Returns:
-    ///     Backend: A deep copy of self.
-    pub fn __deepcopy__(&self, _memodict: Py<PyAny>) -> BackendWrapper {
-        self.clone()
+    ///     Dict[str, Any]: The report with the keys
+    ///         `translatable` (bool), `number_operations` (int),
+    ///         `unsupported_operations` and `symbolic_parameters` (lists of dicts with `position`, `hqslang` and `reason`),
+    ///         `unsupported_counts`, `ignored_operations` and `native_gate_counts` (dicts of counts by name)
+    ///         and `payload_size` (int, size of the json body of the job in bytes).
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    pub fn translation_report<'py>(
+        &self,
+        py: Python<'py>,
+        circuit: &Bound<PyAny>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {err:?}"
+            ))
+        })?;
+        let report = self.internal.translation_report(&circuit);
+        let untranslated_to_list =
+            |operations: &[UntranslatedOperation]| -> PyResult<Vec<Bound<'py, PyDict>>> {
+                operations
+                    .iter()
+                    .map(|operation| {
+                        let dict = PyDict::new_bound(py);
+                        dict.set_item("position", operation.position)?;
+                        dict.set_item("hqslang", &operation.hqslang)?;
+                        dict.set_item("reason", &operation.reason)?;
+                        Ok(dict)
+                    })
+                    .collect()
+            };
+        let dict = PyDict::new_bound(py);
+        dict.set_item("translatable", report.is_translatable())?;
+        dict.set_item("number_operations", report.number_operations)?;
+        dict.set_item(
+            "unsupported_operations",
+            untranslated_to_list(&report.unsupported_operations)?,
+        )?;
+        dict.set_item("unsupported_counts", report.unsupported_counts)?;
+        dict.set_item(
+            "symbolic_parameters",
+            untranslated_to_list(&report.symbolic_parameters)?,
+        )?;
+        dict.set_item("ignored_operations", report.ignored_operations)?;
+        dict.set_item("native_gate_counts", report.native_gate_counts)?;
+        dict.set_item("payload_size", report.payload_size)?;
+        Ok(dict)
     }
 
-    /// Return the bincode representation of the Backend using the [bincode] crate.
+    /// Reconstruct a circuit from the AQT json created by to_aqt_json.
+    ///
+    /// RZ instructions become RotateZ, R instructions RotateXY and RXX instructions VariableMSXX operations.
+    /// Translating the reconstructed circuit with to_aqt_json reproduces the instructions.
+    ///
+    /// Args:
+    ///     json (str): The json list of AQT instructions.
+    ///     number_qubits (Optional[int]): The number of qubits of the readout register, None uses the highest qubit of the instructions.
+    ///     repetitions (int): The number of times the circuit is measured.
+    ///     readout (str): The name of the bit register the measurement results are written to.
     ///
     /// Returns:
-    ///     ByteArray: The serialized Backend (in [bincode] form).
+    ///     Circuit: The reconstructed circuit.
     ///
     /// Raises:
-    ///     ValueError: Cannot serialize Backend to bytes.
-    pub fn to_bincode(&self) -> PyResult<Py<PyByteArray>> {
-        let serialized = serialize(&self.internal)
-            .map_err(|_| PyValueError::new_err("Cannot serialize Backend to bytes"))?;
-        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
-            PyByteArray::new_bound(py, &serialized[..]).into()
-        });
-        Ok(b)
+    ///     ValueError: The json cannot be converted to a Circuit
+    #[staticmethod]
+    #[pyo3(signature = (json, number_qubits = None, repetitions = 1, readout = "ro"))]
+    pub fn circuit_from_aqt_json(
+        json: &str,
+        number_qubits: Option<usize>,
+        repetitions: usize,
+        readout: &str,
+    ) -> PyResult<CircuitWrapper> {
+        let circuit = roqoqo_aqt::aqt_json_to_circuit(json, number_qubits, repetitions, readout)
+            .map_err(|err| {
+                PyValueError::new_err(format!("Cannot convert AQT json to Circuit {err:?}"))
+            })?;
+        Ok(CircuitWrapper { internal: circuit })
     }
 
-    /// Convert the bincode representation of the Backend to a Backend using the [bincode] crate.
+    /// Reconstruct the circuits of a job sent to an AQT device, for example from an archived job.
     ///
     /// Args:
-    ///     input (ByteArray): The serialized Backend (in [bincode] form).
+    ///     json (str): The json body of the job.
+    ///     readout (str): The name of the bit register the measurement results are written to.
     ///
     /// Returns:
-    ///     Backend: The deserialized Backend.
+    ///     List[Circuit]: The reconstructed circuits in the order of the job.
     ///
     /// Raises:
-    ///     TypeError: Input cannot be converted to byte array.
-    ///     ValueError: Input cannot be deserialized to Backend.
+    ///     ValueError: The json cannot be converted to Circuits
     #[staticmethod]
-    pub fn from_bincode(input: &Bound<PyAny>) -> PyResult<BackendWrapper> {
-        let bytes = input
-            .extract::<Vec<u8>>()
-            .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
+    #[pyo3(signature = (json, readout = "ro"))]
+    pub fn circuits_from_aqt_run_data(json: &str, readout: &str) -> PyResult<Vec<CircuitWrapper>> {
+        let circuits = AqtRunData::from_json(json)
+            .and_then(|run_data| run_data.to_circuits(readout))
+            .map_err(|err| {
+                PyValueError::new_err(format!("Cannot convert AQT job to Circuits {err:?}"))
+            })?;
+        Ok(circuits
+            .into_iter()
+            .map(|circuit| CircuitWrapper { internal: circuit })
+            .collect())
+    }
 
-        Ok(BackendWrapper {
-            internal: deserialize(&bytes[..])
-                .map_err(|_| PyValueError::new_err("Input cannot be deserialized to Backend"))?,
-        })
+    /// Set the number of measurements used to emulate PragmaGetPauliProduct and PragmaGetOccupationProbability.
+    ///
+    /// AQT devices do not support the pragmas directly. When emulation is enabled,
+    /// every pragma is run as an additional circuit in the same job and its float register
+    /// is filled with the estimate obtained from the sampled measurements.
+    /// The standard error of the estimate due to shot noise is written to the float register `{readout}_error`.
+    ///
+    /// Args:
+    ///     number_measurements (Optional[int]): The number of measurements of each emulated pragma, None disables the emulation.
+    pub fn set_pragma_emulation_shots(&mut self, number_measurements: Option<usize>) {
+        self.internal
+            .set_pragma_emulation_shots(number_measurements)
     }
 
-    /// Return the json representation of the Backend.
+    /// Return the number of measurements used to emulate PragmaGetPauliProduct and PragmaGetOccupationProbability.
     ///
     /// Returns:
-    ///     str: The serialized form of Backend.
+    ///     Optional[int]: The number of measurements of each emulated pragma, None when the emulation is disabled.
+    pub fn pragma_emulation_shots(&self) -> Option<usize> {
+        self.internal.pragma_emulation_shots()
+    }
+
+    /// Set the time between two queries of the status of a job while waiting for the job.
+    ///
+    /// Args:
+    ///     seconds (float): The time between two queries in seconds, the default is 20 seconds.
     ///
     /// Raises:
-    ///     ValueError: Cannot serialize Backend to json.
-    fn to_json(&self) -> PyResult<String> {
-        let serialized = serde_json::to_string(&self.internal)
-            .map_err(|_| PyValueError::new_err("Cannot serialize Backend to json"))?;
-        Ok(serialized)
+    ///     ValueError: The time is negative or not finite
+    pub fn set_poll_interval(&mut self, seconds: f64) -> PyResult<()> {
+        let poll_interval = Duration::try_from_secs_f64(seconds).map_err(|err| {
+            PyValueError::new_err(format!("Poll interval is not a valid duration {err:?}"))
+        })?;
+        self.internal.set_poll_interval(poll_interval);
+        Ok(())
     }
 
-    /// Convert the json representation of a Backend to a Backend.
+    /// Return the time between two queries of the status of a job while waiting for the job.
+    ///
+    /// Returns:
+    ///     float: The time between two queries in seconds.
+    pub fn poll_interval(&self) -> f64 {
+        self.internal.poll_interval().as_secs_f64()
+    }
+
+    /// Set whether the translated instructions are optimised before they are sent to the device.
+    ///
+    /// The optimisation fuses consecutive single-qubit rotations into at most one R and one RZ instruction per qubit,
+    /// removes Z rotations before the measurement and merges or cancels adjacent RXX instructions on the same qubits.
     ///
     /// Args:
-    ///     input (str): The serialized Backend in json form.
+    ///     optimise_instructions (bool): Whether the instructions are optimised, disabled by default.
+    pub fn set_optimise_instructions(&mut self, optimise_instructions: bool) {
+        self.internal
+            .set_optimise_instructions(optimise_instructions);
+    }
+
+    /// Return whether the translated instructions are optimised before they are sent to the device.
     ///
     /// Returns:
-    ///     Backend: The deserialized Backend.
-    ///
-    /// Raises:
-    ///     ValueError: Input cannot be deserialized to Backend.
-    #[staticmethod]
-    fn from_json(input: &str) -> PyResult<BackendWrapper> {
-        Ok(BackendWrapper {
-            internal: serde_json::from_str(input)
-                .map_err(|_| PyValueError::new_err("Input cannot be deserialized to Backend"))?,
-        })
+    ///     bool: Whether the instructions are optimised.
+    pub fn optimise_instructions(&self) -> bool {
+        self.internal.optimise_instructions()
     }
 
-    /// Run a circuit with the AQT backend.
+    /// Set whether the optimised instructions are verified to be equivalent to the translated instructions.
     ///
-    /// A circuit is passed to the backend and executed.
-    /// During execution values are written to and read from classical registers
-    /// (List[bool], List[float], List[complex]).
-    /// To produce sufficient statistics for evaluating expectation values,
-    /// circuits have to be run multiple times.
-    /// The results of each repetition are concatenated in OutputRegisters
-    /// (List[List[bool]], List[List[float]], List[List[complex]]).  
+    /// The verification compares the unitaries of the instructions and is limited to circuits on at most 10 qubits.
+    ///
+    /// Args:
+    ///     verify_optimisation (bool): Whether the optimisation is verified, disabled by default.
+    pub fn set_verify_optimisation(&mut self, verify_optimisation: bool) {
+        self.internal.set_verify_optimisation(verify_optimisation);
+    }
+
+    /// Return whether the optimised instructions are verified to be equivalent to the translated instructions.
     ///
+    /// Returns:
+    ///     bool: Whether the optimisation is verified.
+    pub fn verify_optimisation(&self) -> bool {
+        self.internal.verify_optimisation()
+    }
+
+    /// Return the reduction of the number of instructions achieved by optimising the translated circuit.
     ///
     /// Args:
-    ///     circuit (Circuit): The circuit that is run on the backend.
+    ///     circuit (Circuit): The circuit that is translated and optimised.
     ///
     /// Returns:
-    ///     Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]: The output registers written by the evaluated circuits.
+    ///     Dict[str, int]: The number of instructions, of single-qubit and of two-qubit instructions before and after the optimisation
+    ///         with the keys `instructions_before`, `instructions_after`,
+    ///         `single_qubit_instructions_before`, `single_qubit_instructions_after`,
+    ///         `two_qubit_instructions_before` and `two_qubit_instructions_after`.
     ///
     /// Raises:
     ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
-    ///     RuntimeError: Running Circuit failed
-    pub fn run_circuit(&self, circuit: &Bound<PyAny>) -> PyResult<Registers> {
+    ///     RuntimeError: Translating Circuit failed
+    pub fn optimisation_report<'py>(
+        &self,
+        py: Python<'py>,
+        circuit: &Bound<PyAny>,
+    ) -> PyResult<Bound<'py, PyDict>> {
         let circuit = convert_into_circuit(circuit).map_err(|err| {
             PyTypeError::new_err(format!(
                 "Circuit argument cannot be converted to qoqo Circuit {err:?}"
             ))
         })?;
+        let report = self.internal.optimisation_report(&circuit).map_err(|err| {
+            PyRuntimeError::new_err(format!("Translating Circuit failed {err:?}"))
+        })?;
+        let dict = PyDict::new_bound(py);
+        dict.set_item("instructions_before", report.instructions_before)?;
+        dict.set_item("instructions_after", report.instructions_after)?;
+        dict.set_item(
+            "single_qubit_instructions_before",
+            report.single_qubit_instructions_before,
+        )?;
+        dict.set_item(
+            "single_qubit_instructions_after",
+            report.single_qubit_instructions_after,
+        )?;
+        dict.set_item(
+            "two_qubit_instructions_before",
+            report.two_qubit_instructions_before,
+        )?;
+        dict.set_item(
+            "two_qubit_instructions_after",
+            report.two_qubit_instructions_after,
+        )?;
+        Ok(dict)
+    }
+
+    /// Measure the readout errors of the device and cache the calibration in the backend.
+    ///
+    /// Circuits preparing all qubits in 0 and all qubits in 1 are run in a single AQT job.
+    ///
+    /// Args:
+    ///     number_measurements (int): The number of measurements of each calibration circuit.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///
+    /// Returns:
+    ///     List[List[List[float]]]: The confusion matrix of each qubit, entry [measured][prepared] is the probability to measure a state when the other was prepared.
+    ///
+    /// Raises:
+    ///     RuntimeError: Running the calibration circuits failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (number_measurements, cancel_on_interrupt = false))]
+    pub fn calibrate_readout(
+        &mut self,
+        py: Python,
+        number_measurements: usize,
+        cancel_on_interrupt: bool,
+    ) -> PyResult<Vec<[[f64; 2]; 2]>> {
+        let calibration = py
+            .allow_threads(|| {
+                self.internal
+                    .submit_readout_calibration(number_measurements)
+            })
+            .map_err(RunError::Backend)
+            .and_then(|job| {
+                self.interruptible_releasing_gil(py, &job, cancel_on_interrupt, |interrupt| {
+                    self.internal
+                        .wait_for_readout_calibration_interruptible(&job, interrupt)
+                })
+            })
+            .map_err(|err| match err {
+                RunError::Interrupted(err) => err,
+                RunError::Backend(err) => {
+                    PyRuntimeError::new_err(format!("Calibrating readout failed {err:?}"))
+                }
+            })?;
+        let confusion_matrices = calibration.confusion_matrices().to_vec();
+        self.internal.set_readout_calibration(Some(calibration));
+        Ok(confusion_matrices)
+    }
+
+    /// Return the confusion matrices of the cached readout calibration.
+    ///
+    /// Returns:
+    ///     Optional[List[List[List[float]]]]: The confusion matrix of each qubit, None when the readout has not been calibrated.
+    pub fn readout_confusion_matrices(&self) -> Option<Vec<[[f64; 2]; 2]>> {
         self.internal
-            .run_circuit(&circuit)
-            .map_err(|err| PyRuntimeError::new_err(format!("Running Circuit failed {err:?}")))
+            .readout_calibration()
+            .map(|calibration| calibration.confusion_matrices().to_vec())
     }
 
-    /// Run all circuits corresponding to one measurement with the AQT backend.
+    /// Set the method used to correct readout errors.
     ///
-    /// An expectation value measurement in general involves several circuits.
-    /// Each circuit is passes to the backend and executed separately.
-    /// A circuit is passed to the backend and executed.
-    /// During execution values are written to and read from classical registers
-    /// (List[bool], List[float], List[complex]).
-    /// To produce sufficient statistics for evaluating expectation values,
-    /// circuits have to be run multiple times.
-    /// The results of each repetition are concatenated in OutputRegisters
-    /// (List[List[bool]], List[List[float]], List[List[complex]]).  
+    /// Args:
+    ///     method (Optional[str]): "inverse" applies the inverse confusion matrix, "least_squares" additionally
+    ///                             projects onto the closest probability distribution, None disables the mitigation.
+    ///
+    /// Raises:
+    ///     ValueError: Unknown mitigation method
+    #[pyo3(signature = (method = None))]
+    pub fn set_readout_mitigation(&mut self, method: Option<&str>) -> PyResult<()> {
+        let method = match method {
+            None => None,
+            Some("inverse") => Some(MitigationMethod::Inverse),
+            Some("least_squares") => Some(MitigationMethod::LeastSquares),
+            Some(method) => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown readout mitigation method {method}"
+                )))
+            }
+        };
+        self.internal.set_readout_mitigation(method);
+        Ok(())
+    }
+
+    /// Return the method used to correct readout errors.
     ///
+    /// Returns:
+    ///     Optional[str]: "inverse", "least_squares" or None when the mitigation is disabled.
+    pub fn readout_mitigation(&self) -> Option<&'static str> {
+        self.internal
+            .readout_mitigation()
+            .map(|method| match method {
+                MitigationMethod::Inverse => "inverse",
+                MitigationMethod::LeastSquares => "least_squares",
+            })
+    }
+
+    /// Correct the distribution of the measured shots of a bit register for readout errors.
     ///
     /// Args:
-    ///     measurement (Measurement): The measurement that is run on the backend.
+    ///     register (List[List[bool]]): The measured shots.
     ///
     /// Returns:
-    ///     Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]: The output registers written by the evaluated circuits.
+    ///     Dict[str, float]: The corrected probability of each bitstring, qubit 0 is the first character.
     ///
     /// Raises:
-    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
-    ///     RuntimeError: Running Circuit failed
-    pub fn run_measurement_registers(&self, measurement: &Bound<PyAny>) -> PyResult<Registers> {
-        let mut run_circuits: Vec<Circuit> = Vec::new();
-
-        let get_constant_circuit = measurement
-            .call_method0("constant_circuit")
-            .map_err(|err| {
-                PyTypeError::new_err(format!(
-                    "Cannot extract constant circuit from measurement {err:?}"
-                ))
-            })?;
-        let const_circuit = get_constant_circuit
-            .extract::<Option<&PyAny>>()
-            .map_err(|err| {
-                PyTypeError::new_err(format!(
-                    "Cannot extract constant circuit from measurement {err:?}"
-                ))
-            })?;
+    ///     RuntimeError: Readout error mitigation is disabled or the readout has not been calibrated
+    pub fn mitigate_distribution(
+        &self,
+        register: Vec<Vec<bool>>,
+    ) -> PyResult<HashMap<String, f64>> {
+        let distribution = self
+            .internal
+            .mitigate_distribution(&register)
+            .map_err(|err| PyRuntimeError::new_err(format!("Mitigating readout failed {err:?}")))?;
+        Ok(distribution
+            .into_iter()
+            .map(|(bits, probability)| {
+                let bitstring = bits
+                    .iter()
+                    .map(|bit| if *bit { '1' } else { '0' })
+                    .collect();
+                (bitstring, probability)
+            })
+            .collect())
+    }
 
-        let constant_circuit = match const_circuit {
-            Some(x) => convert_into_circuit(&x.as_borrowed()).map_err(|err| {
-                PyTypeError::new_err(format!(
-                    "Cannot extract constant circuit from measurement {err:?}"
-                ))
-            })?,
-            None => Circuit::new(),
+    /// Set the position of qubit 0 in the packed integer samples returned by the device.
+    ///
+    /// The order of the qubits in counts is chosen separately with the bit_ordering argument of run_circuit_counts.
+    ///
+    /// Args:
+    ///     packed_bit_order (str): "least_significant" puts qubit 0 in the least significant bit like AQT devices (default),
+    ///                             "most_significant" puts qubit 0 in the most significant bit.
+    ///
+    /// Raises:
+    ///     ValueError: Unknown packed bit order
+    pub fn set_packed_bit_order(&mut self, packed_bit_order: &str) -> PyResult<()> {
+        let packed_bit_order = match packed_bit_order {
+            "least_significant" => PackedBitOrder::QubitZeroLeastSignificant,
+            "most_significant" => PackedBitOrder::QubitZeroMostSignificant,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown packed bit order {packed_bit_order}"
+                )))
+            }
         };
+        self.internal.set_packed_bit_order(packed_bit_order);
+        Ok(())
+    }
 
-        let get_circuit_list = measurement.call_method0("circuits").map_err(|err| {
-            PyTypeError::new_err(format!(
-                "Cannot extract circuit list from measurement {err:?}"
-            ))
-        })?;
-        let circuit_list = get_circuit_list.extract::<Vec<&PyAny>>().map_err(|err| {
-            PyTypeError::new_err(format!(
-                "Cannot extract circuit list from measurement {err:?}"
-            ))
-        })?;
+    /// Return the position of qubit 0 in the packed integer samples returned by the device.
+    ///
+    /// Returns:
+    ///     str: "least_significant" or "most_significant".
+    pub fn packed_bit_order(&self) -> &'static str {
+        match self.internal.packed_bit_order() {
+            PackedBitOrder::QubitZeroLeastSignificant => "least_significant",
+            PackedBitOrder::QubitZeroMostSignificant => "most_significant",
+        }
+    }
+
+    /// Set the layout of the samples returned by the device.
+    ///
+    /// Args:
+    ///     sample_layout (str): "packed" for one integer per shot, "per_qubit" for a list of bits per shot starting with qubit 0,
+    ///                          "auto" decodes shots with a single entry as packed integers and longer shots as lists (default).
+    ///
+    /// Raises:
+    ///     ValueError: Unknown sample layout
+    pub fn set_sample_layout(&mut self, sample_layout: &str) -> PyResult<()> {
+        let sample_layout = match sample_layout {
+            "auto" => SampleLayout::Auto,
+            "packed" => SampleLayout::Packed,
+            "per_qubit" => SampleLayout::PerQubit,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown sample layout {sample_layout}"
+                )))
+            }
+        };
+        self.internal.set_sample_layout(sample_layout);
+        Ok(())
+    }
 
-        for c in circuit_list {
-            run_circuits.push(
-                constant_circuit.clone()
-                    + convert_into_circuit(&c.as_borrowed()).map_err(|err| {
-                        PyTypeError::new_err(format!(
-                            "Cannot extract circuit of circuit list from measurement {err:?}"
-                        ))
-                    })?,
-            )
+    /// Return the layout of the samples returned by the device.
+    ///
+    /// Returns:
+    ///     str: "auto", "packed" or "per_qubit".
+    pub fn sample_layout(&self) -> &'static str {
+        match self.internal.sample_layout() {
+            SampleLayout::Auto => "auto",
+            SampleLayout::Packed => "packed",
+            SampleLayout::PerQubit => "per_qubit",
         }
+    }
 
-        let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
-        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
-        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
-
-        for circuit in run_circuits {
-            let (tmp_bit_reg, tmp_float_reg, tmp_complex_reg) =
-                self.internal.run_circuit(&circuit).map_err(|err| {
-                    PyRuntimeError::new_err(format!("Running a circuit failed {err:?}"))
-                })?;
-
-            for (key, mut val) in tmp_bit_reg.into_iter() {
-                if let Some(x) = bit_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = bit_registers.insert(key, val);
-                }
+    /// Set the on-disk cache of the results of jobs sent to the AQT servers.
+    ///
+    /// Jobs with the same circuits on the same resource of the same remote host are answered from the cache
+    /// instead of being sent again.
+    ///
+    /// Args:
+    ///     directory (Optional[str]): The directory the results are stored in, None disables caching.
+    ///     policy (str): "read_write" reads and stores results, "read_only" only reads stored results,
+    ///                   "expiring" is "read_write" ignoring results older than max_age, "never" disables the cache.
+    ///     max_age (Optional[float]): The maximal age of stored results in seconds, required for the "expiring" policy.
+    ///
+    /// Raises:
+    ///     ValueError: Unknown cache policy or max_age not matching the policy
+    #[pyo3(signature = (directory, policy = "read_write", max_age = None))]
+    pub fn set_result_cache(
+        &mut self,
+        directory: Option<PathBuf>,
+        policy: &str,
+        max_age: Option<f64>,
+    ) -> PyResult<()> {
+        let policy = match (policy, max_age) {
+            ("never", None) => CachePolicy::Never,
+            ("read_only", None) => CachePolicy::ReadOnly,
+            ("read_write", None) => CachePolicy::ReadWrite,
+            ("expiring", Some(max_age)) => {
+                CachePolicy::Expiring(Duration::try_from_secs_f64(max_age).map_err(|err| {
+                    PyValueError::new_err(format!("Invalid maximal age {max_age} {err:?}"))
+                })?)
             }
-            for (key, mut val) in tmp_float_reg.into_iter() {
-                if let Some(x) = float_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = float_registers.insert(key, val);
-                }
+            ("expiring", None) => {
+                return Err(PyValueError::new_err(
+                    "The expiring cache policy requires a maximal age",
+                ))
             }
-            for (key, mut val) in tmp_complex_reg.into_iter() {
-                if let Some(x) = complex_registers.get_mut(&key) {
-                    x.append(&mut val);
-                } else {
-                    let _ = complex_registers.insert(key, val);
-                }
+            ("never" | "read_only" | "read_write", Some(_)) => {
+                return Err(PyValueError::new_err(format!(
+                    "A maximal age can not be used with the {policy} cache policy"
+                )))
+            }
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown cache policy {policy}"
+                )))
             }
+        };
+        self.internal
+            .set_result_cache(directory.map(|directory| ResultCache::new(&directory, policy)));
+        Ok(())
+    }
+
+    /// Return the policy of the result cache.
+    ///
+    /// Returns:
+    ///     Optional[str]: "never", "read_only", "read_write" or "expiring", None when no cache is set.
+    pub fn result_cache_policy(&self) -> Option<&'static str> {
+        self.internal
+            .result_cache()
+            .map(|cache| match cache.policy() {
+                CachePolicy::Never => "never",
+                CachePolicy::ReadOnly => "read_only",
+                CachePolicy::ReadWrite => "read_write",
+                CachePolicy::Expiring(_) => "expiring",
+            })
+    }
+
+    /// Remove all stored results from the result cache.
+    ///
+    /// Raises:
+    ///     RuntimeError: Removing the stored results failed
+    pub fn clear_result_cache(&self) -> PyResult<()> {
+        match self.internal.result_cache() {
+            Some(cache) => cache.clear().map_err(|err| {
+                PyRuntimeError::new_err(format!("Clearing result cache failed {err:?}"))
+            }),
+            None => Ok(()),
         }
-        Ok((bit_registers, float_registers, complex_registers))
     }
 
-    /// Evaluates expectation values of a measurement with the backend.
+    /// Set the journal file the jobs sent to the AQT servers are recorded in.
     ///
+    /// Every submitted job is recorded with its register layout, so unfinished jobs can be resumed after a restart.
     ///
     /// Args:
-    ///     measurement (Measurement): The measurement that is run on the backend.
+    ///     path (Optional[str]): The path of the journal file, None disables journaling.
+    #[pyo3(signature = (path))]
+    pub fn set_job_journal(&mut self, path: Option<PathBuf>) {
+        self.internal
+            .set_job_journal(path.map(|path| JobJournal::new(&path)));
+    }
+
+    /// Return the path of the journal file the jobs sent to the AQT servers are recorded in.
     ///
     /// Returns:
-    ///     Optional[Dict[str, float]]: The  dictionary of expectation values.
+    ///     Optional[str]: The path of the journal file, None when journaling is disabled.
+    pub fn job_journal(&self) -> Option<PathBuf> {
+        self.internal
+            .job_journal()
+            .map(|journal| journal.path().to_path_buf())
+    }
+
+    /// Return the journaled jobs that have not finished, failed or been cancelled.
+    ///
+    /// The results of the jobs can be fetched with `wait_for_job`.
+    ///
+    /// Returns:
+    ///     List[JobHandle]: The handles of the unfinished jobs in order of submission.
     ///
     /// Raises:
-    ///     TypeError: Measurement evaluate function could not be used
-    ///     RuntimeError: Internal error measurement.evaluation returned unknown type
-    pub fn run_measurement(
-        &self,
-        measurement: &Bound<PyAny>,
-    ) -> PyResult<Option<HashMap<String, f64>>> {
-        let (bit_registers, float_registers, complex_registers) =
-            self.run_measurement_registers(measurement)?;
-        let get_expectation_values = measurement
-            .call_method1(
-                "evaluate",
-                (bit_registers, float_registers, complex_registers),
-            )
-            .map_err(|err| {
-                PyTypeError::new_err(format!(
-                    "Measurement evaluate function could not be used: {err:?}"
-                ))
-            })?;
-        get_expectation_values
-            .extract::<Option<HashMap<String, f64>>>()
-            .map_err(|_| {
-                PyRuntimeError::new_err(
-                    "Internal error measurement.evaluation returned unknown type",
-                )
-            })
+    ///     RuntimeError: Reading the journal failed
+    pub fn unfinished_jobs(&self) -> PyResult<Vec<JobHandleWrapper>> {
+        let jobs = self.internal.unfinished_jobs().map_err(|err| {
+            PyRuntimeError::new_err(format!("Reading job journal failed {err:?}"))
+        })?;
+        Ok(jobs
+            .into_iter()
+            .map(|job| JobHandleWrapper { internal: job })
+            .collect())
     }
-}
 
-/// Convert generic python object to [roqoqo_aqt::Backend].
-///
-/// Fallible conversion of generic python object to [roqoqo_aqt::Backend].
-pub fn convert_into_backend(input: &Bound<PyAny>) -> Result<Backend<AqtDevice>, QoqoBackendError> {
-    if let Ok(try_downcast) = input.extract::<BackendWrapper>() {
-        Ok(try_downcast.internal)
-    } else {
-        // Everything that follows tries to extract the circuit when two separately
-        // compiled python packages are involved
-        let get_bytes = input
-            .call_method0("_enum_to_bincode")
-            .map_err(|_| QoqoBackendError::CannotExtractObject)?;
-        let bytes = get_bytes
-            .extract::<Vec<u8>>()
-            .map_err(|_| QoqoBackendError::CannotExtractObject)?;
-        deserialize(&bytes[..]).map_err(|_| QoqoBackendError::CannotExtractObject)
-    }
+    /// Set the template of the label of submitted jobs, which identifies the jobs in the AQT portal.
+    ///
+    /// The placeholders `{name}`, `{tag}` and `{resource_id}` are replaced by the name given when submitting
+    /// a circuit, the user tag set with `set_job_tag` and the resource id of the device.
+    ///
+    /// Args:
+    ///     template (str): The template of the label, "qoqo_aqt_backend" by default.
+    ///
+    /// Raises:
+    ///     ValueError: The template contains an unknown placeholder or an unmatched brace
+    pub fn set_job_label(&mut self, template: &str) -> PyResult<()> {
+        self.internal
+            .set_job_label(template)
+            .map_err(|err| PyValueError::new_err(format!("Invalid job label {err:?}")))
+    }
+
+    /// Return the template of the label of submitted jobs.
+    ///
+    /// Returns:
+    ///     str: The template of the label.
+    pub fn job_label(&self) -> String {
+        self.internal.job_label().to_string()
+    }
+
+    /// Set the user tag inserted into job labels in place of `{tag}`.
+    ///
+    /// Args:
+    ///     tag (str): The user tag.
+    pub fn set_job_tag(&mut self, tag: &str) {
+        self.internal.set_job_tag(tag);
+    }
+
+    /// Return the user tag inserted into job labels in place of `{tag}`.
+    ///
+    /// Returns:
+    ///     str: The user tag, empty by default.
+    pub fn job_tag(&self) -> String {
+        self.internal.job_tag().to_string()
+    }
+
+    /// Set the timeouts, proxy, additional root certificates and user agent of the HTTP client.
+    ///
+    /// The client is created from the settings when the next request is sent and reused afterwards.
+    ///
+    /// Args:
+    ///     timeout (Optional[float]): Timeout of every request in seconds, None keeps the default of 30 seconds.
+    ///     connect_timeout (Optional[float]): Timeout of connecting to the remote host in seconds.
+    ///     proxy (Optional[str]): URL of the proxy all requests are sent through, None uses the proxies set in the environment.
+    ///     root_certificates (Optional[List[str]]): PEM files with additional trusted root certificates.
+    ///     user_agent (Optional[str]): User agent identifying the requests, None sends "roqoqo-aqt/<version>".
+    ///
+    /// Raises:
+    ///     ValueError: A timeout, the proxy URL, a root certificate or the user agent is invalid
+    #[pyo3(signature = (timeout = None, connect_timeout = None, proxy = None, root_certificates = None, user_agent = None))]
+    pub fn set_http_client_config(
+        &mut self,
+        timeout: Option<f64>,
+        connect_timeout: Option<f64>,
+        proxy: Option<String>,
+        root_certificates: Option<Vec<PathBuf>>,
+        user_agent: Option<String>,
+    ) -> PyResult<()> {
+        let duration = |seconds: Option<f64>| {
+            seconds
+                .map(Duration::try_from_secs_f64)
+                .transpose()
+                .map_err(|err| PyValueError::new_err(format!("Invalid timeout {err:?}")))
+        };
+        let config = HttpClientConfig {
+            timeout: duration(timeout)?,
+            connect_timeout: duration(connect_timeout)?,
+            proxy,
+            root_certificates: root_certificates.unwrap_or_default(),
+            user_agent,
+        };
+        self.internal
+            .set_http_client_config(config)
+            .map_err(|err| PyValueError::new_err(format!("Invalid HTTP client settings {err:?}")))
+    }
+
+    /// Return the settings of the HTTP client communicating with the AQT REST API.
+    ///
+    /// Returns:
+    ///     Dict[str, Any]: The settings with the keys "timeout", "connect_timeout", "proxy", "root_certificates"
+    ///     and "user_agent", timeouts are given in seconds.
+    pub fn http_client_config(&self, py: Python) -> PyResult<Py<PyDict>> {
+        let config = self.internal.http_client_config();
+        let dict = PyDict::new_bound(py);
+        dict.set_item(
+            "timeout",
+            config.timeout.map(|timeout| timeout.as_secs_f64()),
+        )?;
+        dict.set_item(
+            "connect_timeout",
+            config
+                .connect_timeout
+                .map(|connect_timeout| connect_timeout.as_secs_f64()),
+        )?;
+        dict.set_item("proxy", &config.proxy)?;
+        dict.set_item("root_certificates", &config.root_certificates)?;
+        dict.set_item("user_agent", &config.user_agent)?;
+        Ok(dict.unbind())
+    }
+
+    /// Record all following interactions with the AQT REST API to a cassette file.
+    ///
+    /// The file is rewritten after every interaction, the access token is never recorded.
+    ///
+    /// Args:
+    ///     path (str): The path of the cassette file.
+    pub fn record_cassette(&mut self, path: PathBuf) {
+        self.internal.record_cassette(&path);
+    }
+
+    /// Answer all following requests to the AQT REST API from a recorded cassette file.
+    ///
+    /// No requests are sent to the AQT servers while replaying. Requests with a different
+    /// body than the recorded request raise a RuntimeError.
+    ///
+    /// Args:
+    ///     path (str): The path of the cassette file.
+    ///
+    /// Raises:
+    ///     RuntimeError: The cassette file could not be read
+    pub fn replay_cassette(&mut self, path: PathBuf) -> PyResult<()> {
+        self.internal
+            .replay_cassette(&path)
+            .map_err(|err| PyRuntimeError::new_err(format!("Could not replay cassette {err:?}")))
+    }
+
+    /// Stop recording or replaying a cassette.
+    pub fn stop_cassette(&mut self) {
+        self.internal.stop_cassette();
+    }
+
+    /// Return a copy of the Backend (copy here produces a deepcopy).
+    ///
+    /// Returns:
+    ///     Backend: A deep copy of self.
+    pub fn __copy__(&self) -> BackendWrapper {
+        self.clone()
+    }
+
+    /// Return a deep copy of the Backend.
+    ///
+    /// Returns:
+    ///     Backend: A deep copy of self.
+    pub fn __deepcopy__(&self, _memodict: Py<PyAny>) -> BackendWrapper {
+        self.clone()
+    }
+
+    /// Return the bincode representation of the Backend using the [bincode] crate.
+    ///
+    /// Returns:
+    ///     ByteArray: The serialized Backend (in [bincode] form).
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize Backend to bytes.
+    pub fn to_bincode(&self) -> PyResult<Py<PyByteArray>> {
+        let serialized = serialize(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize Backend to bytes"))?;
+        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
+            PyByteArray::new_bound(py, &serialized[..]).into()
+        });
+        Ok(b)
+    }
+
+    /// Convert the bincode representation of the Backend to a Backend using the [bincode] crate.
+    ///
+    /// Backends serialized with version 0.8.0 are also accepted.
+    ///
+    /// Args:
+    ///     input (ByteArray): The serialized Backend (in [bincode] form).
+    ///
+    /// Returns:
+    ///     Backend: The deserialized Backend.
+    ///
+    /// Raises:
+    ///     TypeError: Input cannot be converted to byte array.
+    ///     ValueError: Input cannot be deserialized to Backend.
+    #[staticmethod]
+    pub fn from_bincode(input: &Bound<PyAny>) -> PyResult<BackendWrapper> {
+        let bytes = input
+            .extract::<Vec<u8>>()
+            .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
+
+        Ok(BackendWrapper {
+            internal: deserialize_backend(&bytes)
+                .map_err(|_| PyValueError::new_err("Input cannot be deserialized to Backend"))?,
+        })
+    }
+
+    /// Return the json representation of the Backend.
+    ///
+    /// Returns:
+    ///     str: The serialized form of Backend.
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize Backend to json.
+    fn to_json(&self) -> PyResult<String> {
+        let serialized = serde_json::to_string(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize Backend to json"))?;
+        Ok(serialized)
+    }
+
+    /// Convert the json representation of a Backend to a Backend.
+    ///
+    /// Backends serialized with version 0.8.0 are also accepted.
+    ///
+    /// Args:
+    ///     input (str): The serialized Backend in json form.
+    ///
+    /// Returns:
+    ///     Backend: The deserialized Backend.
+    ///
+    /// Raises:
+    ///     ValueError: Input cannot be deserialized to Backend.
+    #[staticmethod]
+    fn from_json(input: &str) -> PyResult<BackendWrapper> {
+        let internal = serde_json::from_str(input).or_else(|err| {
+            serde_json::from_str::<LegacyBackend>(input)
+                .map(Backend::from)
+                .map_err(|_| err)
+        });
+        Ok(BackendWrapper {
+            internal: internal
+                .map_err(|_| PyValueError::new_err("Input cannot be deserialized to Backend"))?,
+        })
+    }
+
+    /// Run a circuit with the AQT backend.
+    ///
+    /// A circuit is passed to the backend and executed.
+    /// During execution values are written to and read from classical registers
+    /// (List[bool], List[float], List[complex]).
+    /// To produce sufficient statistics for evaluating expectation values,
+    /// circuits have to be run multiple times.
+    /// The results of each repetition are concatenated in OutputRegisters
+    /// (List[List[bool]], List[List[float]], List[List[complex]]).  
+    ///
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit that is run on the backend.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "circuit" when None.
+    ///
+    /// Returns:
+    ///     Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]: The output registers written by the evaluated circuits.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     RuntimeError: Running Circuit failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (circuit, cancel_on_interrupt = false, name = None))]
+    pub fn run_circuit(
+        &self,
+        circuit: &Bound<PyAny>,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<Registers> {
+        let py = circuit.py();
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {err:?}"
+            ))
+        })?;
+        self.run_circuit_releasing_gil(py, &circuit, cancel_on_interrupt, name)
+            .map_err(|err| match err {
+                RunError::Interrupted(err) => err,
+                RunError::Backend(err) => {
+                    PyRuntimeError::new_err(format!("Running Circuit failed {err:?}"))
+                }
+            })
+    }
+
+    /// Submit a circuit to the AQT backend without waiting for the result.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit that is submitted to the backend.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "circuit" when None.
+    ///
+    /// Returns:
+    ///     JobHandle: The handle of the submitted job.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     RuntimeError: Submitting Circuit failed
+    #[pyo3(signature = (circuit, name = None))]
+    pub fn submit_circuit(
+        &self,
+        circuit: &Bound<PyAny>,
+        name: Option<&str>,
+    ) -> PyResult<JobHandleWrapper> {
+        let py = circuit.py();
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {err:?}"
+            ))
+        })?;
+        let job = py
+            .allow_threads(|| {
+                self.internal
+                    .submit_circuit_with_name(&circuit, name.unwrap_or("circuit"))
+            })
+            .map_err(|err| PyRuntimeError::new_err(format!("Submitting Circuit failed {err:?}")))?;
+        Ok(JobHandleWrapper { internal: job })
+    }
+
+    /// Query the status of a submitted job once.
+    ///
+    /// Args:
+    ///     job (JobHandle): The handle of the submitted job.
+    ///
+    /// Returns:
+    ///     Optional[Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]]: The output registers when the job has finished, None while it is queued or ongoing.
+    ///
+    /// Raises:
+    ///     RuntimeError: Job failed or was cancelled
+    pub fn poll_job(&self, py: Python, job: &JobHandleWrapper) -> PyResult<Option<Registers>> {
+        py.allow_threads(|| self.internal.poll_job(&job.internal))
+            .map_err(|err| PyRuntimeError::new_err(format!("Polling job failed {err:?}")))
+    }
+
+    /// Wait until a submitted job has finished and return its output registers.
+    ///
+    /// Args:
+    ///     job (JobHandle): The handle of the submitted job.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///
+    /// Returns:
+    ///     Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]: The output registers written by the evaluated circuit.
+    ///
+    /// Raises:
+    ///     RuntimeError: Job failed, was cancelled or timed out
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (job, cancel_on_interrupt = false))]
+    pub fn wait_for_job(
+        &self,
+        py: Python,
+        job: &JobHandleWrapper,
+        cancel_on_interrupt: bool,
+    ) -> PyResult<Registers> {
+        self.wait_releasing_gil(py, &job.internal, cancel_on_interrupt)
+            .map_err(|err| match err {
+                RunError::Interrupted(err) => err,
+                RunError::Backend(err) => {
+                    PyRuntimeError::new_err(format!("Waiting for job failed {err:?}"))
+                }
+            })
+    }
+
+    /// Run a circuit with the AQT backend and return counts instead of bit registers.
+    ///
+    /// The shots measured by AQT devices are integers, only their counts and the raw integers are returned
+    /// instead of expanding every shot into a list of bools.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit that is run on the backend.
+    ///     bit_ordering (str): "qubit_zero_first" puts qubit 0 first in bitstrings and as most significant bit of integers,
+    ///                         "qubit_zero_last" puts qubit 0 last and as least significant bit like the raw samples.
+    ///     integer_keys (bool): Whether the counts are keyed by integers instead of bitstrings.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "circuit" when None.
+    ///
+    /// Returns:
+    ///     Tuple[Dict[str, Dict[Union[str, int], int]], Dict[str, List[int]]]: The counts and the raw integer samples
+    ///     of each readout register, qubit 0 is the least significant bit of the raw samples.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     ValueError: Unknown bit ordering
+    ///     RuntimeError: Running Circuit failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (circuit, bit_ordering = "qubit_zero_first", integer_keys = false, cancel_on_interrupt = false, name = None))]
+    pub fn run_circuit_counts(
+        &self,
+        circuit: &Bound<PyAny>,
+        bit_ordering: &str,
+        integer_keys: bool,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<Counts> {
+        let py = circuit.py();
+        let bit_ordering = bit_ordering_from_str(bit_ordering)?;
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {err:?}"
+            ))
+        })?;
+        let job = py
+            .allow_threads(|| {
+                self.internal
+                    .submit_circuit_with_name(&circuit, name.unwrap_or("circuit"))
+            })
+            .map_err(|err| PyRuntimeError::new_err(format!("Running Circuit failed {err:?}")))?;
+        let counts = self
+            .wait_counts_releasing_gil(py, &job, cancel_on_interrupt)
+            .map_err(|err| match err {
+                RunError::Interrupted(err) => err,
+                RunError::Backend(err) => {
+                    PyRuntimeError::new_err(format!("Running Circuit failed {err:?}"))
+                }
+            })?;
+        Ok(counts_into_py(py, counts, bit_ordering, integer_keys))
+    }
+
+    /// Wait until a submitted job has finished and return counts instead of bit registers.
+    ///
+    /// Args:
+    ///     job (JobHandle): The handle of the submitted job.
+    ///     bit_ordering (str): "qubit_zero_first" or "qubit_zero_last", the position of qubit 0 in the counts.
+    ///     integer_keys (bool): Whether the counts are keyed by integers instead of bitstrings.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///
+    /// Returns:
+    ///     Tuple[Dict[str, Dict[Union[str, int], int]], Dict[str, List[int]]]: The counts and the raw integer samples
+    ///     of each readout register.
+    ///
+    /// Raises:
+    ///     ValueError: Unknown bit ordering
+    ///     RuntimeError: Job failed, was cancelled or timed out
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (job, bit_ordering = "qubit_zero_first", integer_keys = false, cancel_on_interrupt = false))]
+    pub fn wait_for_job_counts(
+        &self,
+        py: Python,
+        job: &JobHandleWrapper,
+        bit_ordering: &str,
+        integer_keys: bool,
+        cancel_on_interrupt: bool,
+    ) -> PyResult<Counts> {
+        let bit_ordering = bit_ordering_from_str(bit_ordering)?;
+        let counts = self
+            .wait_counts_releasing_gil(py, &job.internal, cancel_on_interrupt)
+            .map_err(|err| match err {
+                RunError::Interrupted(err) => err,
+                RunError::Backend(err) => {
+                    PyRuntimeError::new_err(format!("Waiting for job failed {err:?}"))
+                }
+            })?;
+        Ok(counts_into_py(py, counts, bit_ordering, integer_keys))
+    }
+
+    /// Run a circuit with the AQT backend and return the output registers together with the metadata of the job.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit that is run on the backend.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "circuit" when None.
+    ///
+    /// Returns:
+    ///     Tuple[Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]], Dict[str, Any]]: The output registers
+    ///     and the metadata of the job with the keys "job_id", "resource_id", "workspace_id", "label", "submitted" and "finished",
+    ///     timestamps are seconds since the Unix epoch or None.
+    ///
+    /// Raises:
+    ///     TypeError: Circuit argument cannot be converted to qoqo Circuit
+    ///     RuntimeError: Running Circuit failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (circuit, cancel_on_interrupt = false, name = None))]
+    pub fn run_circuit_with_metadata(
+        &self,
+        circuit: &Bound<PyAny>,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<(Registers, Py<PyDict>)> {
+        let py = circuit.py();
+        let circuit = convert_into_circuit(circuit).map_err(|err| {
+            PyTypeError::new_err(format!(
+                "Circuit argument cannot be converted to qoqo Circuit {err:?}"
+            ))
+        })?;
+        let job = py
+            .allow_threads(|| {
+                self.internal
+                    .submit_circuit_with_name(&circuit, name.unwrap_or("circuit"))
+            })
+            .map_err(|err| PyRuntimeError::new_err(format!("Running Circuit failed {err:?}")))?;
+        let result = self
+            .interruptible_releasing_gil(py, &job, cancel_on_interrupt, |interrupt| {
+                self.internal
+                    .wait_for_job_with_metadata_interruptible(&job, interrupt)
+            })
+            .map_err(|err| match err {
+                RunError::Interrupted(err) => err,
+                RunError::Backend(err) => {
+                    PyRuntimeError::new_err(format!("Running Circuit failed {err:?}"))
+                }
+            })?;
+        Ok((result.registers, metadata_into_py(py, &result.metadata)?))
+    }
+
+    /// Wait until a submitted job has finished and return its output registers together with the metadata of the job.
+    ///
+    /// Args:
+    ///     job (JobHandle): The handle of the submitted job.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///
+    /// Returns:
+    ///     Tuple[Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]], Dict[str, Any]]: The output registers
+    ///     and the metadata of the job, see `run_circuit_with_metadata`.
+    ///
+    /// Raises:
+    ///     RuntimeError: Job failed, was cancelled or timed out
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (job, cancel_on_interrupt = false))]
+    pub fn wait_for_job_with_metadata(
+        &self,
+        py: Python,
+        job: &JobHandleWrapper,
+        cancel_on_interrupt: bool,
+    ) -> PyResult<(Registers, Py<PyDict>)> {
+        let result = self
+            .interruptible_releasing_gil(py, &job.internal, cancel_on_interrupt, |interrupt| {
+                self.internal
+                    .wait_for_job_with_metadata_interruptible(&job.internal, interrupt)
+            })
+            .map_err(|err| match err {
+                RunError::Interrupted(err) => err,
+                RunError::Backend(err) => {
+                    PyRuntimeError::new_err(format!("Waiting for job failed {err:?}"))
+                }
+            })?;
+        Ok((result.registers, metadata_into_py(py, &result.metadata)?))
+    }
+
+    /// Cancel a submitted job that has not finished yet.
+    ///
+    /// Args:
+    ///     job (JobHandle): The handle of the submitted job.
+    ///
+    /// Raises:
+    ///     RuntimeError: Cancelling job failed
+    pub fn cancel_job(&self, py: Python, job: &JobHandleWrapper) -> PyResult<()> {
+        py.allow_threads(|| self.internal.cancel_job(&job.internal))
+            .map_err(|err| PyRuntimeError::new_err(format!("Cancelling job failed {err:?}")))
+    }
+
+    /// Run a circuit with the AQT backend without blocking the asyncio event loop.
+    ///
+    /// The circuit is run in the default executor of the running event loop,
+    /// so several circuits can be run concurrently.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit that is run on the backend.
+    ///
+    /// Returns:
+    ///     Awaitable[Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]]: Awaitable resolving to the output registers written by the evaluated circuits.
+    ///
+    /// Raises:
+    ///     RuntimeError: No running event loop
+    pub fn run_circuit_async<'py>(
+        slf: &Bound<'py, Self>,
+        circuit: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        run_in_executor(slf, "run_circuit", circuit)
+    }
+
+    /// Wait for a submitted job without blocking the asyncio event loop.
+    ///
+    /// Args:
+    ///     job (JobHandle): The handle of the submitted job.
+    ///
+    /// Returns:
+    ///     Awaitable[Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]]: Awaitable resolving to the output registers written by the evaluated circuit.
+    ///
+    /// Raises:
+    ///     RuntimeError: No running event loop
+    pub fn wait_for_job_async<'py>(
+        slf: &Bound<'py, Self>,
+        job: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        run_in_executor(slf, "wait_for_job", job)
+    }
+
+    /// Run all circuits corresponding to one measurement with the AQT backend.
+    ///
+    /// An expectation value measurement in general involves several circuits.
+    /// All circuits of the measurement are passed to the backend and executed in a single AQT job.
+    /// During execution values are written to and read from classical registers
+    /// (List[bool], List[float], List[complex]).
+    /// To produce sufficient statistics for evaluating expectation values,
+    /// circuits have to be run multiple times.
+    /// The results of each repetition are concatenated in OutputRegisters
+    /// (List[List[bool]], List[List[float]], List[List[complex]]).  
+    ///
+    ///
+    /// Args:
+    ///     measurement (Measurement): The measurement that is run on the backend.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "measurement" when None.
+    ///
+    /// Returns:
+    ///     Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]: The output registers written by the evaluated circuits.
+    ///
+    /// Raises:
+    ///     TypeError: Measurement argument cannot be converted to qoqo measurement
+    ///     RuntimeError: Running measurement failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (measurement, cancel_on_interrupt = false, name = None))]
+    pub fn run_measurement_registers(
+        &self,
+        measurement: &Bound<PyAny>,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<Registers> {
+        let py = measurement.py();
+        let result = match convert_into_measurement(measurement)? {
+            AqtMeasurement::PauliZProduct(measurement) => {
+                self.run_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+            }
+            AqtMeasurement::CheatedPauliZProduct(measurement) => {
+                self.run_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+            }
+            AqtMeasurement::Cheated(measurement) => {
+                self.run_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+            }
+            AqtMeasurement::ClassicalRegister(measurement) => {
+                self.run_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+            }
+        };
+        result.map_err(|err| match err {
+            RunError::Interrupted(err) => err,
+            RunError::Backend(err) => {
+                PyRuntimeError::new_err(format!("Running measurement failed {err:?}"))
+            }
+        })
+    }
+
+    /// Evaluates expectation values of a measurement with the backend.
+    ///
+    /// All circuits of the measurement are run in a single AQT job.
+    /// When readout error mitigation is enabled, the expectation values of PauliZProduct measurements are corrected
+    /// with the cached readout calibration.
+    ///
+    /// Args:
+    ///     measurement (Measurement): The measurement that is run on the backend.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "measurement" when None.
+    ///
+    /// Returns:
+    ///     Optional[Dict[str, float]]: The  dictionary of expectation values.
+    ///
+    /// Raises:
+    ///     TypeError: Measurement argument cannot be converted to qoqo measurement or does not produce expectation values
+    ///     RuntimeError: Running measurement failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (measurement, cancel_on_interrupt = false, name = None))]
+    pub fn run_measurement(
+        &self,
+        measurement: &Bound<PyAny>,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<Option<HashMap<String, f64>>> {
+        let py = measurement.py();
+        let result = match convert_into_measurement(measurement)? {
+            AqtMeasurement::PauliZProduct(measurement)
+                if self.internal.readout_mitigation().is_some() =>
+            {
+                self.run_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+                    .and_then(|(bit_registers, _, _)| {
+                        self.internal
+                            .evaluate_mitigated(&measurement, &bit_registers)
+                            .map_err(RunError::Backend)
+                    })
+            }
+            AqtMeasurement::PauliZProduct(measurement) => {
+                self.evaluate_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+            }
+            AqtMeasurement::CheatedPauliZProduct(measurement) => {
+                self.evaluate_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+            }
+            AqtMeasurement::Cheated(measurement) => {
+                self.evaluate_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+            }
+            AqtMeasurement::ClassicalRegister(_) => {
+                return Err(PyTypeError::new_err(
+                    "ClassicalRegister measurement does not produce expectation values, use run_measurement_registers",
+                ))
+            }
+        };
+        result.map_err(|err| match err {
+            RunError::Interrupted(err) => err,
+            RunError::Backend(err) => {
+                PyRuntimeError::new_err(format!("Running measurement failed {err:?}"))
+            }
+        })
+    }
+
+    /// Evaluates expectation values of a measurement at several noise scales and extrapolates them to zero noise.
+    ///
+    /// Every circuit of the measurement is run once for each scale factor in a single AQT job,
+    /// the noise is amplified by folding the R and RXX instructions like PragmaBoostNoise.
+    ///
+    /// Args:
+    ///     measurement (Measurement): The measurement that is run on the backend.
+    ///     scale_factors (List[float]): The noise scale factors, at least 1. Odd integers are implemented exactly.
+    ///     order (int): The order of the polynomial fitted for the extrapolation.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "zero_noise_extrapolation" when None.
+    ///
+    /// Returns:
+    ///     Dict[str, Any]: The scale factors ("scale_factors"), the expectation values for each scale factor ("values")
+    ///                     and the expectation values extrapolated to zero noise ("extrapolated").
+    ///
+    /// Raises:
+    ///     TypeError: Measurement argument cannot be converted to qoqo measurement or does not produce expectation values
+    ///     RuntimeError: Running measurement or extrapolating failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (measurement, scale_factors, order = 1, cancel_on_interrupt = false, name = None))]
+    pub fn run_measurement_zero_noise_extrapolation<'py>(
+        &self,
+        measurement: &Bound<'py, PyAny>,
+        scale_factors: Vec<f64>,
+        order: usize,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let py = measurement.py();
+        let result = match convert_into_measurement(measurement)? {
+            AqtMeasurement::PauliZProduct(measurement) => self
+                .zero_noise_extrapolation_releasing_gil(
+                    py,
+                    &measurement,
+                    &scale_factors,
+                    order,
+                    cancel_on_interrupt,
+                    name,
+                ),
+            AqtMeasurement::CheatedPauliZProduct(measurement) => self
+                .zero_noise_extrapolation_releasing_gil(
+                    py,
+                    &measurement,
+                    &scale_factors,
+                    order,
+                    cancel_on_interrupt,
+                    name,
+                ),
+            AqtMeasurement::Cheated(measurement) => self.zero_noise_extrapolation_releasing_gil(
+                py,
+                &measurement,
+                &scale_factors,
+                order,
+                cancel_on_interrupt,
+                name,
+            ),
+            AqtMeasurement::ClassicalRegister(_) => {
+                return Err(PyTypeError::new_err(
+                    "ClassicalRegister measurement does not produce expectation values",
+                ))
+            }
+        }
+        .map_err(|err| match err {
+            RunError::Interrupted(err) => err,
+            RunError::Backend(err) => {
+                PyRuntimeError::new_err(format!("Zero-noise extrapolation failed {err:?}"))
+            }
+        })?;
+        let dict = PyDict::new_bound(py);
+        dict.set_item("scale_factors", result.scale_factors)?;
+        dict.set_item("values", result.values)?;
+        dict.set_item("extrapolated", result.extrapolated)?;
+        Ok(dict)
+    }
+
+    /// Run a QuantumProgram with the AQT backend and evaluate its expectation values.
+    ///
+    /// The input parameters of the program are replaced by the given values
+    /// and all circuits of the measurement of the program are run in a single AQT job.
+    /// Programs with a ClassicalRegister measurement have to be run with `run_registers`.
+    ///
+    /// Args:
+    ///     program (QuantumProgram): The quantum program that is run on the backend.
+    ///     params_values (List[float]): The values of the input parameters of the program in the order of its input_parameter_names.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "measurement" when None.
+    ///
+    /// Returns:
+    ///     Optional[Dict[str, float]]: The dictionary of expectation values.
+    ///
+    /// Raises:
+    ///     TypeError: Program argument cannot be converted to qoqo QuantumProgram or returns classical registers
+    ///     ValueError: Wrong number of parameters or parameters cannot be substituted
+    ///     RuntimeError: Running QuantumProgram failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (program, params_values, cancel_on_interrupt = false, name = None))]
+    pub fn run_program(
+        &self,
+        program: &Bound<PyAny>,
+        params_values: Vec<f64>,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<Option<HashMap<String, f64>>> {
+        let py = program.py();
+        let program = convert_into_program(program)?;
+        let substituted_parameters = program_parameters(&program, params_values)?;
+        match program {
+            QuantumProgram::PauliZProduct { measurement, .. } => {
+                let measurement = measurement
+                    .substitute_parameters(substituted_parameters)
+                    .map_err(substitution_error)?;
+                self.evaluate_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+                    .map_err(program_run_error)
+            }
+            QuantumProgram::CheatedPauliZProduct { measurement, .. } => {
+                let measurement = measurement
+                    .substitute_parameters(substituted_parameters)
+                    .map_err(substitution_error)?;
+                self.evaluate_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+                    .map_err(program_run_error)
+            }
+            QuantumProgram::Cheated { measurement, .. } => {
+                let measurement = measurement
+                    .substitute_parameters(substituted_parameters)
+                    .map_err(substitution_error)?;
+                self.evaluate_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+                    .map_err(program_run_error)
+            }
+            _ => Err(PyTypeError::new_err(
+                "A QuantumProgram returning classical registers cannot be executed by `run_program`, use `run_registers` instead",
+            )),
+        }
+    }
+
+    /// Run a QuantumProgram with a ClassicalRegister measurement with the AQT backend.
+    ///
+    /// The input parameters of the program are replaced by the given values
+    /// and all circuits of the measurement of the program are run in a single AQT job.
+    ///
+    /// Args:
+    ///     program (QuantumProgram): The quantum program that is run on the backend.
+    ///     params_values (List[float]): The values of the input parameters of the program in the order of its input_parameter_names.
+    ///     cancel_on_interrupt (bool): Whether the AQT job is cancelled when waiting is interrupted with Ctrl-C.
+    ///     name (Optional[str]): The name replacing `{name}` in the job label, "measurement" when None.
+    ///
+    /// Returns:
+    ///     Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]]], Dict[str, List[List[complex]]]]: The output registers of the program.
+    ///
+    /// Raises:
+    ///     TypeError: Program argument cannot be converted to qoqo QuantumProgram or returns expectation values
+    ///     ValueError: Wrong number of parameters or parameters cannot be substituted
+    ///     RuntimeError: Running QuantumProgram failed
+    ///     KeyboardInterrupt: Waiting for the result was interrupted
+    #[pyo3(signature = (program, params_values, cancel_on_interrupt = false, name = None))]
+    pub fn run_registers(
+        &self,
+        program: &Bound<PyAny>,
+        params_values: Vec<f64>,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> PyResult<Registers> {
+        let py = program.py();
+        let program = convert_into_program(program)?;
+        let substituted_parameters = program_parameters(&program, params_values)?;
+        match program {
+            QuantumProgram::ClassicalRegister { measurement, .. } => {
+                let measurement = measurement
+                    .substitute_parameters(substituted_parameters)
+                    .map_err(substitution_error)?;
+                self.run_measurement_releasing_gil(py, &measurement, cancel_on_interrupt, name)
+                    .map_err(program_run_error)
+            }
+            _ => Err(PyTypeError::new_err(
+                "A QuantumProgram returning expectation values cannot be executed by `run_registers`, use `run_program` instead",
+            )),
+        }
+    }
+
+    /// Evaluate expectation values of a measurement without blocking the asyncio event loop.
+    ///
+    /// The measurement is run in the default executor of the running event loop,
+    /// so several measurements can be run concurrently.
+    ///
+    /// Args:
+    ///     measurement (Measurement): The measurement that is run on the backend.
+    ///
+    /// Returns:
+    ///     Awaitable[Optional[Dict[str, float]]]: Awaitable resolving to the dictionary of expectation values.
+    ///
+    /// Raises:
+    ///     RuntimeError: No running event loop
+    pub fn run_measurement_async<'py>(
+        slf: &Bound<'py, Self>,
+        measurement: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        run_in_executor(slf, "run_measurement", measurement)
+    }
+}
+
+/// Measurements that can be run with the AQT backend.
+enum AqtMeasurement {
+    PauliZProduct(PauliZProduct),
+    CheatedPauliZProduct(CheatedPauliZProduct),
+    Cheated(Cheated),
+    ClassicalRegister(ClassicalRegister),
+}
+
+/// Extracts a roqoqo measurement from a qoqo measurement Python object.
+///
+/// Like the circuit conversion this also accepts measurements created by a separately compiled qoqo.
+fn convert_into_measurement(input: &Bound<PyAny>) -> PyResult<AqtMeasurement> {
+    if let Ok(measurement) = PauliZProductWrapper::from_pyany(input) {
+        return Ok(AqtMeasurement::PauliZProduct(measurement));
+    }
+    if let Ok(measurement) = CheatedPauliZProductWrapper::from_pyany(input) {
+        return Ok(AqtMeasurement::CheatedPauliZProduct(measurement));
+    }
+    if let Ok(measurement) = CheatedWrapper::from_pyany(input) {
+        return Ok(AqtMeasurement::Cheated(measurement));
+    }
+    if let Ok(measurement) = ClassicalRegisterWrapper::from_pyany(input) {
+        return Ok(AqtMeasurement::ClassicalRegister(measurement));
+    }
+    Err(PyTypeError::new_err(
+        "Measurement argument cannot be converted to qoqo measurement",
+    ))
+}
+
+/// Extracts a roqoqo QuantumProgram from a qoqo QuantumProgram Python object.
+fn convert_into_program(input: &Bound<PyAny>) -> PyResult<QuantumProgram> {
+    convert_into_quantum_program(input).map_err(|err| {
+        PyTypeError::new_err(format!(
+            "Program argument cannot be converted to qoqo QuantumProgram {err:?}"
+        ))
+    })
+}
+
+/// Maps the input parameter names of a QuantumProgram to the given values.
+fn program_parameters(
+    program: &QuantumProgram,
+    params_values: Vec<f64>,
+) -> PyResult<HashMap<String, f64>> {
+    let input_parameter_names = match program {
+        QuantumProgram::PauliZProduct {
+            input_parameter_names,
+            ..
+        }
+        | QuantumProgram::CheatedPauliZProduct {
+            input_parameter_names,
+            ..
+        }
+        | QuantumProgram::Cheated {
+            input_parameter_names,
+            ..
+        }
+        | QuantumProgram::ClassicalRegister {
+            input_parameter_names,
+            ..
+        } => input_parameter_names,
+        _ => {
+            return Err(PyTypeError::new_err(
+                "QuantumProgram type is not supported by the AQT backend",
+            ))
+        }
+    };
+    if params_values.len() != input_parameter_names.len() {
+        return Err(PyValueError::new_err(format!(
+            "Wrong number of parameters {} parameters expected {} parameters given",
+            input_parameter_names.len(),
+            params_values.len()
+        )));
+    }
+    Ok(input_parameter_names
+        .iter()
+        .cloned()
+        .zip(params_values)
+        .collect())
+}
+
+/// Converts an error substituting the parameters of a QuantumProgram to a Python error.
+fn substitution_error(err: RoqoqoError) -> PyErr {
+    PyValueError::new_err(format!("Parameters cannot be substituted {err:?}"))
+}
+
+/// Converts an error running a QuantumProgram to a Python error.
+fn program_run_error(err: RunError) -> PyErr {
+    match err {
+        RunError::Interrupted(err) => err,
+        RunError::Backend(err) => {
+            PyRuntimeError::new_err(format!("Running QuantumProgram failed {err:?}"))
+        }
+    }
+}
+
+/// Error of a run with the GIL released.
+enum RunError {
+    /// Waiting was interrupted by a Python signal handler, usually a KeyboardInterrupt
+    Interrupted(PyErr),
+    /// Running on the AQT backend failed
+    Backend(RoqoqoBackendError),
+}
+
+impl BackendWrapper {
+    /// Submits a circuit and waits for its result with the GIL released.
+    fn run_circuit_releasing_gil(
+        &self,
+        py: Python,
+        circuit: &Circuit,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> Result<Registers, RunError> {
+        let job = py
+            .allow_threads(|| {
+                self.internal
+                    .submit_circuit_with_name(circuit, name.unwrap_or("circuit"))
+            })
+            .map_err(RunError::Backend)?;
+        self.wait_releasing_gil(py, &job, cancel_on_interrupt)
+    }
+
+    /// Runs all circuits of a measurement in a single job with the GIL released.
+    fn run_measurement_releasing_gil<M: Measure + Sync>(
+        &self,
+        py: Python,
+        measurement: &M,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> Result<Registers, RunError> {
+        let job = py
+            .allow_threads(|| {
+                self.internal
+                    .submit_measurement_with_name(measurement, name.unwrap_or("measurement"))
+            })
+            .map_err(RunError::Backend)?;
+        self.wait_releasing_gil(py, &job, cancel_on_interrupt)
+    }
+
+    /// Runs all circuits of a measurement in a single job with the GIL released and evaluates the expectation values.
+    fn evaluate_measurement_releasing_gil<M: MeasureExpectationValues + Sync>(
+        &self,
+        py: Python,
+        measurement: &M,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> Result<Option<HashMap<String, f64>>, RunError> {
+        let (bit_registers, float_registers, complex_registers) =
+            self.run_measurement_releasing_gil(py, measurement, cancel_on_interrupt, name)?;
+        measurement
+            .evaluate(bit_registers, float_registers, complex_registers)
+            .map_err(|err| RunError::Backend(err.into()))
+    }
+
+    /// Runs a measurement at several noise scales in a single job with the GIL released and extrapolates to zero noise.
+    fn zero_noise_extrapolation_releasing_gil<M: MeasureExpectationValues + Sync>(
+        &self,
+        py: Python,
+        measurement: &M,
+        scale_factors: &[f64],
+        order: usize,
+        cancel_on_interrupt: bool,
+        name: Option<&str>,
+    ) -> Result<ZeroNoiseExtrapolation, RunError> {
+        let job = py
+            .allow_threads(|| {
+                self.internal
+                    .submit_measurement_zero_noise_extrapolation_with_name(
+                        measurement,
+                        scale_factors,
+                        order,
+                        name.unwrap_or("zero_noise_extrapolation"),
+                    )
+            })
+            .map_err(RunError::Backend)?;
+        self.interruptible_releasing_gil(py, job.job(), cancel_on_interrupt, |interrupt| {
+            self.internal
+                .wait_for_zero_noise_extrapolation_interruptible(measurement, &job, interrupt)
+        })
+    }
+
+    /// Waits for a job with the GIL released.
+    fn wait_releasing_gil(
+        &self,
+        py: Python,
+        job: &AqtJobHandle,
+        cancel_on_interrupt: bool,
+    ) -> Result<Registers, RunError> {
+        self.interruptible_releasing_gil(py, job, cancel_on_interrupt, |interrupt| {
+            self.internal.wait_for_job_interruptible(job, interrupt)
+        })
+    }
+
+    /// Waits for the counts of a job with the GIL released.
+    fn wait_counts_releasing_gil(
+        &self,
+        py: Python,
+        job: &AqtJobHandle,
+        cancel_on_interrupt: bool,
+    ) -> Result<HashMap<String, AqtCounts>, RunError> {
+        self.interruptible_releasing_gil(py, job, cancel_on_interrupt, |interrupt| {
+            self.internal
+                .wait_for_job_counts_interruptible(job, interrupt)
+        })
+    }
+
+    /// Runs an interruptible wait for a job with the GIL released.
+    ///
+    /// Python signal handlers are run regularly while waiting so that a KeyboardInterrupt aborts the wait.
+    fn interruptible_releasing_gil<R, W>(
+        &self,
+        py: Python,
+        job: &AqtJobHandle,
+        cancel_on_interrupt: bool,
+        wait: W,
+    ) -> Result<R, RunError>
+    where
+        R: Send,
+        W: FnOnce(
+                &mut dyn FnMut() -> Result<(), RoqoqoBackendError>,
+            ) -> Result<R, RoqoqoBackendError>
+            + Send,
+    {
+        let mut interrupt: Option<PyErr> = None;
+        let result = py.allow_threads(|| {
+            wait(&mut || {
+                Python::with_gil(|py| py.check_signals()).map_err(|err| {
+                    interrupt = Some(err);
+                    RoqoqoBackendError::GenericError {
+                        msg: "Waiting for AQT job was interrupted".to_string(),
+                    }
+                })
+            })
+        });
+        match interrupt {
+            Some(err) => {
+                if cancel_on_interrupt {
+                    // The interrupt is reported to the user, a failed cancellation must not hide it
+                    let _ = py.allow_threads(|| self.internal.cancel_job(job));
+                }
+                Err(RunError::Interrupted(err))
+            }
+            None => result.map_err(RunError::Backend),
+        }
+    }
+}
+
+/// Converts the metadata of a job to a Python dict.
+fn metadata_into_py(py: Python, metadata: &JobMetadata) -> PyResult<Py<PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("job_id", &metadata.job_id)?;
+    dict.set_item("resource_id", &metadata.resource_id)?;
+    dict.set_item("workspace_id", &metadata.workspace_id)?;
+    dict.set_item("label", &metadata.label)?;
+    dict.set_item("submitted", metadata.submitted)?;
+    dict.set_item("finished", metadata.finished)?;
+    Ok(dict.unbind())
+}
+
+/// Converts the name of a bit ordering to [BitOrdering].
+fn bit_ordering_from_str(bit_ordering: &str) -> PyResult<BitOrdering> {
+    match bit_ordering {
+        "qubit_zero_first" => Ok(BitOrdering::QubitZeroFirst),
+        "qubit_zero_last" => Ok(BitOrdering::QubitZeroLast),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown bit ordering {bit_ordering}"
+        ))),
+    }
+}
+
+/// Converts the counts of the readout registers to Python counts and raw integer samples.
+fn counts_into_py(
+    py: Python,
+    counts: HashMap<String, AqtCounts>,
+    bit_ordering: BitOrdering,
+    integer_keys: bool,
+) -> Counts {
+    let histograms = counts
+        .iter()
+        .map(|(name, counts)| {
+            let histogram = if integer_keys {
+                counts.integer_counts(bit_ordering).into_py(py)
+            } else {
+                counts.counts(bit_ordering).into_py(py)
+            };
+            (name.clone(), histogram)
+        })
+        .collect();
+    let samples = counts
+        .into_iter()
+        .map(|(name, counts)| (name, counts.samples))
+        .collect();
+    (histograms, samples)
+}
+
+/// Schedules a blocking method of the Backend in the default executor of the running asyncio event loop.
+fn run_in_executor<'py>(
+    backend: &Bound<'py, BackendWrapper>,
+    method: &str,
+    argument: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = backend.py();
+    let event_loop = py
+        .import_bound("asyncio")?
+        .call_method0("get_running_loop")
+        .map_err(|err| PyRuntimeError::new_err(format!("No running event loop {err:?}")))?;
+    event_loop.call_method1(
+        "run_in_executor",
+        (py.None(), backend.getattr(method)?, argument),
+    )
+}
+
+/// Convert generic python object to [roqoqo_aqt::Backend].
+///
+/// Fallible conversion of generic python object to [roqoqo_aqt::Backend].
+pub fn convert_into_backend(
+    input: &Bound<PyAny>,
+) -> Result<Backend<AnyAqtDevice>, QoqoBackendError> {
+    if let Ok(try_downcast) = input.extract::<BackendWrapper>() {
+        Ok(try_downcast.internal)
+    } else {
+        // Everything that follows tries to extract the circuit when two separately
+        // compiled python packages are involved
+        let get_bytes = input
+            .call_method0("_enum_to_bincode")
+            .map_err(|_| QoqoBackendError::CannotExtractObject)?;
+        let bytes = get_bytes
+            .extract::<Vec<u8>>()
+            .map_err(|_| QoqoBackendError::CannotExtractObject)?;
+        deserialize_backend(&bytes).map_err(|_| QoqoBackendError::CannotExtractObject)
+    }
+}
+
+/// Deserializes the bincode representation of a Backend, falling back to the layout of version 0.8.0.
+fn deserialize_backend(bytes: &[u8]) -> bincode::Result<Backend<AnyAqtDevice>> {
+    deserialize(bytes).or_else(|err| {
+        deserialize::<LegacyBackend>(bytes)
+            .map(Backend::from)
+            .map_err(|_| err)
+    })
 }
@@ -14,7 +14,10 @@ use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyByteArray;
 use qoqo::QoqoBackendError;
-use roqoqo_aqt::{devices::AqtDevice, AqtApi};
+use roqoqo_aqt::{
+    devices::{AqtDevice, LegacyAqtDevice},
+    AqtApi,
+};
 
 /// AQT quantum simulator device
 ///
@@ -32,10 +35,12 @@ impl AqtDeviceWrapper {
     ///
     /// Args:
     ///     number_qubits (int): Number of qubits that should be simulated
+    ///     remote_host (Optional[str]): URL of an endpoint replacing the AQT cloud, for example a mock server in tests
     #[new]
-    pub fn new(number_qubits: usize) -> Self {
+    #[pyo3(signature = (number_qubits, remote_host = None))]
+    pub fn new(number_qubits: usize, remote_host: Option<String>) -> Self {
         Self {
-            internal: AqtDevice::new(number_qubits),
+            internal: AqtDevice::new(number_qubits).set_remote_host(remote_host),
         }
     }
 
@@ -73,6 +78,8 @@ impl AqtDeviceWrapper {
 
     /// Convert the bincode representation of the AqtDevice to a AqtDevice using the [bincode] crate.
     ///
+    /// AqtDevices serialized with version 0.8.0 are also accepted.
+    ///
     /// Args:
     ///     input (ByteArray): The serialized AqtDevice (in [bincode] form).
     ///
@@ -89,7 +96,7 @@ impl AqtDeviceWrapper {
             .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
 
         Ok(AqtDeviceWrapper {
-            internal: deserialize(&bytes[..])
+            internal: deserialize_device(&bytes)
                 .map_err(|_| PyValueError::new_err("Input cannot be deserialized to AqtDevice"))?,
         })
     }
@@ -168,5 +175,14 @@ pub fn convert_into_device(input: &Bound<PyAny>) -> Result<AqtDevice, QoqoBacken
     let bytes = get_bytes
         .extract::<Vec<u8>>()
         .map_err(|_| QoqoBackendError::CannotExtractObject)?;
-    deserialize(&bytes[..]).map_err(|_| QoqoBackendError::CannotExtractObject)
+    deserialize_device(&bytes).map_err(|_| QoqoBackendError::CannotExtractObject)
+}
+
+/// Deserializes the bincode representation of an AqtDevice, falling back to the layout of version 0.8.0.
+fn deserialize_device(bytes: &[u8]) -> bincode::Result<AqtDevice> {
+    deserialize(bytes).or_else(|err| {
+        deserialize::<LegacyAqtDevice>(bytes)
+            .map(AqtDevice::from)
+            .map_err(|_| err)
+    })
 }
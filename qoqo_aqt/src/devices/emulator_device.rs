@@ -0,0 +1,328 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::convert_into_device;
+use bincode::{deserialize, serialize};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyByteArray;
+use roqoqo_aqt::{
+    devices::{AqtNoiseModel, EmulatorDevice},
+    AqtApi,
+};
+
+/// Noise model of trapped-ion hardware used by the EmulatorDevice
+///
+/// All errors are applied stochastically in every simulated trajectory, one trajectory is simulated per measurement.
+#[pyclass(name = "AqtNoiseModel", module = "qoqo_aqt")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AqtNoiseModelWrapper {
+    /// Internal storage of [roqoqo_aqt::devices::AqtNoiseModel]
+    pub internal: AqtNoiseModel,
+}
+
+#[pymethods]
+impl AqtNoiseModelWrapper {
+    /// Create new trapped-ion noise model, all probabilities default to zero.
+    ///
+    /// Args:
+    ///     single_qubit_depolarising (float): Probability of a random Pauli error after each R gate
+    ///     ms_gate_infidelity (float): Probability of a random two-qubit Pauli error after each RXX gate
+    ///     dephasing (float): Probability of a Z error on every qubit that is idle during a gate
+    ///     preparation_error (float): Probability that a qubit is prepared in the one state
+    ///     measurement_error (float): Probability that the measured value of a qubit is flipped
+    ///
+    /// Raises:
+    ///     ValueError: A probability is not in the range [0, 1]
+    #[new]
+    #[pyo3(signature = (single_qubit_depolarising = 0.0, ms_gate_infidelity = 0.0, dephasing = 0.0, preparation_error = 0.0, measurement_error = 0.0))]
+    pub fn new(
+        single_qubit_depolarising: f64,
+        ms_gate_infidelity: f64,
+        dephasing: f64,
+        preparation_error: f64,
+        measurement_error: f64,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            internal: AqtNoiseModel::new(
+                single_qubit_depolarising,
+                ms_gate_infidelity,
+                dephasing,
+                preparation_error,
+                measurement_error,
+            )
+            .map_err(|err| PyValueError::new_err(format!("Invalid noise model {err:?}")))?,
+        })
+    }
+
+    /// Return a copy of the AqtNoiseModel (copy here produces a deepcopy).
+    ///
+    /// Returns:
+    ///     AqtNoiseModel: A deep copy of self.
+    pub fn __copy__(&self) -> AqtNoiseModelWrapper {
+        self.clone()
+    }
+
+    /// Return a deep copy of the AqtNoiseModel.
+    ///
+    /// Returns:
+    ///     AqtNoiseModel: A deep copy of self.
+    pub fn __deepcopy__(&self, _memodict: Py<PyAny>) -> AqtNoiseModelWrapper {
+        self.clone()
+    }
+
+    /// Return the json representation of the AqtNoiseModel.
+    ///
+    /// Returns:
+    ///     str: The serialized form of AqtNoiseModel.
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize AqtNoiseModel to json.
+    fn to_json(&self) -> PyResult<String> {
+        let serialized = serde_json::to_string(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize AqtNoiseModel to json"))?;
+        Ok(serialized)
+    }
+
+    /// Convert the json representation of a AqtNoiseModel to a AqtNoiseModel.
+    ///
+    /// Args:
+    ///     input (str): The serialized AqtNoiseModel in json form.
+    ///
+    /// Returns:
+    ///     AqtNoiseModel: The deserialized AqtNoiseModel.
+    ///
+    /// Raises:
+    ///     ValueError: Input cannot be deserialized to AqtNoiseModel.
+    #[staticmethod]
+    fn from_json(input: &str) -> PyResult<AqtNoiseModelWrapper> {
+        Ok(AqtNoiseModelWrapper {
+            internal: serde_json::from_str(input).map_err(|_| {
+                PyValueError::new_err("Input cannot be deserialized to AqtNoiseModel")
+            })?,
+        })
+    }
+
+    /// Return the probability of a random Pauli error after each R gate.
+    ///
+    /// Returns:
+    ///     float: The probability.
+    pub fn single_qubit_depolarising(&self) -> f64 {
+        self.internal.single_qubit_depolarising()
+    }
+
+    /// Return the probability of a random two-qubit Pauli error after each RXX gate.
+    ///
+    /// Returns:
+    ///     float: The probability.
+    pub fn ms_gate_infidelity(&self) -> f64 {
+        self.internal.ms_gate_infidelity()
+    }
+
+    /// Return the probability of a Z error on every qubit that is idle during a gate.
+    ///
+    /// Returns:
+    ///     float: The probability.
+    pub fn dephasing(&self) -> f64 {
+        self.internal.dephasing()
+    }
+
+    /// Return the probability that a qubit is prepared in the one state.
+    ///
+    /// Returns:
+    ///     float: The probability.
+    pub fn preparation_error(&self) -> f64 {
+        self.internal.preparation_error()
+    }
+
+    /// Return the probability that the measured value of a qubit is flipped.
+    ///
+    /// Returns:
+    ///     float: The probability.
+    pub fn measurement_error(&self) -> f64 {
+        self.internal.measurement_error()
+    }
+}
+
+/// Local emulator of noisy AQT trapped-ion hardware
+///
+/// Simulates the translated AQT instructions locally, applying the errors of an AqtNoiseModel,
+/// so results of AQT hardware can be predicted without the AQT cloud or an access token.
+#[pyclass(name = "EmulatorDevice", module = "qoqo_aqt")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmulatorDeviceWrapper {
+    /// Internal storage of [roqoqo_aqt::devices::EmulatorDevice]
+    pub internal: EmulatorDevice,
+}
+
+#[pymethods]
+impl EmulatorDeviceWrapper {
+    /// Create new local emulator device.
+    ///
+    /// Args:
+    ///     number_qubits (int): Number of qubits that should be emulated
+    ///     noise_model (AqtNoiseModel): Noise model applied during the emulation
+    ///     seed (Optional[int]): Seed of the random number generator, results are not reproducible when None
+    #[new]
+    #[pyo3(signature = (number_qubits, noise_model, seed = None))]
+    pub fn new(number_qubits: usize, noise_model: AqtNoiseModelWrapper, seed: Option<u64>) -> Self {
+        Self {
+            internal: EmulatorDevice::new(number_qubits, noise_model.internal, seed),
+        }
+    }
+
+    /// Create a local emulator of an AqtDevice.
+    ///
+    /// Only the number of qubits is taken from the AqtDevice, the remote host is not used.
+    ///
+    /// Args:
+    ///     device (AqtDevice): The AQT device whose number of qubits is emulated
+    ///     noise_model (AqtNoiseModel): Noise model applied during the emulation
+    ///     seed (Optional[int]): Seed of the random number generator, results are not reproducible when None
+    ///
+    /// Returns:
+    ///     EmulatorDevice: The emulator of the device.
+    ///
+    /// Raises:
+    ///     TypeError: Device Parameter is not AqtDevice
+    #[staticmethod]
+    #[pyo3(signature = (device, noise_model, seed = None))]
+    pub fn from_device(
+        device: &Bound<PyAny>,
+        noise_model: AqtNoiseModelWrapper,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let device = convert_into_device(device).map_err(|err| {
+            PyTypeError::new_err(format!("Device Parameter is not AqtDevice {err:?}"))
+        })?;
+        Ok(Self {
+            internal: EmulatorDevice::from_device(&device, noise_model.internal, seed),
+        })
+    }
+
+    /// Return a copy of the EmulatorDevice (copy here produces a deepcopy).
+    ///
+    /// Returns:
+    ///     EmulatorDevice: A deep copy of self.
+    pub fn __copy__(&self) -> EmulatorDeviceWrapper {
+        self.clone()
+    }
+
+    /// Return a deep copy of the EmulatorDevice.
+    ///
+    /// Returns:
+    ///     EmulatorDevice: A deep copy of self.
+    pub fn __deepcopy__(&self, _memodict: Py<PyAny>) -> EmulatorDeviceWrapper {
+        self.clone()
+    }
+
+    /// Return the bincode representation of the EmulatorDevice using the [bincode] crate.
+    ///
+    /// Returns:
+    ///     ByteArray: The serialized EmulatorDevice (in [bincode] form).
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize EmulatorDevice to bytes.
+    pub fn to_bincode(&self) -> PyResult<Py<PyByteArray>> {
+        let serialized = serialize(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize EmulatorDevice to bytes"))?;
+        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
+            PyByteArray::new_bound(py, &serialized[..]).into()
+        });
+        Ok(b)
+    }
+
+    /// Convert the bincode representation of the EmulatorDevice to a EmulatorDevice using the [bincode] crate.
+    ///
+    /// Args:
+    ///     input (ByteArray): The serialized EmulatorDevice (in [bincode] form).
+    ///
+    /// Returns:
+    ///     EmulatorDevice: The deserialized EmulatorDevice.
+    ///
+    /// Raises:
+    ///     TypeError: Input cannot be converted to byte array.
+    ///     ValueError: Input cannot be deserialized to EmulatorDevice.
+    #[staticmethod]
+    pub fn from_bincode(input: &Bound<PyAny>) -> PyResult<EmulatorDeviceWrapper> {
+        let bytes = input
+            .extract::<Vec<u8>>()
+            .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
+
+        Ok(EmulatorDeviceWrapper {
+            internal: deserialize(&bytes[..]).map_err(|_| {
+                PyValueError::new_err("Input cannot be deserialized to EmulatorDevice")
+            })?,
+        })
+    }
+
+    /// Return the json representation of the EmulatorDevice.
+    ///
+    /// Returns:
+    ///     str: The serialized form of EmulatorDevice.
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize EmulatorDevice to json.
+    fn to_json(&self) -> PyResult<String> {
+        let serialized = serde_json::to_string(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize EmulatorDevice to json"))?;
+        Ok(serialized)
+    }
+
+    /// Convert the json representation of a EmulatorDevice to a EmulatorDevice.
+    ///
+    /// Args:
+    ///     input (str): The serialized EmulatorDevice in json form.
+    ///
+    /// Returns:
+    ///     EmulatorDevice: The deserialized EmulatorDevice.
+    ///
+    /// Raises:
+    ///     ValueError: Input cannot be deserialized to EmulatorDevice.
+    #[staticmethod]
+    fn from_json(input: &str) -> PyResult<EmulatorDeviceWrapper> {
+        Ok(EmulatorDeviceWrapper {
+            internal: serde_json::from_str(input).map_err(|_| {
+                PyValueError::new_err("Input cannot be deserialized to EmulatorDevice")
+            })?,
+        })
+    }
+
+    /// Return number of qubits emulated by the EmulatorDevice.
+    ///
+    /// Returns:
+    ///     int: The number of qubits.
+    ///
+    pub fn number_qubits(&self) -> usize {
+        self.internal.number_qubits()
+    }
+
+    /// Return the seed of the random number generator used to sample errors and measurement results.
+    ///
+    /// Returns:
+    ///     Optional[int]: The seed, None when results are not reproducible.
+    ///
+    pub fn seed(&self) -> Option<u64> {
+        self.internal.seed()
+    }
+
+    /// Return the noise model applied during the emulation.
+    ///
+    /// Returns:
+    ///     AqtNoiseModel: The noise model.
+    ///
+    pub fn noise_model(&self) -> AqtNoiseModelWrapper {
+        AqtNoiseModelWrapper {
+            internal: self.internal.noise_model.clone(),
+        }
+    }
+}
@@ -17,7 +17,15 @@
 mod aqt_device;
 pub use aqt_device::*;
 
+mod simulator_device;
+pub use simulator_device::*;
+
+mod emulator_device;
+pub use emulator_device::*;
+
 use pyo3::prelude::*;
+use qoqo::QoqoBackendError;
+use roqoqo_aqt::devices::AnyAqtDevice;
 
 /// AQT Devices
 // ///
@@ -27,11 +35,29 @@ use pyo3::prelude::*;
 // /// .. autosummary::
 // ///    :toctree: generated/
 // ///
+// ///    AqtDevice
 // ///    SimulatorDevice
-// ///    NoisySimulatorDevice
+// ///    EmulatorDevice
+// ///    AqtNoiseModel
 // ///
 #[pymodule]
 pub fn aqt_devices(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<AqtDeviceWrapper>()?;
+    m.add_class::<SimulatorDeviceWrapper>()?;
+    m.add_class::<EmulatorDeviceWrapper>()?;
+    m.add_class::<AqtNoiseModelWrapper>()?;
     Ok(())
 }
+
+/// Convert generic python object to any device the AQT backend can run on.
+///
+/// Accepts an AqtDevice, a SimulatorDevice or an EmulatorDevice.
+pub fn convert_into_any_device(input: &Bound<PyAny>) -> Result<AnyAqtDevice, QoqoBackendError> {
+    if let Ok(try_downcast) = input.extract::<SimulatorDeviceWrapper>() {
+        return Ok(try_downcast.internal.into());
+    }
+    if let Ok(try_downcast) = input.extract::<EmulatorDeviceWrapper>() {
+        return Ok(try_downcast.internal.into());
+    }
+    convert_into_device(input).map(AnyAqtDevice::from)
+}
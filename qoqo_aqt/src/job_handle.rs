@@ -0,0 +1,140 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bincode::{deserialize, serialize};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyByteArray;
+use roqoqo_aqt::AqtJobHandle;
+
+/// Handle of a job submitted to an AQT device.
+///
+/// Returned by Backend.submit_circuit and used to poll or wait for the results of the job.
+#[pyclass(name = "JobHandle", module = "qoqo_aqt")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JobHandleWrapper {
+    /// Internal storage of [roqoqo_aqt::AqtJobHandle]
+    pub internal: AqtJobHandle,
+}
+
+#[pymethods]
+impl JobHandleWrapper {
+    /// Return the id of the job assigned by the AQT device.
+    ///
+    /// Returns:
+    ///     str: The id of the job.
+    pub fn job_id(&self) -> String {
+        self.internal.job_id().to_string()
+    }
+
+    /// Return the name of the bit register the measurement results of the first circuit in the job are written to.
+    ///
+    /// Returns:
+    ///     str: The name of the readout register.
+    pub fn readout(&self) -> String {
+        self.internal.readout().to_string()
+    }
+
+    /// Return the names of the bit registers the measurement results of each circuit in the job are written to.
+    ///
+    /// Returns:
+    ///     List[str]: The names of the readout registers.
+    pub fn readouts(&self) -> Vec<String> {
+        self.internal.readouts().to_vec()
+    }
+
+    /// Return a copy of the JobHandle (copy here produces a deepcopy).
+    ///
+    /// Returns:
+    ///     JobHandle: A deep copy of self.
+    pub fn __copy__(&self) -> JobHandleWrapper {
+        self.clone()
+    }
+
+    /// Return a deep copy of the JobHandle.
+    ///
+    /// Returns:
+    ///     JobHandle: A deep copy of self.
+    pub fn __deepcopy__(&self, _memodict: Py<PyAny>) -> JobHandleWrapper {
+        self.clone()
+    }
+
+    /// Return the bincode representation of the JobHandle using the [bincode] crate.
+    ///
+    /// Returns:
+    ///     ByteArray: The serialized JobHandle (in [bincode] form).
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize JobHandle to bytes.
+    pub fn to_bincode(&self) -> PyResult<Py<PyByteArray>> {
+        let serialized = serialize(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize JobHandle to bytes"))?;
+        let b: Py<PyByteArray> = Python::with_gil(|py| -> Py<PyByteArray> {
+            PyByteArray::new_bound(py, &serialized[..]).into()
+        });
+        Ok(b)
+    }
+
+    /// Convert the bincode representation of the JobHandle to a JobHandle using the [bincode] crate.
+    ///
+    /// Args:
+    ///     input (ByteArray): The serialized JobHandle (in [bincode] form).
+    ///
+    /// Returns:
+    ///     JobHandle: The deserialized JobHandle.
+    ///
+    /// Raises:
+    ///     TypeError: Input cannot be converted to byte array.
+    ///     ValueError: Input cannot be deserialized to JobHandle.
+    #[staticmethod]
+    pub fn from_bincode(input: &Bound<PyAny>) -> PyResult<JobHandleWrapper> {
+        let bytes = input
+            .extract::<Vec<u8>>()
+            .map_err(|_| PyTypeError::new_err("Input cannot be converted to byte array"))?;
+
+        Ok(JobHandleWrapper {
+            internal: deserialize(&bytes[..])
+                .map_err(|_| PyValueError::new_err("Input cannot be deserialized to JobHandle"))?,
+        })
+    }
+
+    /// Return the json representation of the JobHandle.
+    ///
+    /// Returns:
+    ///     str: The serialized form of JobHandle.
+    ///
+    /// Raises:
+    ///     ValueError: Cannot serialize JobHandle to json.
+    fn to_json(&self) -> PyResult<String> {
+        let serialized = serde_json::to_string(&self.internal)
+            .map_err(|_| PyValueError::new_err("Cannot serialize JobHandle to json"))?;
+        Ok(serialized)
+    }
+
+    /// Convert the json representation of a JobHandle to a JobHandle.
+    ///
+    /// Args:
+    ///     input (str): The serialized JobHandle in json form.
+    ///
+    /// Returns:
+    ///     JobHandle: The deserialized JobHandle.
+    ///
+    /// Raises:
+    ///     ValueError: Input cannot be deserialized to JobHandle.
+    #[staticmethod]
+    fn from_json(input: &str) -> PyResult<JobHandleWrapper> {
+        Ok(JobHandleWrapper {
+            internal: serde_json::from_str(input)
+                .map_err(|_| PyValueError::new_err("Input cannot be deserialized to JobHandle"))?,
+        })
+    }
+}
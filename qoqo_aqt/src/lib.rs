@@ -28,6 +28,19 @@ pub mod devices;
 mod backend;
 pub use backend::{convert_into_backend, BackendWrapper};
 
+mod job_handle;
+pub use job_handle::JobHandleWrapper;
+
+#[cfg(feature = "mock-server")]
+mod mock_server;
+#[cfg(feature = "mock-server")]
+pub use mock_server::MockServerWrapper;
+
+#[cfg(feature = "tracing")]
+mod python_logging;
+#[cfg(feature = "tracing")]
+pub use python_logging::enable_tracing;
+
 /// AQT python interface
 ///
 /// Provides the devices that are used to execute quantum programs with the AQT backend, as well as the AQT backend.
@@ -36,11 +49,17 @@ pub use backend::{convert_into_backend, BackendWrapper};
 ///     :toctree: generated/
 ///
 ///     Backend
+///     JobHandle
 ///     devices
 ///
 #[pymodule]
 fn qoqo_aqt(_py: Python, module: &Bound<PyModule>) -> PyResult<()> {
     module.add_class::<BackendWrapper>()?;
+    module.add_class::<JobHandleWrapper>()?;
+    #[cfg(feature = "mock-server")]
+    module.add_class::<MockServerWrapper>()?;
+    #[cfg(feature = "tracing")]
+    module.add_function(wrap_pyfunction!(enable_tracing, module)?)?;
 
     let wrapper = wrap_pymodule!(devices::aqt_devices);
     module.add_wrapped(wrapper)?;
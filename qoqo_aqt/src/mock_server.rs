@@ -0,0 +1,166 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::devices::AqtDeviceWrapper;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use roqoqo_aqt::mock_server::{MockAqtServer, MockServerConfig};
+use std::time::Duration;
+
+/// Mock of the AQT REST API for testing without the AQT cloud.
+///
+/// The server runs in a background thread until the object is deleted.
+/// Submitted jobs are simulated locally and pass through the states queued, ongoing and finished.
+///
+/// Args:
+///     resource_id (str): The id of the resource served by the mock server.
+///     available_qubits (int): The number of qubits of the resource.
+///     online (bool): Whether the resource is reported as online.
+///     queued_polls (int): The number of status queries for which a job is reported as queued.
+///     ongoing_polls (int): The number of status queries for which a job is reported as ongoing.
+///     latency (float): The delay before every response in seconds.
+///     seed (Optional[int]): The seed of the random number generator used to simulate jobs.
+///
+/// Raises:
+///     ValueError: The latency is negative or not finite
+///     RuntimeError: The mock server could not be started
+#[pyclass(name = "MockServer", module = "qoqo_aqt")]
+#[derive(Debug)]
+pub struct MockServerWrapper {
+    /// Internal storage of [roqoqo_aqt::mock_server::MockAqtServer]
+    pub internal: MockAqtServer,
+}
+
+#[pymethods]
+impl MockServerWrapper {
+    /// Start a new mock server.
+    #[new]
+    #[pyo3(signature = (
+        resource_id = "simulator_noise".to_string(),
+        available_qubits = 20,
+        online = true,
+        queued_polls = 1,
+        ongoing_polls = 1,
+        latency = 0.0,
+        seed = None,
+    ))]
+    pub fn new(
+        resource_id: String,
+        available_qubits: usize,
+        online: bool,
+        queued_polls: usize,
+        ongoing_polls: usize,
+        latency: f64,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let config = MockServerConfig {
+            resource_id,
+            available_qubits,
+            online,
+            queued_polls,
+            ongoing_polls,
+            latency: duration_from_seconds(latency)?,
+            noise_model: None,
+            seed,
+        };
+        let internal = MockAqtServer::start(config).map_err(|err| {
+            PyRuntimeError::new_err(format!("Could not start mock server {err:?}"))
+        })?;
+        Ok(Self { internal })
+    }
+
+    /// Return the URL of the mock server.
+    ///
+    /// Returns:
+    ///     str: The URL that replaces the remote host of a device.
+    pub fn remote_host(&self) -> String {
+        self.internal.remote_host()
+    }
+
+    /// Return an AqtDevice sending its requests to the mock server.
+    ///
+    /// Args:
+    ///     number_qubits (int): The number of qubits of the device.
+    ///
+    /// Returns:
+    ///     AqtDevice: The device using the mock server as remote host.
+    pub fn device(&self, number_qubits: usize) -> AqtDeviceWrapper {
+        AqtDeviceWrapper {
+            internal: self.internal.device(number_qubits),
+        }
+    }
+
+    /// Answer the next submissions with an HTTP error instead of accepting the jobs.
+    ///
+    /// Args:
+    ///     number_submissions (int): The number of failing submissions.
+    ///     status_code (int): The HTTP status code returned for the failing submissions.
+    #[pyo3(signature = (number_submissions, status_code = 500))]
+    pub fn fail_next_submissions(&self, number_submissions: usize, status_code: u16) {
+        self.internal
+            .fail_next_submissions(number_submissions, status_code)
+    }
+
+    /// Let the next accepted jobs end with the status error instead of finishing.
+    ///
+    /// Args:
+    ///     number_jobs (int): The number of failing jobs.
+    ///     message (str): The error message reported for the failing jobs.
+    pub fn fail_next_jobs(&self, number_jobs: usize, message: &str) {
+        self.internal.fail_next_jobs(number_jobs, message)
+    }
+
+    /// Set whether the resource is reported as online.
+    ///
+    /// Args:
+    ///     online (bool): Whether the resource is online.
+    pub fn set_online(&self, online: bool) {
+        self.internal.set_online(online)
+    }
+
+    /// Set the delay before every response of the server.
+    ///
+    /// Args:
+    ///     latency (float): The delay in seconds.
+    ///
+    /// Raises:
+    ///     ValueError: The latency is negative or not finite
+    pub fn set_latency(&self, latency: f64) -> PyResult<()> {
+        self.internal.set_latency(duration_from_seconds(latency)?);
+        Ok(())
+    }
+
+    /// Return the ids of all jobs submitted to the server.
+    ///
+    /// Returns:
+    ///     List[str]: The job ids in order of submission.
+    pub fn job_ids(&self) -> Vec<String> {
+        self.internal.job_ids()
+    }
+
+    /// Return the current status of a submitted job.
+    ///
+    /// Args:
+    ///     job_id (str): The id of the job.
+    ///
+    /// Returns:
+    ///     Optional[str]: The status of the job, None when the job does not exist.
+    pub fn job_status(&self, job_id: &str) -> Option<String> {
+        self.internal.job_status(job_id)
+    }
+}
+
+/// Converts a time in seconds from Python into a duration
+fn duration_from_seconds(seconds: f64) -> PyResult<Duration> {
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|err| PyValueError::new_err(format!("Latency is not a valid duration {err:?}")))
+}
@@ -0,0 +1,206 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Routing of the tracing events of roqoqo-aqt into Python logging
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Target prefix of the spans and events forwarded to Python logging
+const TARGET: &str = "roqoqo_aqt";
+
+/// Most verbose level forwarded to Python logging, see [level_rank], 0 disables forwarding
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Result of installing the layer as global tracing subscriber, set by the first call of [enable_tracing]
+static INSTALLED: OnceLock<Result<(), String>> = OnceLock::new();
+
+/// Route the tracing spans and events of the AQT backend into Python logging.
+///
+/// Requires qoqo_aqt to be built with the `tracing` feature. Events are logged to the logger named
+/// after the Rust module emitting them, e.g. `roqoqo_aqt.backend`, with the fields of the event
+/// and of the enclosing spans appended to the message. Calling the function again changes the level.
+///
+/// Args:
+///     level (str): The most verbose level forwarded, one of "trace", "debug", "info", "warning", "error" or "off".
+///
+/// Raises:
+///     ValueError: Unknown level
+///     RuntimeError: Another global tracing subscriber has already been installed
+#[pyfunction]
+#[pyo3(signature = (level = "info"))]
+pub fn enable_tracing(level: &str) -> PyResult<()> {
+    let rank = match level.to_lowercase().as_str() {
+        "off" => 0,
+        "error" => level_rank(&Level::ERROR),
+        "warn" | "warning" => level_rank(&Level::WARN),
+        "info" => level_rank(&Level::INFO),
+        "debug" => level_rank(&Level::DEBUG),
+        "trace" => level_rank(&Level::TRACE),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown tracing level {level}, expected trace, debug, info, warning, error or off"
+            )))
+        }
+    };
+    INSTALLED
+        .get_or_init(|| {
+            let subscriber = tracing_subscriber::registry().with(PythonLoggingLayer);
+            tracing::subscriber::set_global_default(subscriber).map_err(|err| format!("{err:?}"))
+        })
+        .clone()
+        .map_err(|err| {
+            PyRuntimeError::new_err(format!(
+                "Could not install tracing subscriber forwarding to Python logging {err}"
+            ))
+        })?;
+    MAX_LEVEL.store(rank, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Returns the rank of a tracing level, more verbose levels have higher ranks
+fn level_rank(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 1,
+        Level::WARN => 2,
+        Level::INFO => 3,
+        Level::DEBUG => 4,
+        Level::TRACE => 5,
+    }
+}
+
+/// Returns the Python logging level of a tracing level
+fn python_level(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 40,
+        Level::WARN => 30,
+        Level::INFO => 20,
+        Level::DEBUG => 10,
+        Level::TRACE => 5,
+    }
+}
+
+/// Tracing layer forwarding the events of roqoqo-aqt to Python logging
+struct PythonLoggingLayer;
+
+/// Formatted fields of a span, stored in the extensions of the span
+struct SpanFields(String);
+
+/// Formats the message and the other fields of a span or an event
+#[derive(Default)]
+struct FieldFormatter {
+    /// The message of an event
+    message: String,
+    /// The other fields as `name=value` separated by spaces
+    fields: String,
+}
+
+impl Visit for FieldFormatter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.push_field(field, value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message.push_str(&format!("{value:?}"));
+        } else {
+            self.push_field(field, &format!("{value:?}"));
+        }
+    }
+}
+
+impl FieldFormatter {
+    /// Appends a field as `name=value`
+    fn push_field(&mut self, field: &Field, value: &str) {
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        self.fields.push_str(&format!("{}={value}", field.name()));
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for PythonLoggingLayer {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // The level can be changed at runtime, so the decision must not be cached
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        metadata.target().starts_with(TARGET)
+            && level_rank(metadata.level()) <= MAX_LEVEL.load(Ordering::Relaxed)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut formatter = FieldFormatter::default();
+        attrs.record(&mut formatter);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(formatter.fields));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut formatter = FieldFormatter::default();
+        values.record(&mut formatter);
+        if let Some(span) = ctx.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+                if !fields.0.is_empty() && !formatter.fields.is_empty() {
+                    fields.0.push(' ');
+                }
+                fields.0.push_str(&formatter.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut formatter = FieldFormatter::default();
+        event.record(&mut formatter);
+        let mut message = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                message.push_str(span.name());
+                if let Some(fields) = span.extensions().get::<SpanFields>() {
+                    if !fields.0.is_empty() {
+                        message.push_str(&format!("{{{}}}", fields.0));
+                    }
+                }
+                message.push_str(": ");
+            }
+        }
+        message.push_str(&formatter.message);
+        if !formatter.fields.is_empty() {
+            message.push_str(&format!(" {}", formatter.fields));
+        }
+        let metadata = event.metadata();
+        let logger_name = metadata.target().replace("::", ".");
+        // Logging must never make the backend fail, errors raised by Python handlers are dropped
+        let _ = Python::with_gil(|py| -> PyResult<()> {
+            PyModule::import_bound(py, "logging")?
+                .call_method1("getLogger", (logger_name,))?
+                .call_method1("log", (python_level(metadata.level()), message))?;
+            Ok(())
+        });
+    }
+}
@@ -12,15 +12,23 @@
 
 //! Integration test for public API of Basis rotation measurement
 
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use pyo3::Python;
-use qoqo::measurements::ClassicalRegisterWrapper;
-use qoqo::CircuitWrapper;
-use qoqo_aqt::devices::AqtDeviceWrapper;
-use qoqo_aqt::BackendWrapper;
-use roqoqo::measurements::ClassicalRegister;
+use qoqo::measurements::{ClassicalRegisterWrapper, PauliZProductWrapper};
+use qoqo::{CircuitWrapper, QuantumProgramWrapper};
+use qoqo_aqt::devices::{
+    AqtDeviceWrapper, AqtNoiseModelWrapper, EmulatorDeviceWrapper, SimulatorDeviceWrapper,
+};
+use qoqo_aqt::{BackendWrapper, JobHandleWrapper};
+use roqoqo::measurements::{ClassicalRegister, PauliZProduct, PauliZProductInput};
 use roqoqo::operations;
 use roqoqo::Circuit;
+use roqoqo::QuantumProgram;
+use roqoqo_aqt::devices::{AnyAqtDevice, AqtDevice, LegacyAqtDevice};
+use roqoqo_aqt::{Backend, LegacyBackend};
+use std::collections::HashMap;
 use std::env;
 
 #[test]
@@ -123,3 +131,1121 @@ fn test_running_measurement() {
         })
     }
 }
+
+#[test]
+fn test_running_circuit_local_devices() {
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += operations::PauliX::new(1);
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 5, None);
+    let circuit_wrapper = CircuitWrapper { internal: circuit };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let simulator = py
+            .get_type_bound::<SimulatorDeviceWrapper>()
+            .call1((2, 3))
+            .unwrap();
+        let noise_model = py.get_type_bound::<AqtNoiseModelWrapper>().call0().unwrap();
+        let emulator = py
+            .get_type_bound::<EmulatorDeviceWrapper>()
+            .call1((2, noise_model, 3))
+            .unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        // Local devices do not need an access token
+        for device in [simulator, emulator] {
+            let backend = backend_type.call1((device,)).unwrap();
+            let bits: HashMap<String, Vec<Vec<bool>>> = backend
+                .call_method1("run_circuit", (circuit_wrapper.clone(),))
+                .unwrap()
+                .get_item(0)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(bits["ro"], vec![vec![false, true]; 5]);
+        }
+        let res = backend_type.call1((1_u32, "DUMMY_ACCESS_TOKEN"));
+        assert!(res.unwrap_err().is_instance_of::<PyTypeError>(py));
+    })
+}
+
+#[test]
+fn test_running_with_name() {
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 2, None);
+    let circuit_wrapper = CircuitWrapper {
+        internal: circuit.clone(),
+    };
+    let measurement = ClassicalRegisterWrapper {
+        internal: ClassicalRegister {
+            constant_circuit: None,
+            circuits: vec![circuit],
+        },
+    };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device = py
+            .get_type_bound::<SimulatorDeviceWrapper>()
+            .call1((1, 3))
+            .unwrap();
+        let backend = py
+            .get_type_bound::<BackendWrapper>()
+            .call1((device,))
+            .unwrap();
+        backend.call_method1("set_job_label", ("{name}",)).unwrap();
+        let label = |name: Option<&str>| -> String {
+            let kwargs = PyDict::new_bound(py);
+            kwargs.set_item("name", name).unwrap();
+            backend
+                .call_method(
+                    "run_circuit_with_metadata",
+                    (circuit_wrapper.clone(),),
+                    Some(&kwargs),
+                )
+                .unwrap()
+                .get_item(1)
+                .unwrap()
+                .get_item("label")
+                .unwrap()
+                .extract()
+                .unwrap()
+        };
+        assert_eq!(label(None), "circuit");
+        assert_eq!(label(Some("bell")), "bell");
+
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("name", "scan").unwrap();
+        backend
+            .call_method("run_circuit_counts", (circuit_wrapper,), Some(&kwargs))
+            .unwrap();
+        let bits: HashMap<String, Vec<Vec<bool>>> = backend
+            .call_method("run_measurement_registers", (measurement,), Some(&kwargs))
+            .unwrap()
+            .get_item(0)
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(bits["ro"], vec![vec![false]; 2]);
+    })
+}
+
+#[test]
+fn test_job_handle_json() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let job_type = py.get_type_bound::<JobHandleWrapper>();
+        let job = job_type
+            .call_method1(
+                "from_json",
+                (r#"{"job_id":"dummy_id","readout":"ro","bit_registers":["ro"],"float_registers":[],"complex_registers":[]}"#,),
+            )
+            .unwrap();
+        let job_id = job
+            .call_method0("job_id")
+            .unwrap()
+            .extract::<String>()
+            .unwrap();
+        let readout = job
+            .call_method0("readout")
+            .unwrap()
+            .extract::<String>()
+            .unwrap();
+        let readouts = job
+            .call_method0("readouts")
+            .unwrap()
+            .extract::<Vec<String>>()
+            .unwrap();
+        assert_eq!(job_id, "dummy_id");
+        assert_eq!(readout, "ro");
+        assert_eq!(readouts, vec!["ro".to_string()]);
+
+        let serialised = job.call_method0("to_json").unwrap();
+        let deserialised = job_type
+            .call_method1("from_json", (serialised,))
+            .unwrap()
+            .extract::<JobHandleWrapper>()
+            .unwrap();
+        assert_eq!(deserialised, job.extract::<JobHandleWrapper>().unwrap());
+
+        let serialised = job.call_method0("to_bincode").unwrap();
+        let deserialised = job_type
+            .call_method1("from_bincode", (serialised,))
+            .unwrap()
+            .extract::<JobHandleWrapper>()
+            .unwrap();
+        assert_eq!(deserialised, job.extract::<JobHandleWrapper>().unwrap());
+    })
+}
+
+#[test]
+fn test_async_without_event_loop() {
+    pyo3::prepare_freethreaded_python();
+    let circuit_wrapper = CircuitWrapper {
+        internal: Circuit::new(),
+    };
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((3,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let res = backend.call_method1("run_circuit_async", (circuit_wrapper,));
+        assert!(res.is_err());
+    })
+}
+
+#[test]
+fn test_run_program_wrong_number_of_parameters() {
+    pyo3::prepare_freethreaded_python();
+    let measurement = ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![Circuit::new()],
+    };
+    let program_wrapper = QuantumProgramWrapper {
+        internal: QuantumProgram::ClassicalRegister {
+            measurement,
+            input_parameter_names: vec!["theta".to_string()],
+        },
+    };
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((3,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let res = backend.call_method1("run_program", (program_wrapper, Vec::<f64>::new()));
+        assert!(res.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+#[test]
+fn test_run_program_wrong_type() {
+    pyo3::prepare_freethreaded_python();
+    let registers_program = QuantumProgramWrapper {
+        internal: QuantumProgram::ClassicalRegister {
+            measurement: ClassicalRegister {
+                constant_circuit: None,
+                circuits: vec![Circuit::new()],
+            },
+            input_parameter_names: vec![],
+        },
+    };
+    let expectation_values_program = QuantumProgramWrapper {
+        internal: QuantumProgram::PauliZProduct {
+            measurement: PauliZProduct {
+                constant_circuit: None,
+                circuits: vec![Circuit::new()],
+                input: PauliZProductInput::new(1, false),
+            },
+            input_parameter_names: vec![],
+        },
+    };
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((3,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let res = backend.call_method1("run_program", (registers_program, Vec::<f64>::new()));
+        assert!(res.unwrap_err().is_instance_of::<PyTypeError>(py));
+        let res = backend.call_method1(
+            "run_registers",
+            (expectation_values_program, Vec::<f64>::new()),
+        );
+        assert!(res.unwrap_err().is_instance_of::<PyTypeError>(py));
+        let res = backend.call_method1("run_registers", (1_u32, Vec::<f64>::new()));
+        assert!(res.unwrap_err().is_instance_of::<PyTypeError>(py));
+    })
+}
+
+#[test]
+fn test_run_measurement_wrong_type() {
+    pyo3::prepare_freethreaded_python();
+    let measurement_wrapper = ClassicalRegisterWrapper {
+        internal: ClassicalRegister {
+            constant_circuit: None,
+            circuits: vec![Circuit::new()],
+        },
+    };
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((3,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let res = backend.call_method1("run_measurement", (measurement_wrapper,));
+        assert!(res.unwrap_err().is_instance_of::<PyTypeError>(py));
+        let res = backend.call_method1("run_measurement_registers", (1_u32,));
+        assert!(res.unwrap_err().is_instance_of::<PyTypeError>(py));
+    })
+}
+
+#[test]
+fn test_pragma_emulation_shots() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((3,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let shots: Option<usize> = backend
+            .call_method0("pragma_emulation_shots")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(shots, None);
+        backend
+            .call_method1("set_pragma_emulation_shots", (100,))
+            .unwrap();
+        let shots: Option<usize> = backend
+            .call_method0("pragma_emulation_shots")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(shots, Some(100));
+    })
+}
+
+#[test]
+fn test_poll_interval() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((3,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let poll_interval: f64 = backend
+            .call_method0("poll_interval")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(poll_interval, 20.0);
+        backend.call_method1("set_poll_interval", (0.5,)).unwrap();
+        let poll_interval: f64 = backend
+            .call_method0("poll_interval")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(poll_interval, 0.5);
+        let error = backend.call_method1("set_poll_interval", (-1.0,));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+#[test]
+fn test_circuit_from_aqt_json() {
+    let mut circuit = Circuit::new();
+    circuit += operations::RotateZ::new(0, std::f64::consts::FRAC_PI_2.into());
+    circuit += operations::MolmerSorensenXX::new(0, 1);
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let circuit_wrapper = CircuitWrapper { internal: circuit };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((2,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let json: String = backend
+            .call_method1("to_aqt_json", (circuit_wrapper,))
+            .unwrap()
+            .extract()
+            .unwrap();
+        let reconstructed = backend_type
+            .call_method1("circuit_from_aqt_json", (json.clone(), 2, 10))
+            .unwrap();
+        let reconstructed = reconstructed.downcast::<CircuitWrapper>().unwrap();
+        let json_again: String = backend
+            .call_method1("to_aqt_json", (reconstructed,))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(json_again, json);
+
+        let error = backend_type.call_method1("circuit_from_aqt_json", ("not json",));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+        let error = backend_type.call_method1("circuits_from_aqt_run_data", (json,));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+#[test]
+fn test_translation_report() {
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += operations::CNOT::new(0, 1);
+    circuit += operations::RotateZ::new(0, "phi".into());
+    circuit += operations::PauliX::new(1);
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let circuit_wrapper = CircuitWrapper { internal: circuit };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((2,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let report = backend
+            .call_method1("translation_report", (circuit_wrapper,))
+            .unwrap();
+        let translatable: bool = report.get_item("translatable").unwrap().extract().unwrap();
+        assert!(!translatable);
+        let unsupported_counts: HashMap<String, usize> = report
+            .get_item("unsupported_counts")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(unsupported_counts, HashMap::from([("CNOT".to_string(), 1)]));
+        let position: usize = report
+            .get_item("symbolic_parameters")
+            .unwrap()
+            .get_item(0)
+            .unwrap()
+            .get_item("position")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(position, 2);
+        let native_gate_counts: HashMap<String, usize> = report
+            .get_item("native_gate_counts")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(native_gate_counts.get("R"), Some(&1));
+
+        let error = backend.call_method1("translation_report", (1_u32,));
+        assert!(error.unwrap_err().is_instance_of::<PyTypeError>(py));
+    })
+}
+
+#[test]
+fn test_zero_noise_extrapolation_errors() {
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let crm_wrapper = ClassicalRegisterWrapper {
+        internal: ClassicalRegister {
+            constant_circuit: None,
+            circuits: vec![circuit.clone()],
+        },
+    };
+    let mut input = PauliZProductInput::new(1, false);
+    input.add_pauliz_product("ro".to_string(), vec![0]).unwrap();
+    let pauli_wrapper = PauliZProductWrapper {
+        internal: PauliZProduct {
+            constant_circuit: None,
+            circuits: vec![circuit],
+            input,
+        },
+    };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let result = backend.call_method1(
+            "run_measurement_zero_noise_extrapolation",
+            (crm_wrapper, vec![1.0, 3.0]),
+        );
+        assert!(result.unwrap_err().is_instance_of::<PyTypeError>(py));
+        // The extrapolation needs more scale factors than the order, checked before submitting the job
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("cancel_on_interrupt", true).unwrap();
+        let result = backend.call_method(
+            "run_measurement_zero_noise_extrapolation",
+            (pauli_wrapper, vec![1.0, 3.0], 2),
+            Some(&kwargs),
+        );
+        assert!(result.unwrap_err().is_instance_of::<PyRuntimeError>(py));
+    })
+}
+
+#[test]
+fn test_optimise_instructions() {
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operations::RotateZ::new(0, 0.5.into());
+    circuit += operations::RotateX::new(0, 0.5.into());
+    circuit += operations::RotateZ::new(0, 0.5.into());
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let circuit_wrapper = CircuitWrapper { internal: circuit };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let optimise: bool = backend
+            .call_method0("optimise_instructions")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(!optimise);
+        backend
+            .call_method1("set_optimise_instructions", (true,))
+            .unwrap();
+        let optimise: bool = backend
+            .call_method0("optimise_instructions")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(optimise);
+        let report: HashMap<String, usize> = backend
+            .call_method1("optimisation_report", (circuit_wrapper,))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(report["instructions_before"], 4);
+        assert_eq!(report["instructions_after"], 2);
+        assert_eq!(report["two_qubit_instructions_after"], 0);
+
+        backend
+            .call_method1("set_verify_optimisation", (true,))
+            .unwrap();
+        let verify: bool = backend
+            .call_method0("verify_optimisation")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(verify);
+    })
+}
+
+#[test]
+fn test_cassette() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((3,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let error = backend.call_method1("replay_cassette", ("does_not_exist.json",));
+        assert!(error.unwrap_err().is_instance_of::<PyRuntimeError>(py));
+        backend
+            .call_method1("record_cassette", ("cassette.json",))
+            .unwrap();
+        backend.call_method0("stop_cassette").unwrap();
+    })
+}
+
+#[test]
+fn test_readout_mitigation() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let method: Option<String> = backend
+            .call_method0("readout_mitigation")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(method, None);
+        backend
+            .call_method1("set_readout_mitigation", ("least_squares",))
+            .unwrap();
+        let method: Option<String> = backend
+            .call_method0("readout_mitigation")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(method, Some("least_squares".to_string()));
+        let error = backend.call_method1("set_readout_mitigation", ("unknown",));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+
+        let confusion_matrices: Option<Vec<[[f64; 2]; 2]>> = backend
+            .call_method0("readout_confusion_matrices")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(confusion_matrices, None);
+        let error = backend.call_method1("mitigate_distribution", (vec![vec![true]],));
+        assert!(error.unwrap_err().is_instance_of::<PyRuntimeError>(py));
+    })
+}
+
+#[test]
+fn test_result_cache() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let policy: Option<String> = backend
+            .call_method0("result_cache_policy")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(policy, None);
+
+        let directory = env::temp_dir().join("qoqo_aqt_result_cache_test");
+        backend
+            .call_method1("set_result_cache", (directory.clone(), "expiring", 60.0))
+            .unwrap();
+        let policy: Option<String> = backend
+            .call_method0("result_cache_policy")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(policy, Some("expiring".to_string()));
+        backend.call_method0("clear_result_cache").unwrap();
+
+        let error = backend.call_method1("set_result_cache", (directory.clone(), "expiring"));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+        let error = backend.call_method1("set_result_cache", (directory.clone(), "read_only", 1.0));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+        let error = backend.call_method1("set_result_cache", (directory, "unknown"));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+
+        backend
+            .call_method1("set_result_cache", (py.None(),))
+            .unwrap();
+        let policy: Option<String> = backend
+            .call_method0("result_cache_policy")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(policy, None);
+    })
+}
+
+#[test]
+fn test_job_journal() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let journal: Option<std::path::PathBuf> = backend
+            .call_method0("job_journal")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(journal, None);
+
+        let path = env::temp_dir().join("qoqo_aqt_missing_job_journal.jsonl");
+        backend
+            .call_method1("set_job_journal", (path.clone(),))
+            .unwrap();
+        let journal: Option<std::path::PathBuf> = backend
+            .call_method0("job_journal")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(journal, Some(path));
+        let unfinished: Vec<JobHandleWrapper> = backend
+            .call_method0("unfinished_jobs")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(unfinished.is_empty());
+        backend
+            .call_method1("set_job_journal", (py.None(),))
+            .unwrap();
+    })
+}
+
+#[test]
+fn test_sample_decoding() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let packed_bit_order: String = backend
+            .call_method0("packed_bit_order")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(packed_bit_order, "least_significant");
+        let sample_layout: String = backend
+            .call_method0("sample_layout")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(sample_layout, "auto");
+
+        backend
+            .call_method1("set_packed_bit_order", ("most_significant",))
+            .unwrap();
+        backend
+            .call_method1("set_sample_layout", ("per_qubit",))
+            .unwrap();
+        let packed_bit_order: String = backend
+            .call_method0("packed_bit_order")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(packed_bit_order, "most_significant");
+        let sample_layout: String = backend
+            .call_method0("sample_layout")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(sample_layout, "per_qubit");
+
+        let error = backend.call_method1("set_packed_bit_order", ("unknown",));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+        let error = backend.call_method1("set_sample_layout", ("unknown",));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+/// Counts and raw samples of each readout register returned by `run_circuit_counts`
+#[cfg(feature = "mock-server")]
+type Counts<K> = (
+    HashMap<String, HashMap<K, usize>>,
+    HashMap<String, Vec<u32>>,
+);
+
+#[test]
+fn test_job_label() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let label: String = backend
+            .call_method0("job_label")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(label, "qoqo_aqt_backend");
+
+        backend
+            .call_method1("set_job_label", ("{tag}_{name}_{resource_id}",))
+            .unwrap();
+        backend
+            .call_method1("set_job_tag", ("experiment",))
+            .unwrap();
+        let label: String = backend
+            .call_method0("job_label")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(label, "{tag}_{name}_{resource_id}");
+        let tag: String = backend.call_method0("job_tag").unwrap().extract().unwrap();
+        assert_eq!(tag, "experiment");
+
+        let error = backend.call_method1("set_job_label", ("{workspace}",));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+#[test]
+fn test_http_client_config() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<AqtDeviceWrapper>();
+        let device = device_type.call1((1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        let config = backend.call_method0("http_client_config").unwrap();
+        let timeout: Option<f64> = config.get_item("timeout").unwrap().extract().unwrap();
+        assert_eq!(timeout, None);
+
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("timeout", 5.0).unwrap();
+        kwargs
+            .set_item("proxy", "http://proxy.example.com:8080")
+            .unwrap();
+        kwargs.set_item("user_agent", "qoqo-tests/1.0").unwrap();
+        backend
+            .call_method("set_http_client_config", (), Some(&kwargs))
+            .unwrap();
+        let config = backend.call_method0("http_client_config").unwrap();
+        let timeout: Option<f64> = config.get_item("timeout").unwrap().extract().unwrap();
+        assert_eq!(timeout, Some(5.0));
+        let proxy: Option<String> = config.get_item("proxy").unwrap().extract().unwrap();
+        assert_eq!(proxy, Some("http://proxy.example.com:8080".to_string()));
+        let user_agent: Option<String> = config.get_item("user_agent").unwrap().extract().unwrap();
+        assert_eq!(user_agent, Some("qoqo-tests/1.0".to_string()));
+
+        // The settings are serialised with the backend
+        let serialised = backend.call_method0("to_json").unwrap();
+        let deserialised = backend
+            .call_method1("from_json", (serialised,))
+            .unwrap()
+            .call_method0("http_client_config")
+            .unwrap();
+        let user_agent: Option<String> = deserialised
+            .get_item("user_agent")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(user_agent, Some("qoqo-tests/1.0".to_string()));
+
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("proxy", "not a proxy url").unwrap();
+        let error = backend.call_method("set_http_client_config", (), Some(&kwargs));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+        let error = backend.call_method1("set_http_client_config", (-1.0,));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+#[cfg(feature = "mock-server")]
+#[test]
+fn test_running_circuit_mock_server() {
+    use qoqo_aqt::MockServerWrapper;
+
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("readout".to_string(), 2, true);
+    circuit += operations::RotateX::new(0, std::f64::consts::PI.into());
+    circuit += operations::PragmaRepeatedMeasurement::new("readout".to_string(), 10, None);
+    let circuit_wrapper = CircuitWrapper { internal: circuit };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let server_type = py.get_type_bound::<MockServerWrapper>();
+        let server = server_type.call0().unwrap();
+        let device = server.call_method1("device", (2,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        backend.call_method1("set_poll_interval", (0.01,)).unwrap();
+        let bits: HashMap<String, Vec<Vec<bool>>> = backend
+            .call_method1("run_circuit", (circuit_wrapper.clone(),))
+            .unwrap()
+            .get_item(0)
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(bits["readout"], vec![vec![true, false]; 10]);
+        let job_ids: Vec<String> = server.call_method0("job_ids").unwrap().extract().unwrap();
+        assert_eq!(job_ids.len(), 1);
+        let status: Option<String> = server
+            .call_method1("job_status", (job_ids[0].clone(),))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(status, Some("finished".to_string()));
+
+        let mut program_circuit = Circuit::new();
+        program_circuit += operations::DefinitionBit::new("readout".to_string(), 2, true);
+        program_circuit += operations::RotateX::new(0, "angle".into());
+        program_circuit +=
+            operations::PragmaRepeatedMeasurement::new("readout".to_string(), 10, None);
+        let program_wrapper = QuantumProgramWrapper {
+            internal: QuantumProgram::ClassicalRegister {
+                measurement: ClassicalRegister {
+                    constant_circuit: None,
+                    circuits: vec![program_circuit],
+                },
+                input_parameter_names: vec!["angle".to_string()],
+            },
+        };
+        let program_bits: HashMap<String, Vec<Vec<bool>>> = backend
+            .call_method1(
+                "run_registers",
+                (program_wrapper, vec![std::f64::consts::PI]),
+            )
+            .unwrap()
+            .get_item(0)
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(program_bits["readout"], vec![vec![true, false]; 10]);
+
+        // The mock server measures without readout errors
+        let confusion_matrices: Vec<[[f64; 2]; 2]> = backend
+            .call_method1("calibrate_readout", (10, true))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(confusion_matrices, vec![[[1.0, 0.0], [0.0, 1.0]]; 2]);
+        let cached: Option<Vec<[[f64; 2]; 2]>> = backend
+            .call_method0("readout_confusion_matrices")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(cached, Some(confusion_matrices));
+        backend
+            .call_method1("set_readout_mitigation", ("inverse",))
+            .unwrap();
+        let distribution: HashMap<String, f64> = backend
+            .call_method1("mitigate_distribution", (bits["readout"].clone(),))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(distribution, HashMap::from([("10".to_string(), 1.0)]));
+
+        let (counts, samples): Counts<String> = backend
+            .call_method1("run_circuit_counts", (circuit_wrapper.clone(),))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(counts["readout"], HashMap::from([("10".to_string(), 10)]));
+        assert_eq!(samples["readout"], vec![1; 10]);
+        let (counts, _): Counts<String> = backend
+            .call_method1(
+                "run_circuit_counts",
+                (circuit_wrapper.clone(), "qubit_zero_last"),
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(counts["readout"], HashMap::from([("01".to_string(), 10)]));
+        let job = backend
+            .call_method1("submit_circuit", (circuit_wrapper.clone(),))
+            .unwrap();
+        let (counts, _): Counts<u64> = backend
+            .call_method1("wait_for_job_counts", (job, "qubit_zero_first", true))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(counts["readout"], HashMap::from([(2, 10)]));
+
+        let result = backend
+            .call_method1("run_circuit_with_metadata", (circuit_wrapper.clone(),))
+            .unwrap();
+        let bits: HashMap<String, Vec<Vec<bool>>> = result
+            .get_item(0)
+            .unwrap()
+            .get_item(0)
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(bits["readout"], vec![vec![true, false]; 10]);
+        let metadata = result.get_item(1).unwrap();
+        let job_id: String = metadata.get_item("job_id").unwrap().extract().unwrap();
+        let job_ids: Vec<String> = server.call_method0("job_ids").unwrap().extract().unwrap();
+        assert!(job_ids.contains(&job_id));
+        let submitted: Option<u64> = metadata.get_item("submitted").unwrap().extract().unwrap();
+        let finished: Option<u64> = metadata.get_item("finished").unwrap().extract().unwrap();
+        assert!(submitted.is_some());
+        assert!(finished >= submitted);
+
+        backend
+            .call_method1("set_job_label", ("{tag}-{name}",))
+            .unwrap();
+        backend.call_method1("set_job_tag", ("demo",)).unwrap();
+        let job = backend
+            .call_method1("submit_circuit", (circuit_wrapper.clone(), "bell"))
+            .unwrap();
+        let label: String = backend
+            .call_method1("wait_for_job_with_metadata", (job,))
+            .unwrap()
+            .get_item(1)
+            .unwrap()
+            .get_item("label")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(label, "demo-bell");
+        let error = backend.call_method1("run_circuit_counts", (circuit_wrapper, "unknown"));
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_enable_tracing_unknown_level() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let error = qoqo_aqt::enable_tracing("verbose");
+        assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
+
+#[cfg(all(feature = "tracing", feature = "mock-server"))]
+#[test]
+fn test_tracing_python_logging_mock_server() {
+    use qoqo_aqt::MockServerWrapper;
+
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("readout".to_string(), 1, true);
+    circuit += operations::PragmaRepeatedMeasurement::new("readout".to_string(), 10, None);
+    let circuit_wrapper = CircuitWrapper { internal: circuit };
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let locals = PyDict::new_bound(py);
+        py.run_bound(
+            r#"
+import logging
+
+class ListHandler(logging.Handler):
+    def __init__(self):
+        super().__init__()
+        self.records = []
+
+    def emit(self, record):
+        self.records.append((record.name, record.levelno, record.getMessage()))
+
+handler = ListHandler()
+logger = logging.getLogger("roqoqo_aqt")
+logger.addHandler(handler)
+logger.setLevel(logging.DEBUG)
+"#,
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+        qoqo_aqt::enable_tracing("debug").unwrap();
+
+        let server_type = py.get_type_bound::<MockServerWrapper>();
+        let server = server_type.call0().unwrap();
+        let device = server.call_method1("device", (1,)).unwrap();
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let backend = backend_type
+            .call1((
+                device.downcast::<AqtDeviceWrapper>().unwrap(),
+                "DUMMY_ACCESS_TOKEN",
+            ))
+            .unwrap();
+        backend.call_method1("set_poll_interval", (0.01,)).unwrap();
+        backend
+            .call_method1("run_circuit", (circuit_wrapper,))
+            .unwrap();
+        qoqo_aqt::enable_tracing("off").unwrap();
+
+        let records: Vec<(String, u8, String)> = locals
+            .get_item("handler")
+            .unwrap()
+            .unwrap()
+            .getattr("records")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(records
+            .iter()
+            .any(|(name, level, message)| name == "roqoqo_aqt.backend"
+                && *level == 20
+                && message.starts_with("submit{resource_id=")
+                && message.contains("Submitted job to AQT")));
+        assert!(records
+            .iter()
+            .any(|(_, level, message)| *level == 10 && message.contains("Polled job")));
+        assert!(records
+            .iter()
+            .all(|(_, _, message)| !message.contains("DUMMY_ACCESS_TOKEN")));
+    })
+}
+
+// Test that Backends serialized with version 0.8.0 can be deserialized
+#[test]
+fn test_legacy_backend_serialization() {
+    pyo3::prepare_freethreaded_python();
+    let legacy_backend = LegacyBackend {
+        device: LegacyAqtDevice { number_qubits: 3 },
+        access_token: "DUMMY_ACCESS_TOKEN".to_string(),
+    };
+    let expected: Backend<AnyAqtDevice> = Backend::new(
+        AqtDevice::new(3).into(),
+        Some("DUMMY_ACCESS_TOKEN".to_string()),
+    )
+    .unwrap();
+    Python::with_gil(|py| {
+        let backend_type = py.get_type_bound::<BackendWrapper>();
+        let deserialised = backend_type
+            .call_method1(
+                "from_bincode",
+                (bincode::serialize(&legacy_backend).unwrap(),),
+            )
+            .unwrap()
+            .extract::<BackendWrapper>()
+            .unwrap();
+        assert_eq!(deserialised.internal, expected);
+
+        let deserialised = backend_type
+            .call_method1(
+                "from_json",
+                (serde_json::to_string(&legacy_backend).unwrap(),),
+            )
+            .unwrap()
+            .extract::<BackendWrapper>()
+            .unwrap();
+        assert_eq!(deserialised.internal, expected);
+
+        let invalid = backend_type.call_method1("from_bincode", (vec![0u8; 4],));
+        assert!(invalid.unwrap_err().is_instance_of::<PyValueError>(py));
+    })
+}
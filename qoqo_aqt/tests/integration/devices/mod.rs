@@ -15,6 +15,7 @@
 use pyo3::prelude::*;
 use pyo3::Python;
 use qoqo_aqt::devices;
+use roqoqo_aqt::devices::{AqtDevice, LegacyAqtDevice};
 use test_case::test_case;
 
 #[test_case(1; "1")]
@@ -46,3 +47,106 @@ fn test_creating_device(number_qubits: usize) {
         assert!(is_https);
     })
 }
+
+#[test]
+fn test_creating_local_devices() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let simulator_type = py.get_type_bound::<devices::SimulatorDeviceWrapper>();
+        let simulator = simulator_type.call1((3, 7)).unwrap();
+        let number_qubits: usize = simulator
+            .call_method0("number_qubits")
+            .unwrap()
+            .extract()
+            .unwrap();
+        let seed: Option<u64> = simulator.call_method0("seed").unwrap().extract().unwrap();
+        assert_eq!(number_qubits, 3);
+        assert_eq!(seed, Some(7));
+        let serialised = simulator.call_method0("to_bincode").unwrap();
+        let deserialised = simulator_type
+            .call_method1("from_bincode", (serialised,))
+            .unwrap()
+            .extract::<devices::SimulatorDeviceWrapper>()
+            .unwrap();
+        assert_eq!(
+            deserialised,
+            simulator
+                .extract::<devices::SimulatorDeviceWrapper>()
+                .unwrap()
+        );
+
+        let noise_model_type = py.get_type_bound::<devices::AqtNoiseModelWrapper>();
+        let noise_model = noise_model_type.call0().unwrap();
+        let dephasing: f64 = noise_model
+            .call_method0("dephasing")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(dephasing, 0.0);
+        assert!(noise_model_type
+            .call1((1.5,))
+            .unwrap_err()
+            .is_instance_of::<pyo3::exceptions::PyValueError>(py));
+        let noise_model = noise_model_type.call1((0.1, 0.2, 0.3, 0.4, 0.5)).unwrap();
+        let measurement_error: f64 = noise_model
+            .call_method0("measurement_error")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(measurement_error, 0.5);
+
+        let emulator_type = py.get_type_bound::<devices::EmulatorDeviceWrapper>();
+        let aqt_device = py
+            .get_type_bound::<devices::AqtDeviceWrapper>()
+            .call1((4,))
+            .unwrap();
+        let emulator = emulator_type
+            .call_method1("from_device", (aqt_device, noise_model.clone()))
+            .unwrap();
+        let number_qubits: usize = emulator
+            .call_method0("number_qubits")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(number_qubits, 4);
+        let emulated_noise_model = emulator
+            .call_method0("noise_model")
+            .unwrap()
+            .extract::<devices::AqtNoiseModelWrapper>()
+            .unwrap();
+        assert_eq!(
+            emulated_noise_model,
+            noise_model
+                .extract::<devices::AqtNoiseModelWrapper>()
+                .unwrap()
+        );
+        let serialised = emulator.call_method0("to_json").unwrap();
+        let deserialised = emulator_type
+            .call_method1("from_json", (serialised,))
+            .unwrap()
+            .extract::<devices::EmulatorDeviceWrapper>()
+            .unwrap();
+        assert_eq!(
+            deserialised,
+            emulator
+                .extract::<devices::EmulatorDeviceWrapper>()
+                .unwrap()
+        );
+    })
+}
+
+// Test that AqtDevices serialized with version 0.8.0 can be deserialized
+#[test]
+fn test_legacy_device_serialization() {
+    pyo3::prepare_freethreaded_python();
+    let legacy_bytes = bincode::serialize(&LegacyAqtDevice { number_qubits: 3 }).unwrap();
+    Python::with_gil(|py| {
+        let device_type = py.get_type_bound::<devices::AqtDeviceWrapper>();
+        let deserialised = device_type
+            .call_method1("from_bincode", (legacy_bytes,))
+            .unwrap()
+            .extract::<devices::AqtDeviceWrapper>()
+            .unwrap();
+        assert_eq!(deserialised.internal, AqtDevice::new(3));
+    })
+}
@@ -10,16 +10,37 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{call_operation, AqtApi, AqtInstruction};
+use crate::cassette::{CassetteInteraction, CassetteMode, CassettePlayer};
+use crate::counts::{self, AqtCounts, PackedBitOrder, SampleLayout};
+use crate::devices::{AqtDevice, LegacyAqtDevice};
+use crate::http_client::{ClientCache, HttpClientConfig};
+#[cfg(feature = "tracing")]
+use crate::instrumentation;
+use crate::interface;
+use crate::journal::{JobJournal, JobStatus};
+use crate::noise_amplification::{self, ZeroNoiseExtrapolation};
+use crate::optimisation::{self, OptimisationReport};
+use crate::readout_mitigation::{MitigationMethod, ReadoutCalibration};
+use crate::report::{TranslationReport, UntranslatedOperation};
+use crate::result_cache::{self, CachePolicy, ResultCache};
+use crate::simulator;
+use crate::{
+    call_instructions, call_loop, call_operation, call_operation_sequence, AqtApi, AqtInstruction,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use reqwest::blocking;
 use reqwest::header::{HeaderValue, ACCEPT};
 use roqoqo::backends::EvaluatingBackend;
 use roqoqo::backends::RegisterResult;
+use roqoqo::measurements::{Measure, MeasureExpectationValues, PauliZProduct};
 use roqoqo::operations::*;
 use roqoqo::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
+use roqoqo::Circuit;
 use roqoqo::RoqoqoBackendError;
 use std::collections::HashMap;
 use std::env;
+use std::path::Path;
 use std::{thread, time};
 
 pub type RegisterDefinition = (
@@ -31,41 +52,164 @@ pub type RegisterDefinition = (
 /// AQT backend
 ///
 /// provides functions to run circuits and measurements on AQT devices.
+///
+/// Fields added after version 0.8.0 take their default values when they are missing in JSON,
+/// bincode data of version 0.8.0 is deserialized with [LegacyBackend].
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Backend<T: AqtApi> {
     /// Number of qubits supported by the device
     pub device: T,
     /// Access token for identification with AQT devices
     access_token: String,
+    /// Number of measurements used to emulate PragmaGetPauliProduct and PragmaGetOccupationProbability,
+    /// the pragmas are rejected when None
+    #[serde(default)]
+    pragma_emulation_shots: Option<usize>,
+    /// Time between two queries of the status of a job while waiting for the job
+    #[serde(default = "default_poll_interval")]
+    poll_interval: time::Duration,
+    /// Whether the translated instructions are optimised before they are sent to the device
+    #[serde(default)]
+    optimise_instructions: bool,
+    /// Whether the equivalence of the optimised and the translated instructions is verified
+    #[serde(default)]
+    verify_optimisation: bool,
+    /// Cassette the interactions with the AQT REST API are recorded to or replayed from
+    #[serde(skip)]
+    cassette: Option<CassettePlayer>,
+    /// Cached calibration of the readout errors of the device
+    #[serde(default)]
+    readout_calibration: Option<ReadoutCalibration>,
+    /// Method used to correct readout errors in mitigated evaluations, None when mitigation is disabled
+    #[serde(default)]
+    readout_mitigation: Option<MitigationMethod>,
+    /// Position of qubit 0 in the packed integer samples returned by the device
+    #[serde(default)]
+    packed_bit_order: PackedBitOrder,
+    /// Layout of the samples returned by the device
+    #[serde(default)]
+    sample_layout: SampleLayout,
+    /// On-disk cache of the results of jobs sent to the AQT servers
+    #[serde(default)]
+    result_cache: Option<ResultCache>,
+    /// Journal of the jobs sent to the AQT servers
+    #[serde(default)]
+    job_journal: Option<JobJournal>,
+    /// Template of the label of submitted jobs
+    #[serde(default = "default_job_label")]
+    job_label: String,
+    /// User tag inserted into job labels in place of `{tag}`
+    #[serde(default)]
+    job_tag: String,
+    /// Settings of the HTTP client communicating with the AQT REST API
+    #[serde(default)]
+    http_client_config: HttpClientConfig,
+    /// HTTP client reused for all requests, created from the settings when the first request is sent
+    #[serde(skip)]
+    http_client: ClientCache,
+}
+
+/// Layout of the [Backend] serialized by version 0.8.0
+///
+/// Formats that are not self-describing like bincode can not skip the fields added later,
+/// data serialized with 0.8.0 is deserialized to this layout and converted to a [Backend]
+/// with the default settings.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LegacyBackend {
+    /// Device of the backend
+    pub device: LegacyAqtDevice,
+    /// Access token for identification with AQT devices
+    pub access_token: String,
+}
+
+impl<T: AqtApi + From<AqtDevice>> From<LegacyBackend> for Backend<T> {
+    fn from(backend: LegacyBackend) -> Self {
+        Self::with_access_token(AqtDevice::from(backend.device).into(), backend.access_token)
+    }
+}
+
+/// Returns the default time between two queries of the status of a job
+fn default_poll_interval() -> time::Duration {
+    time::Duration::from_secs(20)
+}
+
+/// Returns the default template of the label of submitted jobs
+fn default_job_label() -> String {
+    "qoqo_aqt_backend".to_string()
 }
 
 /// Payload sent to AQT device containing a vector of AqtCircuits
 #[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
-struct AqtPayload {
+pub(crate) struct AqtPayload {
     /// Vector of circuit sent to AQT device
-    circuits: Vec<AqtCircuit>,
+    pub(crate) circuits: Vec<AqtCircuit>,
 }
 
 /// Provides the quantum circuit that is to be simulated along with number of qubits used and number of simulation repetitions
 #[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
 pub struct AqtCircuit {
     /// Number of qubits used by AQT device
-    number_of_qubits: u32,
+    pub(crate) number_of_qubits: u32,
     /// Quantum circuit that is to be simulated
-    quantum_circuit: Vec<AqtInstruction>,
+    pub(crate) quantum_circuit: Vec<AqtInstruction>,
     /// Number of times the simulation is repeated
-    repetitions: u32,
+    pub(crate) repetitions: u32,
 }
 
 /// Schema of post request body sent to AQT device to run the simulation
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct AqtRunData {
     /// Name of the job type
-    job_type: String,
+    pub(crate) job_type: String,
     /// Custom label given to job
-    label: String,
+    pub(crate) label: String,
     /// Payload containing the list of quantum circuits that to be simulated on the AQT device
-    payload: AqtPayload,
+    pub(crate) payload: AqtPayload,
+}
+
+impl AqtRunData {
+    /// Reads the JSON body of a job sent to an AQT device, for example from an archived job
+    ///
+    /// # Arguments
+    ///
+    /// `json` - The JSON representation of the job
+    pub fn from_json(json: &str) -> Result<Self, RoqoqoBackendError> {
+        serde_json::from_str(json).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!("Could not parse AQT job {err:?}"),
+        })
+    }
+
+    /// Returns the custom label of the job
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Converts the circuits of the job back into [roqoqo::Circuit]s
+    ///
+    /// Each circuit defines the bit register `readout` with the number of qubits of the AQT circuit
+    /// and is measured as often as the AQT circuit was repeated, see [crate::call_instructions].
+    ///
+    /// # Arguments
+    ///
+    /// `readout` - The name of the bit register the measurement results are written to
+    ///
+    /// # Returns
+    ///
+    /// `Vec<Circuit>` - The reconstructed circuits in the order of the job
+    pub fn to_circuits(&self, readout: &str) -> Result<Vec<Circuit>, RoqoqoBackendError> {
+        self.payload
+            .circuits
+            .iter()
+            .map(|aqt_circuit| {
+                call_instructions(
+                    &aqt_circuit.quantum_circuit,
+                    Some(aqt_circuit.number_of_qubits as usize),
+                    aqt_circuit.repetitions as usize,
+                    readout,
+                )
+            })
+            .collect()
+    }
 }
 
 /// Schema for response recieved from AQT device server
@@ -92,6 +236,168 @@ impl AqtRunResponse {
     }
 }
 
+/// Workspace the jobs of the Backend are submitted to
+const WORKSPACE_ID: &str = "qoqo-integration";
+
+/// Metadata identifying the AQT job that produced a result
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct JobMetadata {
+    /// Id of the job assigned by the AQT device
+    pub job_id: String,
+    /// Id of the AQT resource that ran the job
+    pub resource_id: String,
+    /// Id of the workspace the job was submitted to, empty for local devices
+    pub workspace_id: String,
+    /// Label of the job
+    pub label: String,
+    /// Time the job was submitted in seconds since the Unix epoch, None for results read from the result cache
+    pub submitted: Option<u64>,
+    /// Time the result of the job was received in seconds since the Unix epoch, None while the job is running
+    pub finished: Option<u64>,
+}
+
+/// Output registers of a job together with the metadata of the job
+#[derive(Debug, Clone, PartialEq)]
+pub struct AqtRunResult {
+    /// The output registers written by the evaluated circuits
+    pub registers: RegisterDefinition,
+    /// Metadata identifying the AQT job that produced the registers
+    pub metadata: JobMetadata,
+}
+
+/// Handle of a submitted job running a measurement at several noise scales
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZeroNoiseExtrapolationJob {
+    /// Handle of the job with the circuits of all noise scales
+    job: AqtJobHandle,
+    /// The noise scale factors the measurement is run with
+    scale_factors: Vec<f64>,
+    /// The order of the polynomial used for the extrapolation
+    order: usize,
+    /// Number of measured circuits and of estimate circuits for each scale factor
+    layouts: Vec<(usize, usize)>,
+}
+
+impl ZeroNoiseExtrapolationJob {
+    /// Returns the handle of the job with the circuits of all noise scales
+    pub fn job(&self) -> &AqtJobHandle {
+        &self.job
+    }
+}
+
+/// Measured results of the circuits in a job by index of the circuit,
+/// one packed integer per shot with qubit 0 as the least significant bit.
+/// Results of the AQT servers are normalised to this order when they are received, independent of [PackedBitOrder].
+type MeasuredResults = HashMap<u32, Vec<Vec<u32>>>;
+
+/// Handle of a job that has been submitted to an AQT device
+///
+/// Contains the job id assigned by the AQT device and the register layout of the submitted circuits,
+/// so the job can be polled and its results written to output registers at a later time.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AqtJobHandle {
+    /// Id of the job assigned by the AQT device
+    job_id: String,
+    /// Names of the bit registers the measurement results of each circuit in the job are written to,
+    /// handles of single-circuit jobs serialized with a single `readout` are still accepted
+    #[serde(alias = "readout", deserialize_with = "deserialize_readouts")]
+    readouts: Vec<String>,
+    /// Names of the bit output registers defined in the circuit
+    bit_registers: Vec<String>,
+    /// Names of the float output registers defined in the circuit
+    float_registers: Vec<String>,
+    /// Names of the complex output registers defined in the circuit
+    complex_registers: Vec<String>,
+    /// Estimates obtained from the circuits following the measured circuits in the job
+    #[serde(default)]
+    estimates: Vec<AqtEstimate>,
+    /// Measured results of a job that has been simulated locally or read from the result cache
+    #[serde(default)]
+    local_result: Option<HashMap<u32, Vec<Vec<u32>>>>,
+    /// Fingerprint the result of the job is stored under in the result cache
+    #[serde(default)]
+    cache_key: Option<String>,
+    /// Metadata of the job known when it was submitted
+    #[serde(default)]
+    metadata: JobMetadata,
+}
+
+/// Deserializes the readout registers of a job handle from a list or a single register name
+///
+/// Handles of single-circuit jobs were serialized with a single `readout` string.
+/// Formats that are not human readable, like bincode, always contain a list.
+fn deserialize_readouts<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Readouts {
+        Single(String),
+        Multiple(Vec<String>),
+    }
+    if !deserializer.is_human_readable() {
+        return <Vec<String> as serde::Deserialize>::deserialize(deserializer);
+    }
+    match <Readouts as serde::Deserialize>::deserialize(deserializer)? {
+        Readouts::Single(readout) => Ok(vec![readout]),
+        Readouts::Multiple(readouts) => Ok(readouts),
+    }
+}
+
+/// Estimate of an emulated pragma that is evaluated from the measurements of one circuit in a job
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum AqtEstimate {
+    /// Expectation value of the product of Pauli Z operators on the qubits, emulating PragmaGetPauliProduct
+    PauliProduct {
+        /// Name of the float register the estimate is written to
+        readout: String,
+        /// Qubits in the Pauli product after the rotation into the Z basis
+        qubits: Vec<usize>,
+    },
+    /// Occupation probabilities of all qubits, emulating PragmaGetOccupationProbability
+    OccupationProbability {
+        /// Name of the float register the estimate is written to
+        readout: String,
+    },
+}
+
+/// Result of translating a single circuit
+struct ConvertedCircuit {
+    /// The translated circuit
+    circuit: AqtCircuit,
+    /// Output registers defined in the circuit
+    registers: RegisterDefinition,
+    /// Name of the register the measurement results are written to
+    readout: String,
+    /// Additional circuits emulating pragmas with the estimate evaluated from their measurements
+    estimates: Vec<(AqtCircuit, AqtEstimate)>,
+    /// Factor the noise of the circuit is amplified by, set with PragmaBoostNoise
+    noise_scale: f64,
+}
+
+impl AqtJobHandle {
+    /// Returns the id of the job assigned by the AQT device
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+    /// Returns the name of the bit register the measurement results of the first circuit in the job are written to
+    pub fn readout(&self) -> &str {
+        self.readouts
+            .first()
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+    /// Returns the names of the bit registers the measurement results of each circuit in the job are written to
+    pub fn readouts(&self) -> &[String] {
+        &self.readouts
+    }
+    /// Returns the metadata of the job known when it was submitted
+    pub fn metadata(&self) -> &JobMetadata {
+        &self.metadata
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
 struct AqtJob {
     #[serde(default)]
@@ -142,341 +448,2193 @@ impl<T: AqtApi> Backend<T> {
     /// `device` - The AQT device the Backend uses to execute operations and circuits.
     ///            At the moment limited to the AQT simulator.
     /// `access_token` - An access_token is required to access AQT hardware and simulators.
-    ///                  The access_token can either be given as an argument here or set via the environmental variable `$AQT_ACCESS_TOKEN`
+    ///                  The access_token can either be given as an argument here or set via the environmental variable `$AQT_ACCESS_TOKEN`.
+    ///                  It is not required for local devices such as [crate::devices::SimulatorDevice]
     ///
     /// # Returns
     ///
     /// `Self` - Backend interface for AQT
+    /// `RoqoqoBackendError::GenericError` - A local device has more than [crate::devices::MAX_SIMULATED_QUBITS] qubits
     pub fn new(device: T, access_token: Option<String>) -> Result<Self, RoqoqoBackendError> {
         let access_token_internal: String = match access_token {
             Some(s) => s,
+            // Local devices do not communicate with the AQT cloud
+            None if device.is_local() => "".to_string(),
             None => env::var("AQT_ACCESS_TOKEN").map_err(|_| {
                 RoqoqoBackendError::MissingAuthentication {
                     msg: "AQT access token is missing".to_string(),
                 }
             })?,
         };
+        if device.is_local() {
+            simulator::check_number_qubits(device.number_qubits())?;
+        }
+
+        Ok(Self::with_access_token(device, access_token_internal))
+    }
 
-        Ok(Self {
+    /// Creates a Backend with the default settings for a device and an access token.
+    fn with_access_token(device: T, access_token: String) -> Self {
+        Self {
             device,
-            access_token: access_token_internal,
-        })
+            access_token,
+            pragma_emulation_shots: None,
+            poll_interval: default_poll_interval(),
+            optimise_instructions: false,
+            verify_optimisation: false,
+            cassette: None,
+            readout_calibration: None,
+            readout_mitigation: None,
+            packed_bit_order: PackedBitOrder::default(),
+            sample_layout: SampleLayout::default(),
+            result_cache: None,
+            job_journal: None,
+            job_label: default_job_label(),
+            job_tag: String::new(),
+            http_client_config: HttpClientConfig::default(),
+            http_client: ClientCache::default(),
+        }
     }
 
-    /// Creates an AQT jSON represenstaion of a [roqoqo::Circuit].
+    /// Sets the time between two queries of the status of a job while waiting for the job.
+    ///
+    /// The default of 20 seconds avoids putting load on the AQT servers,
+    /// shorter intervals are useful for mock servers and small jobs.
     ///
     /// # Arguments
     ///
-    /// `circuit` - An iterator over Operations that represents a circuit that is translated
+    /// `poll_interval` - The time between two queries
+    pub fn set_poll_interval(&mut self, poll_interval: time::Duration) {
+        self.poll_interval = poll_interval;
+    }
+
+    /// Returns the time between two queries of the status of a job while waiting for the job.
+    pub fn poll_interval(&self) -> time::Duration {
+        self.poll_interval
+    }
+
+    /// Sets the number of measurements used to emulate PragmaGetPauliProduct and PragmaGetOccupationProbability.
     ///
-    /// # Returns
+    /// AQT devices do not support the pragmas directly. When emulation is enabled,
+    /// every pragma is replaced by an additional circuit in the same job that applies the pragma circuit
+    /// and the basis rotations needed to measure the Pauli product in the Z basis.
+    /// The float register of the pragma is filled with the estimate obtained from the sampled measurements
+    /// and the float register `{readout}_error` with the standard error of the estimate due to shot noise.
     ///
-    /// `String` - JSON representation of a roqoqo circuit
-    pub fn to_aqt_json<'a>(
-        &self,
-        circuit: impl Iterator<Item = &'a Operation>,
-    ) -> Result<String, RoqoqoBackendError> {
-        let mut instruction_vec: Vec<AqtInstruction> = Vec::new();
-        for op in circuit {
-            if let Some(x) = call_operation(op)? {
-                instruction_vec.push(x)
-            }
-        }
+    /// # Arguments
+    ///
+    /// `number_measurements` - The number of measurements of each emulated pragma, None disables the emulation
+    pub fn set_pragma_emulation_shots(&mut self, number_measurements: Option<usize>) {
+        self.pragma_emulation_shots = number_measurements;
+    }
 
-        Ok(serde_json::to_string(&instruction_vec).unwrap())
+    /// Returns the number of measurements used to emulate PragmaGetPauliProduct and PragmaGetOccupationProbability.
+    ///
+    /// # Returns
+    ///
+    /// `Option<usize>` - The number of measurements of each emulated pragma, None when the emulation is disabled
+    pub fn pragma_emulation_shots(&self) -> Option<usize> {
+        self.pragma_emulation_shots
     }
 
-    /// Converts a [roqoqo::Circuit] to `AqtRunData` object that can be sent to AQT device
-    /// to process and initialises registers for measurement
+    /// Sets whether the translated instructions are optimised before they are sent to the device.
+    ///
+    /// The optimisation fuses single-qubit rotations, removes virtual Z rotations and merges RXX instructions,
+    /// see [crate::optimisation::optimise_instructions].
     ///
     /// # Arguments
     ///
-    /// `circuit` - An iterator over Operations that represents a circuit that is translated
+    /// `optimise_instructions` - Whether the instructions are optimised, disabled by default
+    pub fn set_optimise_instructions(&mut self, optimise_instructions: bool) {
+        self.optimise_instructions = optimise_instructions;
+    }
+
+    /// Returns whether the translated instructions are optimised before they are sent to the device.
+    pub fn optimise_instructions(&self) -> bool {
+        self.optimise_instructions
+    }
+
+    /// Sets whether the optimised instructions are verified to be equivalent to the translated instructions.
     ///
-    /// # Returns
+    /// The verification compares the unitaries of the instructions and is only feasible for small numbers of qubits,
+    /// see [crate::optimisation::verify_equivalence]. Submitting a circuit fails when the verification fails.
     ///
-    ///  `(AqtRunData, RegisterDefinition, String)` - Object of `AqtRunData`, registers, readout for registers
-    pub fn convert_circuit_to_aqt_instructions<'a>(
-        &self,
-        circuit: impl Iterator<Item = &'a Operation>,
-    ) -> Result<(AqtRunData, RegisterDefinition, String), RoqoqoBackendError> {
-        let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
-        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
-        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
+    /// # Arguments
+    ///
+    /// `verify_optimisation` - Whether the optimisation is verified, disabled by default
+    pub fn set_verify_optimisation(&mut self, verify_optimisation: bool) {
+        self.verify_optimisation = verify_optimisation;
+    }
 
-        let mut number_measurements: usize = 0;
-        let mut readout: String = "".to_string();
-        let mut instruction_vec: Vec<AqtInstruction> = Vec::new();
-        for op in circuit {
-            match op {
-                Operation::PragmaRepeatedMeasurement(o) => {
-                    number_measurements = *o.number_measurements();
-                    readout.clone_from(o.readout());
-                    if let Some(x) = call_operation(op)? {
-                        instruction_vec.push(x)
-                    }
-                }
-                Operation::PragmaSetNumberOfMeasurements(o) => {
-                    number_measurements = *o.number_measurements();
-                    readout.clone_from(o.readout());
-                    if let Some(x) = call_operation(op)? {
-                        instruction_vec.push(x)
-                    }
-                }
-                Operation::MeasureQubit(o) => {
-                    number_measurements = 1;
-                    readout.clone_from(o.readout());
-                    if let Some(x) = call_operation(op)? {
-                        instruction_vec.push(x)
-                    }
-                }
-                Operation::DefinitionBit(def) => {
-                    if *def.is_output() {
-                        bit_registers.insert(def.name().clone(), Vec::new());
-                    }
-                    if let Some(x) = call_operation(op)? {
-                        instruction_vec.push(x)
-                    }
-                }
-                Operation::DefinitionFloat(def) => {
-                    if *def.is_output() {
-                        float_registers.insert(def.name().clone(), Vec::new());
-                    }
-                    if let Some(x) = call_operation(op)? {
-                        instruction_vec.push(x)
-                    }
-                }
-                Operation::DefinitionComplex(def) => {
-                    if *def.is_output() {
-                        complex_registers.insert(def.name().clone(), Vec::new());
-                    }
-                    if let Some(x) = call_operation(op)? {
-                        instruction_vec.push(x)
-                    }
-                }
-                _ => {
-                    if let Some(x) = call_operation(op)? {
-                        instruction_vec.push(x)
-                    }
-                }
-            }
-        }
-        let aqt_instruction_circuit = AqtCircuit {
-            number_of_qubits: self.device.number_qubits() as u32,
-            quantum_circuit: instruction_vec,
-            repetitions: number_measurements as u32,
-        };
-        let data = AqtRunData {
-            job_type: "quantum_circuit".to_string(),
-            payload: AqtPayload {
-                circuits: vec![aqt_instruction_circuit],
-            },
-            label: "qoqo_aqt_backend".to_string(),
-        };
+    /// Returns whether the optimised instructions are verified to be equivalent to the translated instructions.
+    pub fn verify_optimisation(&self) -> bool {
+        self.verify_optimisation
+    }
 
-        Ok((
-            data,
-            (bit_registers, float_registers, complex_registers),
-            readout,
-        ))
+    /// Measures the readout errors of the device and caches the calibration in the Backend.
+    ///
+    /// Both calibration circuits are run in a single job on the device of the Backend,
+    /// bypassing the result cache.
+    ///
+    /// # Arguments
+    ///
+    /// `number_measurements` - The number of measurements of each calibration circuit
+    ///
+    /// # Returns
+    ///
+    /// `&ReadoutCalibration` - The new calibration
+    pub fn calibrate_readout(
+        &mut self,
+        number_measurements: usize,
+    ) -> Result<&ReadoutCalibration, RoqoqoBackendError> {
+        let job = self.submit_readout_calibration(number_measurements)?;
+        let calibration = self.wait_for_readout_calibration_interruptible(&job, || Ok(()))?;
+        Ok(self.readout_calibration.insert(calibration))
     }
-    /// Sends get request to obtain details of the resource for a given resource id
-    pub fn get_resource_details(
+
+    /// Submits the readout calibration circuits in a single job without waiting for the result.
+    ///
+    /// A calibration measures the current readout errors, it is never answered from the result cache.
+    ///
+    /// # Arguments
+    ///
+    /// `number_measurements` - The number of measurements of each calibration circuit
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::wait_for_readout_calibration_interruptible]
+    pub fn submit_readout_calibration(
         &self,
-        client: &blocking::Client,
-    ) -> Result<AqtResourceDetails, RoqoqoBackendError> {
-        let get_resource_details_url = format!(
-            "{}resources/{}",
-            self.device.remote_host(),
-            self.device.id()
-        );
-        let client_resp = client
-            .get(get_resource_details_url)
-            .header(ACCEPT, HeaderValue::from_static("application/json"))
-            .bearer_auth(&self.access_token)
-            .send()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{e:?}"),
-            })?;
-        let status_code = client_resp.status();
-        if status_code != reqwest::StatusCode::OK {
-            return Err(RoqoqoBackendError::NetworkError {
-                msg: format!(
-                    "Failed to get resource details. Request to server failed with HTTP status code {status_code:?}"
-                ),
-            });
-        };
-        let resource_response: AqtResourceDetails = client_resp
-            .json::<AqtResourceDetails>()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{e:?}"),
-            })?;
-        Ok(resource_response)
+        number_measurements: usize,
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        let converted = ReadoutCalibration::calibration_circuits(
+            self.device.number_qubits(),
+            number_measurements,
+        )
+        .iter()
+        .map(|circuit| self.convert_circuit_to_aqt_circuit(circuit.iter()))
+        .collect::<Result<Vec<ConvertedCircuit>, RoqoqoBackendError>>()?;
+        self.submit_aqt_run_data(
+            self.aqt_run_data_from_converted(converted, "readout_calibration")?,
+            false,
+        )
     }
-    /// Sends a post request to the AQT device server with `AqtRunData` containing job information and quantum circuits
-    pub fn post_job(
+
+    /// Waits until a readout calibration job has finished and returns the calibration,
+    /// checking regularly whether the wait should be aborted.
+    ///
+    /// The calibration is not cached in the Backend, see [Backend::set_readout_calibration].
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted calibration job
+    /// `interrupt` - Function called regularly while waiting, returning an error aborts the wait
+    ///
+    /// # Returns
+    ///
+    /// `ReadoutCalibration` - The measured calibration
+    pub fn wait_for_readout_calibration_interruptible<F>(
         &self,
-        client: &blocking::Client,
-        data: AqtRunData,
-    ) -> Result<AqtRunResponse, RoqoqoBackendError> {
-        // Url to post quantum circuit to AQT simulator
-        let post_quantum_circuit_url = format!(
-            "{}submit/qoqo-integration/{}",
-            self.device.remote_host(),
-            self.device.id()
-        );
-        let resp = client
-            .post(post_quantum_circuit_url)
-            .header(ACCEPT, HeaderValue::from_static("application/json"))
-            .bearer_auth(&self.access_token)
-            .json(&data)
-            .send()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{e:?}"),
-            })?;
-        let status_code = resp.status();
-        if status_code != reqwest::StatusCode::OK {
-            return Err(RoqoqoBackendError::NetworkError {
-                msg: format!(
-                    "Failed to post job to server. Request to server failed with HTTP status code {status_code:?}"
-                ),
-            });
+        job: &AqtJobHandle,
+        interrupt: F,
+    ) -> Result<ReadoutCalibration, RoqoqoBackendError>
+    where
+        F: FnMut() -> Result<(), RoqoqoBackendError>,
+    {
+        let result = self.wait_for_job_result(job, interrupt)?;
+        let measured = |index: u32| {
+            result
+                .get(&index)
+                .map(|measured| self.decode_shots(measured))
+                .ok_or_else(|| RoqoqoBackendError::GenericError {
+                    msg: "Failed to get measurement due to incorrect retrieval from AQT response"
+                        .to_string(),
+                })
         };
-        let run_response: AqtRunResponse =
-            resp.json::<AqtRunResponse>()
-                .map_err(|e| RoqoqoBackendError::NetworkError {
-                    msg: format!("{e:?}"),
-                })?;
-        Ok(run_response)
+        ReadoutCalibration::from_shots(&self.device.id(), &measured(0)?, &measured(1)?)
+    }
+
+    /// Sets the position of qubit 0 in the packed integer samples returned by the device.
+    ///
+    /// AQT devices put qubit 0 in the least significant bit ([PackedBitOrder::QubitZeroLeastSignificant]), which is the default.
+    /// Results of local devices are always decoded with the default.
+    /// The order of the qubits in counts is chosen separately with [crate::counts::BitOrdering].
+    ///
+    /// # Arguments
+    ///
+    /// `packed_bit_order` - The position of qubit 0 in packed samples
+    pub fn set_packed_bit_order(&mut self, packed_bit_order: PackedBitOrder) {
+        self.packed_bit_order = packed_bit_order;
+    }
+
+    /// Returns the position of qubit 0 in the packed integer samples returned by the device.
+    pub fn packed_bit_order(&self) -> PackedBitOrder {
+        self.packed_bit_order
+    }
+
+    /// Sets the layout of the samples returned by the device.
+    ///
+    /// By default ([SampleLayout::Auto]) shots with a single entry are decoded as packed integers
+    /// and longer shots as lists of bits per qubit.
+    ///
+    /// # Arguments
+    ///
+    /// `sample_layout` - The layout of the samples
+    pub fn set_sample_layout(&mut self, sample_layout: SampleLayout) {
+        self.sample_layout = sample_layout;
+    }
+
+    /// Returns the layout of the samples returned by the device.
+    pub fn sample_layout(&self) -> SampleLayout {
+        self.sample_layout
+    }
+
+    /// Sets the on-disk cache of the results of jobs sent to the AQT servers.
+    ///
+    /// Jobs are identified by the fingerprint of their circuits, the remote host and the resource id of the device,
+    /// jobs found in the cache are not sent to the AQT servers again. Local devices do not use the cache.
+    /// Results that can not be stored in the cache are still returned.
+    ///
+    /// # Arguments
+    ///
+    /// `result_cache` - The result cache, None disables caching
+    pub fn set_result_cache(&mut self, result_cache: Option<ResultCache>) {
+        self.result_cache = result_cache;
+    }
+
+    /// Returns the on-disk cache of the results of jobs sent to the AQT servers.
+    pub fn result_cache(&self) -> Option<&ResultCache> {
+        self.result_cache.as_ref()
+    }
+
+    /// Sets the journal the jobs sent to the AQT servers are recorded in.
+    ///
+    /// Every submitted job is recorded with its register layout and every change of its status,
+    /// so unfinished jobs can be resumed with [Backend::unfinished_jobs] after a restart.
+    ///
+    /// # Arguments
+    ///
+    /// `job_journal` - The job journal, None disables journaling
+    pub fn set_job_journal(&mut self, job_journal: Option<JobJournal>) {
+        self.job_journal = job_journal;
+    }
+
+    /// Returns the journal the jobs sent to the AQT servers are recorded in.
+    pub fn job_journal(&self) -> Option<&JobJournal> {
+        self.job_journal.as_ref()
+    }
+
+    /// Sets the template of the label of submitted jobs, which identifies the jobs in the AQT portal.
+    ///
+    /// The placeholders `{name}`, `{tag}` and `{resource_id}` are replaced by the name of the submission,
+    /// the user tag set with [Backend::set_job_tag] and the resource id of the device.
+    /// The name is given with [Backend::submit_circuit_with_name] and [Backend::submit_circuits_with_name],
+    /// otherwise it is `circuit`, `circuits`, `measurement`, `readout_calibration` or `zero_noise_extrapolation`
+    /// depending on the submitted job. The label is the only metadata of a job accepted by the AQT servers.
+    ///
+    /// # Arguments
+    ///
+    /// `template` - The template of the label, `qoqo_aqt_backend` by default
+    ///
+    /// # Returns
+    ///
+    /// `RoqoqoBackendError::GenericError` - The template contains an unknown placeholder or an unmatched brace
+    pub fn set_job_label(&mut self, template: &str) -> Result<(), RoqoqoBackendError> {
+        render_job_label(template, &[("name", ""), ("tag", ""), ("resource_id", "")])?;
+        self.job_label = template.to_string();
+        Ok(())
+    }
+
+    /// Returns the template of the label of submitted jobs.
+    pub fn job_label(&self) -> &str {
+        &self.job_label
+    }
+
+    /// Sets the user tag inserted into job labels in place of `{tag}`.
+    ///
+    /// # Arguments
+    ///
+    /// `tag` - The user tag, empty by default
+    pub fn set_job_tag(&mut self, tag: &str) {
+        self.job_tag = tag.to_string();
+    }
+
+    /// Returns the user tag inserted into job labels in place of `{tag}`.
+    pub fn job_tag(&self) -> &str {
+        &self.job_tag
+    }
+
+    /// Sets the timeouts, proxy, additional root certificates and user agent of the HTTP client.
+    ///
+    /// The client is created from the settings when the next request is sent and reused afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// `http_client_config` - The settings of the HTTP client
+    ///
+    /// # Returns
+    ///
+    /// `RoqoqoBackendError::NetworkError` - The proxy URL, a root certificate or the user agent is invalid
+    pub fn set_http_client_config(
+        &mut self,
+        http_client_config: HttpClientConfig,
+    ) -> Result<(), RoqoqoBackendError> {
+        http_client_config.validate()?;
+        self.http_client_config = http_client_config;
+        self.http_client = ClientCache::default();
+        Ok(())
+    }
+
+    /// Returns the settings of the HTTP client communicating with the AQT REST API.
+    pub fn http_client_config(&self) -> &HttpClientConfig {
+        &self.http_client_config
+    }
+
+    /// Returns the handles of the journaled jobs that have not finished, failed or been cancelled.
+    ///
+    /// The results of the jobs can be fetched into registers with [Backend::wait_for_job].
+    ///
+    /// # Returns
+    ///
+    /// `Vec<AqtJobHandle>` - The handles of the unfinished jobs in order of submission, empty without a journal
+    pub fn unfinished_jobs(&self) -> Result<Vec<AqtJobHandle>, RoqoqoBackendError> {
+        match &self.job_journal {
+            Some(journal) => Ok(journal
+                .unfinished()?
+                .into_iter()
+                .map(|entry| entry.job)
+                .collect()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Sets the cached calibration of the readout errors, for example a calibration measured earlier.
+    ///
+    /// # Arguments
+    ///
+    /// `readout_calibration` - The calibration, None removes the cached calibration
+    pub fn set_readout_calibration(&mut self, readout_calibration: Option<ReadoutCalibration>) {
+        self.readout_calibration = readout_calibration;
+    }
+
+    /// Returns the cached calibration of the readout errors.
+    pub fn readout_calibration(&self) -> Option<&ReadoutCalibration> {
+        self.readout_calibration.as_ref()
+    }
+
+    /// Sets the method used to correct readout errors, None disables readout error mitigation.
+    ///
+    /// # Arguments
+    ///
+    /// `readout_mitigation` - The method used to correct readout errors
+    pub fn set_readout_mitigation(&mut self, readout_mitigation: Option<MitigationMethod>) {
+        self.readout_mitigation = readout_mitigation;
+    }
+
+    /// Returns the method used to correct readout errors, None when readout error mitigation is disabled.
+    pub fn readout_mitigation(&self) -> Option<MitigationMethod> {
+        self.readout_mitigation
+    }
+
+    /// Corrects the distribution of measured shots for readout errors with the cached calibration.
+    ///
+    /// # Arguments
+    ///
+    /// `shots` - The measured shots of a bit register
+    ///
+    /// # Returns
+    ///
+    /// `HashMap<Vec<bool>, f64>` - The corrected probability of each measured bitstring
+    pub fn mitigate_distribution(
+        &self,
+        shots: &[Vec<bool>],
+    ) -> Result<HashMap<Vec<bool>, f64>, RoqoqoBackendError> {
+        let (calibration, method) = self.mitigation_settings()?;
+        calibration.correct_distribution(shots, method)
+    }
+
+    /// Evaluates the expectation values of a PauliZProduct measurement with readout errors corrected.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement that is evaluated
+    /// `bit_registers` - The measured bit registers of the measurement
+    pub fn evaluate_mitigated(
+        &self,
+        measurement: &PauliZProduct,
+        bit_registers: &HashMap<String, BitOutputRegister>,
+    ) -> Result<Option<HashMap<String, f64>>, RoqoqoBackendError> {
+        let (calibration, method) = self.mitigation_settings()?;
+        calibration.evaluate_pauli_z_product(measurement, bit_registers, method)
+    }
+
+    /// Runs a PauliZProduct measurement and evaluates the expectation values with readout errors corrected.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement that is run
+    pub fn run_measurement_mitigated(
+        &self,
+        measurement: &PauliZProduct,
+    ) -> Result<Option<HashMap<String, f64>>, RoqoqoBackendError> {
+        let (bit_registers, _, _) = self.run_measurement_registers(measurement)?;
+        self.evaluate_mitigated(measurement, &bit_registers)
+    }
+
+    /// Returns the calibration and method used for readout error mitigation
+    fn mitigation_settings(
+        &self,
+    ) -> Result<(&ReadoutCalibration, MitigationMethod), RoqoqoBackendError> {
+        let method = self
+            .readout_mitigation
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "Readout error mitigation is disabled".to_string(),
+            })?;
+        let calibration =
+            self.readout_calibration
+                .as_ref()
+                .ok_or_else(|| RoqoqoBackendError::GenericError {
+                    msg: "Readout errors have not been calibrated".to_string(),
+                })?;
+        if calibration.device_id() != self.device.id() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Readout calibration was measured on {} instead of {}",
+                    calibration.device_id(),
+                    self.device.id()
+                ),
+            });
+        }
+        Ok((calibration, method))
+    }
+
+    /// Returns the reduction of the number of instructions achieved by optimising the translated circuit.
+    ///
+    /// The circuits emulating pragmas are included in the report.
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - The circuit that is translated and optimised
+    ///
+    /// # Returns
+    ///
+    /// `OptimisationReport` - The combined reduction of all translated circuits
+    pub fn optimisation_report(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<OptimisationReport, RoqoqoBackendError> {
+        let converted = self.convert_circuit_to_aqt_circuit(circuit.iter())?;
+        let mut report = OptimisationReport::default();
+        let aqt_circuits = std::iter::once(&converted.circuit).chain(
+            converted
+                .estimates
+                .iter()
+                .map(|(estimate_circuit, _)| estimate_circuit),
+        );
+        for aqt_circuit in aqt_circuits {
+            let (_, circuit_report) =
+                optimisation::optimise_instructions(&aqt_circuit.quantum_circuit);
+            report.instructions_before += circuit_report.instructions_before;
+            report.instructions_after += circuit_report.instructions_after;
+            report.single_qubit_instructions_before +=
+                circuit_report.single_qubit_instructions_before;
+            report.single_qubit_instructions_after +=
+                circuit_report.single_qubit_instructions_after;
+            report.two_qubit_instructions_before += circuit_report.two_qubit_instructions_before;
+            report.two_qubit_instructions_after += circuit_report.two_qubit_instructions_after;
+        }
+        Ok(report)
+    }
+
+    /// Records all following interactions with the AQT REST API to a cassette file.
+    ///
+    /// The file is rewritten after every interaction. Headers are not recorded and any occurrence
+    /// of the access token is scrubbed, so cassettes can be committed as test data.
+    ///
+    /// # Arguments
+    ///
+    /// `path` - The path of the cassette file
+    pub fn record_cassette(&mut self, path: &Path) {
+        self.cassette = Some(CassettePlayer::record(path));
+    }
+
+    /// Answers all following requests to the AQT REST API from a recorded cassette file.
+    ///
+    /// No requests are sent to the remote host while replaying and waiting for jobs does not sleep.
+    /// Requests are matched to recorded interactions by method and path in the order they were recorded,
+    /// a request whose body differs from the recorded body returns an error.
+    ///
+    /// # Arguments
+    ///
+    /// `path` - The path of the cassette file
+    pub fn replay_cassette(&mut self, path: &Path) -> Result<(), RoqoqoBackendError> {
+        self.cassette = Some(CassettePlayer::replay(path)?);
+        Ok(())
+    }
+
+    /// Stops recording or replaying a cassette, following requests are sent to the remote host.
+    pub fn stop_cassette(&mut self) {
+        self.cassette = None;
+    }
+
+    /// Returns whether interactions are currently recorded or replayed, None when no cassette is used.
+    pub fn cassette_mode(&self) -> Option<CassetteMode> {
+        self.cassette.as_ref().map(|cassette| cassette.mode())
+    }
+
+    /// Creates an AQT jSON represenstaion of a [roqoqo::Circuit].
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - An iterator over Operations that represents a circuit that is translated
+    ///
+    /// # Returns
+    ///
+    /// `String` - JSON representation of a roqoqo circuit
+    pub fn to_aqt_json<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> Result<String, RoqoqoBackendError> {
+        let mut instruction_vec: Vec<AqtInstruction> = Vec::new();
+        for op in circuit {
+            instruction_vec.extend(call_operation_sequence(op)?);
+        }
+
+        Ok(serde_json::to_string(&instruction_vec).unwrap())
+    }
+
+    /// Converts a [roqoqo::Circuit] to `AqtRunData` object that can be sent to AQT device
+    /// to process and initialises registers for measurement
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - An iterator over Operations that represents a circuit that is translated
+    ///
+    /// # Returns
+    ///
+    ///  `(AqtRunData, RegisterDefinition, String)` - Object of `AqtRunData`, registers, readout for registers
+    pub fn convert_circuit_to_aqt_instructions<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> Result<(AqtRunData, RegisterDefinition, String), RoqoqoBackendError> {
+        let converted = self.convert_circuit_to_aqt_circuit(circuit)?;
+        let readout = converted.readout.clone();
+        let (aqt_run_data, registers, _, _) =
+            self.aqt_run_data_from_converted(vec![converted], "circuit")?;
+        Ok((aqt_run_data, registers, readout))
+    }
+
+    /// Converts several [roqoqo::Circuit]s to a single `AqtRunData` object so they can be run in one job
+    ///
+    /// # Arguments
+    ///
+    /// `circuits` - The circuits that are translated
+    ///
+    /// # Returns
+    ///
+    ///  `(AqtRunData, RegisterDefinition, Vec<String>)` - Object of `AqtRunData`, registers of all circuits, readout for registers of each circuit
+    pub fn convert_circuits_to_aqt_instructions(
+        &self,
+        circuits: &[Circuit],
+    ) -> Result<(AqtRunData, RegisterDefinition, Vec<String>), RoqoqoBackendError> {
+        let converted = circuits
+            .iter()
+            .map(|circuit| self.convert_circuit_to_aqt_circuit(circuit.iter()))
+            .collect::<Result<Vec<ConvertedCircuit>, RoqoqoBackendError>>()?;
+        let (aqt_run_data, registers, readouts, _) =
+            self.aqt_run_data_from_converted(converted, "circuits")?;
+        Ok((aqt_run_data, registers, readouts))
+    }
+
+    /// Analyses the translation of a circuit without stopping at the first operation that cannot be translated.
+    ///
+    /// Every operation is translated separately and classified as translated, ignored, unsupported
+    /// or blocked by symbolic parameters. The native gate counts and the payload size are obtained
+    /// by translating all translatable operations, taking the pragma emulation settings into account.
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - The circuit that is analysed
+    ///
+    /// # Returns
+    ///
+    /// `TranslationReport` - The result of the analysis
+    pub fn translation_report(&self, circuit: &Circuit) -> TranslationReport {
+        let mut report = TranslationReport {
+            number_operations: circuit.len(),
+            ..TranslationReport::default()
+        };
+        let mut translatable = Circuit::new();
+        for (position, op) in circuit.iter().enumerate() {
+            match self.convert_circuit_to_aqt_circuit(std::iter::once(op)) {
+                Ok(converted) => {
+                    if converted.circuit.quantum_circuit.is_empty()
+                        && converted.estimates.is_empty()
+                    {
+                        *report
+                            .ignored_operations
+                            .entry(op.hqslang().to_string())
+                            .or_insert(0) += 1;
+                    }
+                    translatable.add_operation(op.clone());
+                }
+                Err(RoqoqoBackendError::OperationNotInBackend { .. }) => {
+                    *report
+                        .unsupported_counts
+                        .entry(op.hqslang().to_string())
+                        .or_insert(0) += 1;
+                    report.unsupported_operations.push(UntranslatedOperation {
+                        position,
+                        hqslang: op.hqslang().to_string(),
+                        reason: "Operation is not supported by the AQT backend".to_string(),
+                    });
+                }
+                Err(err) => {
+                    let untranslated = UntranslatedOperation {
+                        position,
+                        hqslang: op.hqslang().to_string(),
+                        reason: err.to_string(),
+                    };
+                    if op.is_parametrized() {
+                        report.symbolic_parameters.push(untranslated);
+                    } else {
+                        *report
+                            .unsupported_counts
+                            .entry(op.hqslang().to_string())
+                            .or_insert(0) += 1;
+                        report.unsupported_operations.push(untranslated);
+                    }
+                }
+            }
+        }
+        // Every operation of the translatable circuit has been translated successfully on its own
+        if let Ok((aqt_run_data, _, _, _)) = self
+            .convert_circuit_to_aqt_circuit(translatable.iter())
+            .and_then(|converted| self.aqt_run_data_from_converted(vec![converted], "circuit"))
+        {
+            for aqt_circuit in aqt_run_data.payload.circuits.iter() {
+                for instruction in aqt_circuit.quantum_circuit.iter() {
+                    let name = match instruction {
+                        AqtInstruction::RZ { .. } => "RZ",
+                        AqtInstruction::R { .. } => "R",
+                        AqtInstruction::RXX { .. } => "RXX",
+                        AqtInstruction::MEASURE => "MEASURE",
+                    };
+                    *report
+                        .native_gate_counts
+                        .entry(name.to_string())
+                        .or_insert(0) += 1;
+                }
+            }
+            report.payload_size = serde_json::to_string(&aqt_run_data)
+                .map(|json| json.len())
+                .unwrap_or(0);
+        }
+        report
+    }
+
+    /// Combines translated circuits into a single `AqtRunData` object
+    ///
+    /// The measured circuits come first, followed by the circuits emulating pragmas.
+    /// Circuits without a measurement are only sent when they do not emulate any pragmas.
+    /// The name of the submission is inserted into the label of the job.
+    fn aqt_run_data_from_converted(
+        &self,
+        converted: Vec<ConvertedCircuit>,
+        name: &str,
+    ) -> Result<
+        (
+            AqtRunData,
+            RegisterDefinition,
+            Vec<String>,
+            Vec<AqtEstimate>,
+        ),
+        RoqoqoBackendError,
+    > {
+        let mut aqt_circuits: Vec<(AqtCircuit, f64)> = Vec::with_capacity(converted.len());
+        let mut readouts: Vec<String> = Vec::with_capacity(converted.len());
+        let mut estimate_circuits: Vec<(AqtCircuit, f64)> = Vec::new();
+        let mut estimates: Vec<AqtEstimate> = Vec::new();
+        let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
+        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
+        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
+        for converted_circuit in converted {
+            let (tmp_bit_reg, tmp_float_reg, tmp_complex_reg) = converted_circuit.registers;
+            if !converted_circuit.readout.is_empty() || converted_circuit.estimates.is_empty() {
+                aqt_circuits.push((converted_circuit.circuit, converted_circuit.noise_scale));
+                readouts.push(converted_circuit.readout);
+            }
+            for (estimate_circuit, estimate) in converted_circuit.estimates {
+                estimate_circuits.push((estimate_circuit, converted_circuit.noise_scale));
+                estimates.push(estimate);
+            }
+            bit_registers.extend(tmp_bit_reg);
+            float_registers.extend(tmp_float_reg);
+            complex_registers.extend(tmp_complex_reg);
+        }
+        aqt_circuits.extend(estimate_circuits);
+        Ok((
+            self.aqt_run_data(aqt_circuits, name)?,
+            (bit_registers, float_registers, complex_registers),
+            readouts,
+            estimates,
+        ))
+    }
+
+    /// Wraps translated circuits in the `AqtRunData` sent to the AQT device
+    ///
+    /// The instructions are optimised when enabled, afterwards the noise of each circuit is amplified
+    /// by folding according to its noise scale, so the optimisation can not remove the folded gates.
+    fn aqt_run_data(
+        &self,
+        circuits: Vec<(AqtCircuit, f64)>,
+        name: &str,
+    ) -> Result<AqtRunData, RoqoqoBackendError> {
+        let (mut circuits, noise_scales): (Vec<AqtCircuit>, Vec<f64>) =
+            circuits.into_iter().unzip();
+        if self.optimise_instructions {
+            for circuit in circuits.iter_mut() {
+                let (optimised, _) = optimisation::optimise_instructions(&circuit.quantum_circuit);
+                if self.verify_optimisation
+                    && !optimisation::verify_equivalence(&circuit.quantum_circuit, &optimised)?
+                {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: "Optimised AQT instructions are not equivalent to the translated instructions"
+                            .to_string(),
+                    });
+                }
+                circuit.quantum_circuit = optimised;
+            }
+        }
+        for (circuit, noise_scale) in circuits.iter_mut().zip(noise_scales) {
+            if noise_scale != 1.0 {
+                circuit.quantum_circuit =
+                    noise_amplification::fold_instructions(&circuit.quantum_circuit, noise_scale)?;
+            }
+            if circuit.quantum_circuit.len() > self.device.max_number_instructions() {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Circuit with {} instructions exceeds the maximum of {} instructions of the device",
+                        circuit.quantum_circuit.len(),
+                        self.device.max_number_instructions()
+                    ),
+                });
+            }
+        }
+        Ok(AqtRunData {
+            job_type: "quantum_circuit".to_string(),
+            payload: AqtPayload { circuits },
+            label: render_job_label(
+                &self.job_label,
+                &[
+                    ("name", name),
+                    ("tag", &self.job_tag),
+                    ("resource_id", &self.device.id()),
+                ],
+            )?,
+        })
+    }
+
+    /// Translates a single circuit to an `AqtCircuit` and initialises its registers
+    fn convert_circuit_to_aqt_circuit<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> Result<ConvertedCircuit, RoqoqoBackendError> {
+        let mut bit_registers: HashMap<String, BitOutputRegister> = HashMap::new();
+        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
+        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
+
+        let mut number_measurements: usize = 0;
+        let mut readout: String = "".to_string();
+        let mut instruction_vec: Vec<AqtInstruction> = Vec::new();
+        let mut estimates: Vec<(AqtCircuit, AqtEstimate)> = Vec::new();
+        let mut noise_scale: f64 = 1.0;
+        for op in circuit {
+            match (op, self.pragma_emulation_shots) {
+                (Operation::PragmaGetPauliProduct(o), Some(shots)) => {
+                    let mut estimate_instructions = instruction_vec.clone();
+                    for pragma_op in o.circuit().iter() {
+                        self.extend_instructions(&mut estimate_instructions, pragma_op)?;
+                    }
+                    let mut qubit_paulis: Vec<(&usize, &usize)> = o.qubit_paulis().iter().collect();
+                    qubit_paulis.sort_unstable();
+                    let mut qubits: Vec<usize> = Vec::new();
+                    for (qubit, pauli) in qubit_paulis {
+                        // Rotate the eigenbasis of the Pauli operator into the Z basis
+                        let (phi, theta) = match pauli {
+                            0 => continue,
+                            // RotateY(-pi/2)
+                            1 => (0.5, -0.5),
+                            // RotateX(pi/2)
+                            2 => (0.0, 0.5),
+                            3 => (0.0, 0.0),
+                            _ => {
+                                return Err(RoqoqoBackendError::GenericError {
+                                    msg: format!(
+                                        "Unknown Pauli operator {pauli} in PragmaGetPauliProduct"
+                                    ),
+                                })
+                            }
+                        };
+                        if theta != 0.0 {
+                            // Normalised to the theta range [0, 1] accepted by AQT devices
+                            estimate_instructions
+                                .push(interface::r_instruction(theta, phi, *qubit));
+                        }
+                        qubits.push(*qubit);
+                    }
+                    estimate_instructions.push(AqtInstruction::MEASURE);
+                    estimates.push((
+                        AqtCircuit {
+                            number_of_qubits: self.device.number_qubits() as u32,
+                            quantum_circuit: estimate_instructions,
+                            repetitions: shots as u32,
+                        },
+                        AqtEstimate::PauliProduct {
+                            readout: o.readout().clone(),
+                            qubits,
+                        },
+                    ));
+                }
+                (Operation::PragmaGetOccupationProbability(o), Some(shots)) => {
+                    let mut estimate_instructions = instruction_vec.clone();
+                    if let Some(pragma_circuit) = o.circuit() {
+                        for pragma_op in pragma_circuit.iter() {
+                            self.extend_instructions(&mut estimate_instructions, pragma_op)?;
+                        }
+                    }
+                    estimate_instructions.push(AqtInstruction::MEASURE);
+                    estimates.push((
+                        AqtCircuit {
+                            number_of_qubits: self.device.number_qubits() as u32,
+                            quantum_circuit: estimate_instructions,
+                            repetitions: shots as u32,
+                        },
+                        AqtEstimate::OccupationProbability {
+                            readout: o.readout().clone(),
+                        },
+                    ));
+                }
+                (Operation::PragmaRepeatedMeasurement(o), _) => {
+                    number_measurements = *o.number_measurements();
+                    readout.clone_from(o.readout());
+                    if let Some(x) = call_operation(op)? {
+                        instruction_vec.push(x)
+                    }
+                }
+                (Operation::PragmaSetNumberOfMeasurements(o), _) => {
+                    number_measurements = *o.number_measurements();
+                    readout.clone_from(o.readout());
+                    if let Some(x) = call_operation(op)? {
+                        instruction_vec.push(x)
+                    }
+                }
+                (Operation::MeasureQubit(o), _) => {
+                    number_measurements = 1;
+                    readout.clone_from(o.readout());
+                    if let Some(x) = call_operation(op)? {
+                        instruction_vec.push(x)
+                    }
+                }
+                (Operation::DefinitionBit(def), _) => {
+                    if *def.is_output() {
+                        bit_registers.insert(def.name().clone(), Vec::new());
+                    }
+                    if let Some(x) = call_operation(op)? {
+                        instruction_vec.push(x)
+                    }
+                }
+                (Operation::DefinitionFloat(def), _) => {
+                    if *def.is_output() {
+                        float_registers.insert(def.name().clone(), Vec::new());
+                    }
+                    if let Some(x) = call_operation(op)? {
+                        instruction_vec.push(x)
+                    }
+                }
+                (Operation::DefinitionComplex(def), _) => {
+                    if *def.is_output() {
+                        complex_registers.insert(def.name().clone(), Vec::new());
+                    }
+                    if let Some(x) = call_operation(op)? {
+                        instruction_vec.push(x)
+                    }
+                }
+                (Operation::PragmaBoostNoise(o), _) => {
+                    noise_scale *= *o.noise_coefficient().float()?;
+                }
+                _ => {
+                    self.extend_instructions(&mut instruction_vec, op)?;
+                }
+            }
+        }
+        let aqt_instruction_circuit = AqtCircuit {
+            number_of_qubits: self.device.number_qubits() as u32,
+            quantum_circuit: instruction_vec,
+            repetitions: number_measurements as u32,
+        };
+
+        Ok(ConvertedCircuit {
+            circuit: aqt_instruction_circuit,
+            registers: (bit_registers, float_registers, complex_registers),
+            readout,
+            estimates,
+            noise_scale,
+        })
+    }
+    /// Appends the instructions of an operation that is translated without changing the registers
+    ///
+    /// PragmaLoop operations are unrolled with at most the instructions remaining for the device,
+    /// all other operations are translated with [call_operation_sequence].
+    fn extend_instructions(
+        &self,
+        instructions: &mut Vec<AqtInstruction>,
+        operation: &Operation,
+    ) -> Result<(), RoqoqoBackendError> {
+        match operation {
+            Operation::PragmaLoop(o) => {
+                let remaining = self
+                    .device
+                    .max_number_instructions()
+                    .saturating_sub(instructions.len());
+                instructions.extend(call_loop(o, remaining)?);
+            }
+            _ => instructions.extend(call_operation_sequence(operation)?),
+        }
+        Ok(())
+    }
+    /// Sends get request to obtain details of the resource for a given resource id
+    pub fn get_resource_details(
+        &self,
+        client: &blocking::Client,
+    ) -> Result<AqtResourceDetails, RoqoqoBackendError> {
+        let (status_code, body) = self.send_request(
+            client,
+            reqwest::Method::GET,
+            &format!("resources/{}", self.device.id()),
+            None,
+        )?;
+        if status_code != reqwest::StatusCode::OK {
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Failed to get resource details. Request to server failed with HTTP status code {status_code:?}"
+                ),
+            });
+        };
+        let resource_response: AqtResourceDetails =
+            serde_json::from_str(&body).map_err(|e| RoqoqoBackendError::NetworkError {
+                msg: format!("{e:?}"),
+            })?;
+        Ok(resource_response)
+    }
+    /// Sends a post request to the AQT device server with `AqtRunData` containing job information and quantum circuits
+    pub fn post_job(
+        &self,
+        client: &blocking::Client,
+        data: AqtRunData,
+    ) -> Result<AqtRunResponse, RoqoqoBackendError> {
+        // Path to post quantum circuit to AQT simulator
+        let (status_code, body) = self.send_request(
+            client,
+            reqwest::Method::POST,
+            &format!("submit/{WORKSPACE_ID}/{}", self.device.id()),
+            Some(&data),
+        )?;
+        if status_code != reqwest::StatusCode::OK {
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Failed to post job to server. Request to server failed with HTTP status code {status_code:?}"
+                ),
+            });
+        };
+        let run_response: AqtRunResponse =
+            serde_json::from_str(&body).map_err(|e| RoqoqoBackendError::NetworkError {
+                msg: format!("{e:?}"),
+            })?;
+        Ok(run_response)
     }
     /// Send get request to the AQT device server to obtain the status of the current job and result of the simulation
     pub fn get_result(
         &self,
-        client: &blocking::Client,
-        job_id: &str,
-    ) -> Result<AqtRunResponse, RoqoqoBackendError> {
-        // Url to obtain result of simulation from AQT simulator
-        let get_result_url = format!("{}result/{}", self.device.remote_host(), job_id);
+        client: &blocking::Client,
+        job_id: &str,
+    ) -> Result<AqtRunResponse, RoqoqoBackendError> {
+        // Path to obtain result of simulation from AQT simulator
+        let (status_code, body) = self.send_request(
+            client,
+            reqwest::Method::GET,
+            &format!("result/{job_id}"),
+            None,
+        )?;
+        if status_code != reqwest::StatusCode::OK {
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Failed to get result from server. Request to server failed with HTTP status code {status_code:?}"),
+            });
+        }
+        let run_response: AqtRunResponse =
+            serde_json::from_str(&body).map_err(|e| RoqoqoBackendError::NetworkError {
+                msg: format!("second {e:?}"),
+            })?;
+
+        Ok(run_response)
+    }
+
+    /// Sends a delete request to the AQT device server to cancel a job that has not finished yet
+    pub fn delete_job(
+        &self,
+        client: &blocking::Client,
+        job_id: &str,
+    ) -> Result<(), RoqoqoBackendError> {
+        // Path to cancel a job on the AQT simulator
+        let (status_code, _) = self.send_request(
+            client,
+            reqwest::Method::DELETE,
+            &format!("jobs/{job_id}"),
+            None,
+        )?;
+        if status_code != reqwest::StatusCode::OK {
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Failed to cancel job on server. Request to server failed with HTTP status code {status_code:?}"),
+            });
+        }
+        Ok(())
+    }
+
+    /// Sends a request to the remote host of the device and returns the status code and body of the response
+    ///
+    /// All requests to the AQT REST API are sent by this function, so they can be recorded to or replayed from a cassette.
+    ///
+    /// # Arguments
+    ///
+    /// `client` - The http client sending the request
+    /// `method` - The HTTP method of the request
+    /// `path` - The path of the request relative to the remote host
+    /// `data` - The job sent as JSON body of the request
+    fn send_request(
+        &self,
+        client: &blocking::Client,
+        method: reqwest::Method,
+        path: &str,
+        data: Option<&AqtRunData>,
+    ) -> Result<(reqwest::StatusCode, String), RoqoqoBackendError> {
+        let _span = aqt_span!(DEBUG, "aqt_request", method = %method, path);
+        if let Some(cassette) = self
+            .cassette
+            .as_ref()
+            .filter(|cassette| cassette.mode() == CassetteMode::Replay)
+        {
+            aqt_event!(debug, "Replaying response from cassette");
+            let (status_code, body) = cassette.replay_interaction(
+                method.as_str(),
+                path,
+                data.and_then(|data| serde_json::to_value(data).ok()),
+                &self.access_token,
+            )?;
+            let status_code = reqwest::StatusCode::from_u16(status_code).map_err(|e| {
+                RoqoqoBackendError::NetworkError {
+                    msg: format!("{e:?}"),
+                }
+            })?;
+            return Ok((status_code, body));
+        }
+        let url = format!("{}{path}", self.device.remote_host());
+        aqt_event!(debug, %url, "Sending request to AQT REST API");
+        #[cfg(feature = "tracing")]
+        let start = time::Instant::now();
+        let mut request = client
+            .request(method.clone(), &url)
+            .header(ACCEPT, HeaderValue::from_static("application/json"))
+            .bearer_auth(&self.access_token);
+        if let Some(data) = data {
+            request = request.json(data);
+        }
+        let response = request.send().map_err(|e| {
+            let msg = format!("{e:?}");
+            aqt_event!(
+                warn,
+                %url,
+                error = %instrumentation::redact(&msg, &self.access_token),
+                "Request to AQT REST API failed"
+            );
+            RoqoqoBackendError::NetworkError { msg }
+        })?;
+        let status_code = response.status();
+        aqt_event!(
+            debug,
+            status = status_code.as_u16(),
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            "Received response from AQT REST API"
+        );
+        let body = response
+            .text()
+            .map_err(|e| RoqoqoBackendError::NetworkError {
+                msg: format!("{e:?}"),
+            })?;
+        if let Some(cassette) = &self.cassette {
+            cassette.record_interaction(
+                CassetteInteraction {
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    request_body: data.and_then(|data| serde_json::to_value(data).ok()),
+                    status: status_code.as_u16(),
+                    response_body: body.clone(),
+                },
+                &self.access_token,
+            )?;
+        }
+        Ok((status_code, body))
+    }
+
+    /// Returns the blocking http client used to communicate with the AQT device, created by the first call
+    fn client(&self) -> Result<blocking::Client, RoqoqoBackendError> {
+        self.http_client
+            .get_or_build(&self.http_client_config, self.device.is_https())
+    }
+
+    /// Checks that the AQT resource is online and provides enough qubits for the device
+    fn check_resource(&self, client: &blocking::Client) -> Result<(), RoqoqoBackendError> {
+        let _span = aqt_span!(DEBUG, "resource_lookup", resource_id = %self.device.id());
+        let aqt_resources_details = self.get_resource_details(client)?;
+        aqt_event!(
+            debug,
+            status = %aqt_resources_details.status,
+            available_qubits = aqt_resources_details.available_qubits,
+            "Looked up AQT resource"
+        );
+        if aqt_resources_details.status != "online" {
+            aqt_event!(warn, status = %aqt_resources_details.status, "AQT resource is not online");
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: "AQT resource is currently ofline".to_string(),
+            });
+        }
+        if aqt_resources_details.available_qubits < self.device.number_qubits() as u32 {
+            aqt_event!(
+                warn,
+                available_qubits = aqt_resources_details.available_qubits,
+                required_qubits = self.device.number_qubits(),
+                "AQT resource has too few qubits"
+            );
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Insuffient qubits on backend device. Maximum available qubits: {}.",
+                    aqt_resources_details.available_qubits
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Submits a circuit to the AQT device without waiting for the result.
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - An iterator over Operations that represents a circuit that is submitted
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::poll_job] or [Backend::wait_for_job]
+    pub fn submit_circuit_iterator<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        // Convert circuit to aqt instructions
+        let converted = self.convert_circuit_to_aqt_circuit(circuit)?;
+        self.submit_aqt_run_data(
+            self.aqt_run_data_from_converted(vec![converted], "circuit")?,
+            true,
+        )
+    }
+
+    /// Submits several circuits to the AQT device in a single job without waiting for the result.
+    ///
+    /// The output registers of all circuits are combined, results written to the same register are concatenated.
+    ///
+    /// # Arguments
+    ///
+    /// `circuits` - The circuits that are submitted
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::poll_job] or [Backend::wait_for_job]
+    pub fn submit_circuits(
+        &self,
+        circuits: &[Circuit],
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        self.submit_circuits_with_name(circuits, "circuits")
+    }
+
+    /// Submits several circuits to the AQT device in a single job with a name inserted into the job label.
+    ///
+    /// # Arguments
+    ///
+    /// `circuits` - The circuits that are submitted
+    /// `name` - The name replacing `{name}` in the job label, see [Backend::set_job_label]
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::poll_job] or [Backend::wait_for_job]
+    pub fn submit_circuits_with_name(
+        &self,
+        circuits: &[Circuit],
+        name: &str,
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        let converted = circuits
+            .iter()
+            .map(|circuit| self.convert_circuit_to_aqt_circuit(circuit.iter()))
+            .collect::<Result<Vec<ConvertedCircuit>, RoqoqoBackendError>>()?;
+        self.submit_aqt_run_data(self.aqt_run_data_from_converted(converted, name)?, true)
+    }
+
+    /// Submits all circuits of a measurement to the AQT device in a single job without waiting for the result.
+    ///
+    /// The constant circuit of the measurement is prepended to every circuit of the measurement.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement whose circuits are submitted
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::poll_job] or [Backend::wait_for_job]
+    pub fn submit_measurement<M: Measure>(
+        &self,
+        measurement: &M,
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        self.submit_measurement_with_name(measurement, "measurement")
+    }
+
+    /// Submits all circuits of a measurement in a single job with a name inserted into the job label.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement whose circuits are submitted
+    /// `name` - The name replacing `{name}` in the job label, see [Backend::set_job_label]
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::poll_job] or [Backend::wait_for_job]
+    pub fn submit_measurement_with_name<M: Measure>(
+        &self,
+        measurement: &M,
+        name: &str,
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        self.submit_circuits_with_name(&measurement_circuits(measurement), name)
+    }
+
+    /// Runs a measurement at several noise scales in a single job and extrapolates the expectation values to zero noise.
+    ///
+    /// Every circuit of the measurement is run once for each scale factor, with its noise amplified by unitary folding
+    /// as if the circuit started with a PragmaBoostNoise of the scale factor.
+    /// The expectation values are extrapolated with a least squares polynomial fit of the given order.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement that is run
+    /// `scale_factors` - The noise scale factors, odd integers are implemented exactly
+    /// `order` - The order of the polynomial used for the extrapolation
+    ///
+    /// # Returns
+    ///
+    /// `ZeroNoiseExtrapolation` - The expectation values for all scale factors and extrapolated to zero noise
+    pub fn run_measurement_zero_noise_extrapolation<M: MeasureExpectationValues>(
+        &self,
+        measurement: &M,
+        scale_factors: &[f64],
+        order: usize,
+    ) -> Result<ZeroNoiseExtrapolation, RoqoqoBackendError> {
+        let job =
+            self.submit_measurement_zero_noise_extrapolation(measurement, scale_factors, order)?;
+        self.wait_for_zero_noise_extrapolation_interruptible(measurement, &job, || Ok(()))
+    }
+
+    /// Submits a measurement at several noise scales in a single job without waiting for the result.
+    ///
+    /// See [Backend::run_measurement_zero_noise_extrapolation].
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement whose circuits are submitted
+    /// `scale_factors` - The noise scale factors, odd integers are implemented exactly
+    /// `order` - The order of the polynomial used for the extrapolation
+    ///
+    /// # Returns
+    ///
+    /// `ZeroNoiseExtrapolationJob` - Handle of the submitted job that can be passed to [Backend::wait_for_zero_noise_extrapolation_interruptible]
+    pub fn submit_measurement_zero_noise_extrapolation<M: MeasureExpectationValues>(
+        &self,
+        measurement: &M,
+        scale_factors: &[f64],
+        order: usize,
+    ) -> Result<ZeroNoiseExtrapolationJob, RoqoqoBackendError> {
+        self.submit_measurement_zero_noise_extrapolation_with_name(
+            measurement,
+            scale_factors,
+            order,
+            "zero_noise_extrapolation",
+        )
+    }
+
+    /// Submits a measurement at several noise scales in a single job with a name inserted into the job label.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement whose circuits are submitted
+    /// `scale_factors` - The noise scale factors, odd integers are implemented exactly
+    /// `order` - The order of the polynomial used for the extrapolation
+    /// `name` - The name replacing `{name}` in the job label, see [Backend::set_job_label]
+    ///
+    /// # Returns
+    ///
+    /// `ZeroNoiseExtrapolationJob` - Handle of the submitted job that can be passed to [Backend::wait_for_zero_noise_extrapolation_interruptible]
+    pub fn submit_measurement_zero_noise_extrapolation_with_name<M: MeasureExpectationValues>(
+        &self,
+        measurement: &M,
+        scale_factors: &[f64],
+        order: usize,
+        name: &str,
+    ) -> Result<ZeroNoiseExtrapolationJob, RoqoqoBackendError> {
+        // Checks that the scale factors allow the extrapolation before the job is submitted
+        noise_amplification::extrapolate_to_zero(
+            scale_factors,
+            &vec![0.0; scale_factors.len()],
+            order,
+        )?;
+        let circuits = measurement_circuits(measurement);
+        let mut converted: Vec<ConvertedCircuit> = Vec::new();
+        let mut layouts: Vec<(usize, usize)> = Vec::with_capacity(scale_factors.len());
+        for scale_factor in scale_factors {
+            let (mut number_measured, mut number_estimates) = (0, 0);
+            for circuit in circuits.iter() {
+                let mut converted_circuit = self.convert_circuit_to_aqt_circuit(circuit.iter())?;
+                converted_circuit.noise_scale *= scale_factor;
+                if !converted_circuit.readout.is_empty() || converted_circuit.estimates.is_empty() {
+                    number_measured += 1;
+                }
+                number_estimates += converted_circuit.estimates.len();
+                converted.push(converted_circuit);
+            }
+            layouts.push((number_measured, number_estimates));
+        }
+        let job =
+            self.submit_aqt_run_data(self.aqt_run_data_from_converted(converted, name)?, true)?;
+        Ok(ZeroNoiseExtrapolationJob {
+            job,
+            scale_factors: scale_factors.to_vec(),
+            order,
+            layouts,
+        })
+    }
+
+    /// Waits until a zero-noise extrapolation job has finished and extrapolates the expectation values,
+    /// checking regularly whether the wait should be aborted.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement that was submitted
+    /// `job` - Handle of the submitted zero-noise extrapolation job
+    /// `interrupt` - Function called regularly while waiting, returning an error aborts the wait
+    ///
+    /// # Returns
+    ///
+    /// `ZeroNoiseExtrapolation` - The expectation values for all scale factors and extrapolated to zero noise
+    pub fn wait_for_zero_noise_extrapolation_interruptible<M, F>(
+        &self,
+        measurement: &M,
+        job: &ZeroNoiseExtrapolationJob,
+        interrupt: F,
+    ) -> Result<ZeroNoiseExtrapolation, RoqoqoBackendError>
+    where
+        M: MeasureExpectationValues,
+        F: FnMut() -> Result<(), RoqoqoBackendError>,
+    {
+        let ZeroNoiseExtrapolationJob {
+            job,
+            scale_factors,
+            order,
+            layouts,
+        } = job;
+        let result = self.wait_for_job_result(job, interrupt)?;
 
-        let client_resp = client
-            .get(get_result_url)
-            .header(ACCEPT, HeaderValue::from_static("application/json"))
-            .bearer_auth(&self.access_token)
-            .send()
-            .map_err(|e| RoqoqoBackendError::NetworkError {
-                msg: format!("{e:?}"),
-            })?;
-        let status_code = client_resp.status();
-        if status_code != reqwest::StatusCode::OK {
-            return Err(RoqoqoBackendError::NetworkError {
-                msg: format!(
-                    "Failed to get result from server. Request to server failed with HTTP status code {status_code:?}"),
-            });
+        // The measured circuits of all scale factors precede the estimate circuits of all scale factors
+        let mut measured_offset = 0;
+        let mut estimate_offset = 0;
+        let mut values: HashMap<String, Vec<f64>> = HashMap::new();
+        for &(number_measured, number_estimates) in layouts {
+            let scale_job = AqtJobHandle {
+                readouts: job.readouts[measured_offset..measured_offset + number_measured].to_vec(),
+                estimates: job.estimates[estimate_offset..estimate_offset + number_estimates]
+                    .to_vec(),
+                local_result: None,
+                ..job.clone()
+            };
+            let indices = (measured_offset..measured_offset + number_measured).chain(
+                (estimate_offset..estimate_offset + number_estimates)
+                    .map(|index| job.readouts.len() + index),
+            );
+            let scale_result: MeasuredResults = indices
+                .enumerate()
+                .filter_map(|(index, job_index)| {
+                    result
+                        .get(&(job_index as u32))
+                        .map(|measured| (index as u32, measured.clone()))
+                })
+                .collect();
+            let (bit_registers, float_registers, complex_registers) =
+                self.decode_job_result(&scale_job, &scale_result)?;
+            let evaluated = measurement
+                .evaluate(bit_registers, float_registers, complex_registers)?
+                .ok_or_else(|| RoqoqoBackendError::GenericError {
+                    msg: "Measurement returned no expectation values".to_string(),
+                })?;
+            for (name, value) in evaluated {
+                values.entry(name).or_default().push(value);
+            }
+            measured_offset += number_measured;
+            estimate_offset += number_estimates;
         }
-        let run_response: AqtRunResponse =
-            client_resp
-                .json::<AqtRunResponse>()
-                .map_err(|e| RoqoqoBackendError::NetworkError {
-                    msg: format!("second {e:?}"),
+        let extrapolated = values
+            .iter()
+            .map(|(name, name_values)| {
+                noise_amplification::extrapolate_to_zero(scale_factors, name_values, *order)
+                    .map(|value| (name.clone(), value))
+            })
+            .collect::<Result<HashMap<String, f64>, RoqoqoBackendError>>()?;
+        Ok(ZeroNoiseExtrapolation {
+            scale_factors: scale_factors.clone(),
+            values,
+            extrapolated,
+        })
+    }
+
+    /// Posts translated circuits to the AQT device and creates the handle of the job,
+    /// jobs submitted with `use_cache` false are neither answered from nor stored in the result cache
+    fn submit_aqt_run_data(
+        &self,
+        converted: (
+            AqtRunData,
+            RegisterDefinition,
+            Vec<String>,
+            Vec<AqtEstimate>,
+        ),
+        use_cache: bool,
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        let (aqt_run_data, all_registers, readouts, estimates) = converted;
+        let (bit_registers, float_registers, complex_registers) = all_registers;
+        let _span = aqt_span!(
+            INFO,
+            "submit",
+            resource_id = %self.device.id(),
+            label = %aqt_run_data.label,
+            number_circuits = aqt_run_data.payload.circuits.len()
+        );
+        let mut fingerprint: Option<String> = None;
+        let mut cache_key: Option<String> = None;
+        let mut metadata = JobMetadata {
+            resource_id: self.device.id(),
+            label: aqt_run_data.label.clone(),
+            submitted: Some(result_cache::now()),
+            ..JobMetadata::default()
+        };
+        let (job_id, local_result) = if self.device.is_local() {
+            let mut rng = match self.device.seed() {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+            let mut result: HashMap<u32, Vec<Vec<u32>>> = HashMap::new();
+            for (index, circuit) in aqt_run_data.payload.circuits.iter().enumerate() {
+                let measured = match self.device.noise_model() {
+                    Some(noise_model) => simulator::run_noisy_instructions(
+                        circuit.number_of_qubits as usize,
+                        &circuit.quantum_circuit,
+                        circuit.repetitions as usize,
+                        &noise_model,
+                        &mut rng,
+                    )?,
+                    None => simulator::run_instructions(
+                        circuit.number_of_qubits as usize,
+                        &circuit.quantum_circuit,
+                        circuit.repetitions as usize,
+                        &mut rng,
+                    )?,
+                };
+                result.insert(index as u32, measured);
+            }
+            metadata.finished = metadata.submitted;
+            aqt_event!(debug, "Simulated job on local device");
+            (format!("local_{:016x}", rng.gen::<u64>()), Some(result))
+        } else {
+            metadata.workspace_id = WORKSPACE_ID.to_string();
+            if self.result_cache.is_some() || self.job_journal.is_some() {
+                fingerprint = Some(ResultCache::fingerprint(
+                    &aqt_run_data,
+                    &self.device.remote_host(),
+                    &self.device.id(),
+                )?);
+            }
+            cache_key = match &self.result_cache {
+                Some(cache) if use_cache && cache.policy() != CachePolicy::Never => {
+                    fingerprint.clone()
+                }
+                _ => None,
+            };
+            let cached = match (&self.result_cache, &cache_key) {
+                (Some(cache), Some(key)) => cache.load(key)?,
+                _ => None,
+            };
+            match cached {
+                Some(entry) => {
+                    aqt_event!(info, job_id = %entry.job_id, "Answered job from result cache");
+                    metadata.submitted = None;
+                    metadata.finished = Some(entry.created);
+                    (entry.job_id, Some(self.pack_result(&entry.result)?))
+                }
+                None => {
+                    let client = self.client()?;
+                    // check device resource
+                    self.check_resource(&client)?;
+                    // Send POST request to AQT device
+                    let run_response = self.post_job(&client, aqt_run_data)?;
+                    aqt_event!(info, job_id = %run_response.job.job_id, "Submitted job to AQT");
+                    (run_response.job.job_id, None)
+                }
+            }
+        };
+        metadata.job_id = job_id.clone();
+        let job = AqtJobHandle {
+            job_id,
+            readouts,
+            bit_registers: bit_registers.into_keys().collect(),
+            float_registers: float_registers.into_keys().collect(),
+            complex_registers: complex_registers.into_keys().collect(),
+            estimates,
+            local_result,
+            cache_key,
+            metadata,
+        };
+        if let (Some(journal), None) = (&self.job_journal, &job.local_result) {
+            journal
+                .record_submitted(&job, &fingerprint.unwrap_or_default())
+                .map_err(|err| RoqoqoBackendError::GenericError {
+                    msg: format!("Job {} was submitted but not journaled: {err}", job.job_id),
                 })?;
+        }
+        Ok(job)
+    }
 
-        Ok(run_response)
+    /// Submits a [roqoqo::Circuit] to the AQT device without waiting for the result.
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - The circuit that is submitted
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::poll_job] or [Backend::wait_for_job]
+    pub fn submit_circuit(&self, circuit: &Circuit) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        self.submit_circuit_iterator(circuit.iter())
     }
-}
 
-impl<T: AqtApi> EvaluatingBackend for Backend<T> {
-    fn run_circuit_iterator<'a>(
+    /// Submits a [roqoqo::Circuit] to the AQT device with a name inserted into the job label.
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - The circuit that is submitted
+    /// `name` - The name replacing `{name}` in the job label, see [Backend::set_job_label]
+    ///
+    /// # Returns
+    ///
+    /// `AqtJobHandle` - Handle of the submitted job that can be passed to [Backend::poll_job] or [Backend::wait_for_job]
+    pub fn submit_circuit_with_name(
         &self,
-        circuit: impl Iterator<Item = &'a Operation>,
-    ) -> RegisterResult {
-        let client = reqwest::blocking::Client::builder()
-            .https_only(self.device.is_https())
-            .build()
-            .map_err(|x| RoqoqoBackendError::NetworkError {
-                msg: format!("could not create https client {x:?}"),
-            })?;
-        // check device resource
-        let aqt_resources_details = self.get_resource_details(&client)?;
-        if aqt_resources_details.status != "online" {
-            return Err(RoqoqoBackendError::NetworkError {
-                msg: "AQT resource is currently ofline".to_string(),
-            });
+        circuit: &Circuit,
+        name: &str,
+    ) -> Result<AqtJobHandle, RoqoqoBackendError> {
+        let converted = self.convert_circuit_to_aqt_circuit(circuit.iter())?;
+        self.submit_aqt_run_data(
+            self.aqt_run_data_from_converted(vec![converted], name)?,
+            true,
+        )
+    }
+
+    /// Queries the status of a submitted job once.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Some(RegisterDefinition))` - The output registers when the job has finished
+    /// `Ok(None)` - The job is still queued or ongoing
+    /// `Err(RoqoqoBackendError)` - The job failed or was cancelled
+    pub fn poll_job(
+        &self,
+        job: &AqtJobHandle,
+    ) -> Result<Option<RegisterDefinition>, RoqoqoBackendError> {
+        if let Some(result) = &job.local_result {
+            return self.decode_job_result(job, result).map(Some);
         }
-        if aqt_resources_details.available_qubits < self.device.number_qubits() as u32 {
-            return Err(RoqoqoBackendError::GenericError {
-                msg: format!(
-                    "Insuffient qubits on backend device. Maximum available qubits: {}.",
-                    aqt_resources_details.available_qubits
-                ),
-            });
+        let client = self.client()?;
+        self.query_job(&client, job)
+    }
+
+    /// Waits until a submitted job has finished and returns the output registers.
+    ///
+    /// The job is polled every [Backend::poll_interval], an error is returned after 100 unsuccessful polls.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    ///
+    /// # Returns
+    ///
+    /// `RegisterResult` - The output registers written by the evaluated circuit.
+    pub fn wait_for_job(&self, job: &AqtJobHandle) -> RegisterResult {
+        self.wait_for_job_interruptible(job, || Ok(()))
+    }
+
+    /// Waits until a submitted job has finished, checking regularly whether the wait should be aborted.
+    ///
+    /// The waiting time between two polls is split into short intervals. After each interval `interrupt` is called
+    /// and waiting is aborted with the returned error when it does not return `Ok(())`.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    /// `interrupt` - Function called regularly while waiting, returning an error aborts the wait
+    ///
+    /// # Returns
+    ///
+    /// `RegisterResult` - The output registers written by the evaluated circuit.
+    pub fn wait_for_job_interruptible<F>(&self, job: &AqtJobHandle, interrupt: F) -> RegisterResult
+    where
+        F: FnMut() -> Result<(), RoqoqoBackendError>,
+    {
+        let result = self.wait_for_job_result(job, interrupt)?;
+        self.decode_job_result(job, &result)
+    }
+
+    /// Waits until a submitted job has finished and returns the output registers together with the metadata of the job.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    ///
+    /// # Returns
+    ///
+    /// `AqtRunResult` - The output registers and the job id, resource, workspace, label and timestamps of the job
+    pub fn wait_for_job_with_metadata(
+        &self,
+        job: &AqtJobHandle,
+    ) -> Result<AqtRunResult, RoqoqoBackendError> {
+        self.wait_for_job_with_metadata_interruptible(job, || Ok(()))
+    }
+
+    /// Waits until a submitted job has finished and returns the output registers together with the metadata of the job,
+    /// checking regularly whether the wait should be aborted.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    /// `interrupt` - Function called regularly while waiting, returning an error aborts the wait
+    ///
+    /// # Returns
+    ///
+    /// `AqtRunResult` - The output registers and the job id, resource, workspace, label and timestamps of the job
+    pub fn wait_for_job_with_metadata_interruptible<F>(
+        &self,
+        job: &AqtJobHandle,
+        interrupt: F,
+    ) -> Result<AqtRunResult, RoqoqoBackendError>
+    where
+        F: FnMut() -> Result<(), RoqoqoBackendError>,
+    {
+        let (result, metadata) = self.wait_for_finished_job(job, interrupt)?;
+        Ok(AqtRunResult {
+            registers: self.decode_job_result(job, &result)?,
+            metadata,
+        })
+    }
+
+    /// Runs a circuit and returns the output registers together with the metadata of the job.
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - The circuit that is run
+    ///
+    /// # Returns
+    ///
+    /// `AqtRunResult` - The output registers and the job id, resource, workspace, label and timestamps of the job
+    pub fn run_circuit_with_metadata(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<AqtRunResult, RoqoqoBackendError> {
+        let job = self.submit_circuit(circuit)?;
+        self.wait_for_job_with_metadata(&job)
+    }
+
+    /// Waits until a submitted job has finished and returns the measured results of all circuits in the job
+    fn wait_for_job_result<F>(
+        &self,
+        job: &AqtJobHandle,
+        interrupt: F,
+    ) -> Result<MeasuredResults, RoqoqoBackendError>
+    where
+        F: FnMut() -> Result<(), RoqoqoBackendError>,
+    {
+        self.wait_for_finished_job(job, interrupt)
+            .map(|(result, _)| result)
+    }
+
+    /// Waits until a submitted job has finished and returns the measured results and the metadata of the job
+    fn wait_for_finished_job<F>(
+        &self,
+        job: &AqtJobHandle,
+        mut interrupt: F,
+    ) -> Result<(MeasuredResults, JobMetadata), RoqoqoBackendError>
+    where
+        F: FnMut() -> Result<(), RoqoqoBackendError>,
+    {
+        if let Some(result) = &job.local_result {
+            return Ok((result.clone(), job.metadata.clone()));
         }
-        // Convert circuit to aqt instructions
-        let (aqt_run_data, all_registers, readout) =
-            self.convert_circuit_to_aqt_instructions(circuit)?;
-        let (mut bit_registers, float_registers, complex_registers) = all_registers;
-        // Send POST request to AQT device
-        let run_response = self.post_job(&client, aqt_run_data)?;
-        let job_id: &String = run_response.job_id();
-        let mut loop_prevention = 0;
-        let mut finished: bool = false;
-        thread::sleep(time::Duration::from_secs(1));
-        while loop_prevention < 100 {
-            loop_prevention += 1;
-            // Send GET request to AQT evice
-            let run_response = self.get_result(&client, job_id)?;
-
-            if run_response.status() == "finished" {
-                let querry_response = run_response.response;
-                finished = true;
-                let measured_results =
-                    querry_response
-                        .result
-                        .get(&0)
-                        .ok_or(RoqoqoBackendError::GenericError {
+        let _span = aqt_span!(INFO, "wait_for_job", job_id = %job.job_id);
+        let client = self.client()?;
+        let mut sleep = |duration: time::Duration| -> Result<(), RoqoqoBackendError> {
+            let slice = time::Duration::from_millis(100);
+            let start = time::Instant::now();
+            while start.elapsed() < duration {
+                thread::sleep(slice.min(duration.saturating_sub(start.elapsed())));
+                interrupt()?;
+            }
+            Ok(())
+        };
+        // Replayed responses are available immediately
+        let poll_interval = match self.cassette_mode() {
+            Some(CassetteMode::Replay) => time::Duration::ZERO,
+            _ => self.poll_interval,
+        };
+        let number_queries = 100;
+        let start = time::Instant::now();
+        sleep(poll_interval.min(time::Duration::from_secs(1)))?;
+        for _ in 0..number_queries {
+            if let Some(finished) = self.query_job_result(&client, job)? {
+                return Ok(finished);
+            }
+            sleep(poll_interval)?;
+        }
+        aqt_event!(error, "Timed out waiting for job");
+        Err(RoqoqoBackendError::Timeout {
+            msg: format!(
+                "AQT backend timed out waiting for job {} after {:.1}s ({number_queries} queries every {}s)",
+                job.job_id,
+                start.elapsed().as_secs_f64(),
+                poll_interval.as_secs_f64()
+            ),
+        })
+    }
+
+    /// Waits until a submitted job has finished and returns the counts of the measured readout registers.
+    ///
+    /// The measured integers are kept instead of being expanded into bit registers.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    ///
+    /// # Returns
+    ///
+    /// `HashMap<String, AqtCounts>` - The measured samples of each readout register
+    pub fn wait_for_job_counts(
+        &self,
+        job: &AqtJobHandle,
+    ) -> Result<HashMap<String, AqtCounts>, RoqoqoBackendError> {
+        self.wait_for_job_counts_interruptible(job, || Ok(()))
+    }
+
+    /// Waits until a submitted job has finished and returns the counts, checking regularly whether the wait should be aborted.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    /// `interrupt` - Function called regularly while waiting, returning an error aborts the wait
+    ///
+    /// # Returns
+    ///
+    /// `HashMap<String, AqtCounts>` - The measured samples of each readout register
+    pub fn wait_for_job_counts_interruptible<F>(
+        &self,
+        job: &AqtJobHandle,
+        interrupt: F,
+    ) -> Result<HashMap<String, AqtCounts>, RoqoqoBackendError>
+    where
+        F: FnMut() -> Result<(), RoqoqoBackendError>,
+    {
+        let result = self.wait_for_job_result(job, interrupt)?;
+        let mut counts: HashMap<String, AqtCounts> = HashMap::new();
+        for (index, readout) in job.readouts.iter().enumerate() {
+            let measured =
+                result
+                    .get(&(index as u32))
+                    .ok_or_else(|| RoqoqoBackendError::GenericError {
                         msg:
                             "Failed to get measurement due to incorrect retrieval from AQT response"
                                 .to_string(),
                     })?;
-                for measured in measured_results.iter() {
-                    // loop over repetitions
-                    for curr in measured.iter() {
-                        let binary_representation: Vec<bool> = (0..self.device.number_qubits())
-                            .map(|x| {
-                                (*curr as usize)
-                                    .div_euclid(2_usize.pow(x as u32))
-                                    .rem_euclid(2)
-                                    == 1_usize
-                            })
-                            .collect();
-                        if let Some(reg) = bit_registers.get_mut(&readout) {
-                            reg.push(binary_representation)
-                        }
-                    }
-                }
-                break;
+            counts
+                .entry(readout.clone())
+                .or_insert_with(|| AqtCounts::new(self.device.number_qubits(), Vec::new()))
+                .samples
+                .extend(measured.iter().flatten());
+        }
+        Ok(counts)
+    }
+
+    /// Runs a circuit and returns the counts of the measured readout registers.
+    ///
+    /// # Arguments
+    ///
+    /// `circuit` - The circuit that is run
+    ///
+    /// # Returns
+    ///
+    /// `HashMap<String, AqtCounts>` - The measured samples of each readout register
+    pub fn run_circuit_counts(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<HashMap<String, AqtCounts>, RoqoqoBackendError> {
+        let job = self.submit_circuit(circuit)?;
+        self.wait_for_job_counts(&job)
+    }
+
+    /// Cancels a submitted job that has not finished yet.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    pub fn cancel_job(&self, job: &AqtJobHandle) -> Result<(), RoqoqoBackendError> {
+        if job.local_result.is_some() {
+            // Local jobs have already finished when they are submitted
+            return Ok(());
+        }
+        let client = self.client()?;
+        self.delete_job(&client, &job.job_id)?;
+        aqt_event!(info, job_id = %job.job_id, "Cancelled job");
+        self.record_job_status(job, JobStatus::Cancelled)
+    }
+
+    /// Sends a single get request for the job and writes the results to registers when the job has finished
+    fn query_job(
+        &self,
+        client: &blocking::Client,
+        job: &AqtJobHandle,
+    ) -> Result<Option<RegisterDefinition>, RoqoqoBackendError> {
+        self.query_job_result(client, job)?
+            .map(|(result, _)| self.decode_job_result(job, &result))
+            .transpose()
+    }
+
+    /// Sends a single get request for the job and returns the measured results and the metadata when the job has finished
+    fn query_job_result(
+        &self,
+        client: &blocking::Client,
+        job: &AqtJobHandle,
+    ) -> Result<Option<(MeasuredResults, JobMetadata)>, RoqoqoBackendError> {
+        // Send GET request to AQT device
+        let run_response = self.get_result(client, &job.job_id)?;
+        aqt_event!(debug, job_id = %job.job_id, status = %run_response.status(), "Polled job");
+
+        if run_response.status() == "error" {
+            aqt_event!(
+                error,
+                job_id = %job.job_id,
+                message = %instrumentation::redact(run_response.message(), &self.access_token),
+                "AQT reported an error for the job"
+            );
+            self.record_job_status(job, JobStatus::Failed)?;
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "AQT network backend reported error: {}",
+                    run_response.message()
+                ),
+            });
+        }
+        if run_response.status() == "cancelled" {
+            aqt_event!(warn, job_id = %job.job_id, "AQT reported that the job was cancelled");
+            self.record_job_status(job, JobStatus::Cancelled)?;
+            return Err(RoqoqoBackendError::NetworkError {
+                msg: "AQT network backend reported that the job was cancelled".to_string(),
+            });
+        }
+        if run_response.status() != "finished" {
+            return Ok(None);
+        }
+        let result = match self.pack_result(&run_response.response.result) {
+            Ok(result) => result,
+            Err(err) => {
+                aqt_event!(error, job_id = %job.job_id, error = %err, "Could not decode job result");
+                return Err(err);
             }
-            if run_response.status() == "error" {
-                return Err(RoqoqoBackendError::NetworkError {
-                    msg: format!(
-                        "AQT network backend reported error: {}",
-                        run_response.message()
-                    ),
-                });
+        };
+        if let (Some(cache), Some(key)) = (&self.result_cache, &job.cache_key) {
+            // The result of the finished job is returned even when it can not be cached
+            if let Err(_err) = cache.store(key, &job.job_id, &run_response.response.result) {
+                aqt_event!(
+                    warn,
+                    job_id = %job.job_id,
+                    error = %_err,
+                    "Could not store job result in result cache"
+                );
             }
-
-            if run_response.status() == "cancelled" {
-                return Err(RoqoqoBackendError::NetworkError {
-                    msg: "AQT network backend reported that the job was cancelled".to_string(),
-                });
+        }
+        // The job is only journaled as finished once its result has been decoded and stored,
+        // otherwise it can still be resumed
+        self.record_job_status(job, JobStatus::Finished)?;
+        // Prefer the values reported by the AQT servers over the values known when the job was submitted
+        let mut metadata = job.metadata.clone();
+        for (field, reported) in [
+            (&mut metadata.resource_id, &run_response.job.resource_id),
+            (&mut metadata.workspace_id, &run_response.job.workspace_id),
+            (&mut metadata.label, &run_response.job.label),
+        ] {
+            if !reported.is_empty() {
+                field.clone_from(reported);
             }
+        }
+        metadata.job_id.clone_from(&job.job_id);
+        metadata.finished = Some(result_cache::now());
+        aqt_event!(
+            info,
+            job_id = %job.job_id,
+            seconds_since_submission = metadata.finished.zip(metadata.submitted).map(|(finished, submitted)| finished.saturating_sub(submitted)),
+            "Job finished"
+        );
+        Ok(Some((result, metadata)))
+    }
 
-            thread::sleep(time::Duration::from_secs(20));
+    /// Records a change of the status of a job in the job journal, when a journal is set
+    fn record_job_status(
+        &self,
+        job: &AqtJobHandle,
+        status: JobStatus,
+    ) -> Result<(), RoqoqoBackendError> {
+        match &self.job_journal {
+            Some(journal) => journal.record_status(&job.job_id, status),
+            None => Ok(()),
         }
-        if !finished {
-            return Err(RoqoqoBackendError::Timeout {
-                msg: "AQT backend timed out after 20s".to_string(),
-            });
+    }
+
+    /// Converts the measured results returned by the AQT device with the configured sample layout and packed bit order
+    /// to packed integers with qubit 0 as the least significant bit
+    fn pack_result(
+        &self,
+        result: &HashMap<u32, Vec<Vec<u32>>>,
+    ) -> Result<MeasuredResults, RoqoqoBackendError> {
+        result
+            .iter()
+            .map(|(index, measured)| {
+                let samples = counts::pack_samples(
+                    measured,
+                    self.device.number_qubits(),
+                    self.sample_layout,
+                    self.packed_bit_order,
+                )?;
+                Ok((
+                    *index,
+                    samples.into_iter().map(|sample| vec![sample]).collect(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Writes the measured results of a finished job to the output registers
+    fn decode_job_result(
+        &self,
+        job: &AqtJobHandle,
+        result: &HashMap<u32, Vec<Vec<u32>>>,
+    ) -> Result<RegisterDefinition, RoqoqoBackendError> {
+        let _span = aqt_span!(
+            DEBUG,
+            "decode",
+            job_id = %job.job_id,
+            number_circuits = result.len()
+        );
+        let mut bit_registers: HashMap<String, BitOutputRegister> = job
+            .bit_registers
+            .iter()
+            .map(|name| (name.clone(), Vec::new()))
+            .collect();
+        let mut float_registers: HashMap<String, FloatOutputRegister> = job
+            .float_registers
+            .iter()
+            .map(|name| (name.clone(), Vec::new()))
+            .collect();
+        let complex_registers: HashMap<String, ComplexOutputRegister> = job
+            .complex_registers
+            .iter()
+            .map(|name| (name.clone(), Vec::new()))
+            .collect();
+
+        let measured_results = |index: usize| {
+            result
+                .get(&(index as u32))
+                .ok_or(RoqoqoBackendError::GenericError {
+                    msg: "Failed to get measurement due to incorrect retrieval from AQT response"
+                        .to_string(),
+                })
+        };
+        for (index, readout) in job.readouts.iter().enumerate() {
+            let shots = self.decode_shots(measured_results(index)?);
+            if let Some(reg) = bit_registers.get_mut(readout) {
+                reg.extend(shots)
+            }
+        }
+        // Qubits outside the measured results must not be read as zero, that would bias the estimates
+        let measured_qubit = |shot: &[bool], qubit: usize| {
+            shot.get(qubit)
+                .copied()
+                .ok_or_else(|| RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Qubit {qubit} of an emulated pragma is outside the {} measured qubits",
+                        shot.len()
+                    ),
+                })
+        };
+        for (index, estimate) in job.estimates.iter().enumerate() {
+            let shots = self.decode_shots(measured_results(job.readouts.len() + index)?);
+            let number_shots = shots.len() as f64;
+            let (readout, values): (&String, Vec<f64>) = match estimate {
+                AqtEstimate::PauliProduct { readout, qubits } => {
+                    let mut sum: f64 = 0.0;
+                    for shot in shots.iter() {
+                        let mut parity: usize = 0;
+                        for qubit in qubits.iter() {
+                            if measured_qubit(shot, *qubit)? {
+                                parity += 1;
+                            }
+                        }
+                        sum += if parity % 2 == 0 { 1.0 } else { -1.0 };
+                    }
+                    (readout, vec![sum / number_shots])
+                }
+                AqtEstimate::OccupationProbability { readout } => {
+                    let mut values: Vec<f64> = Vec::with_capacity(self.device.number_qubits());
+                    for qubit in 0..self.device.number_qubits() {
+                        let mut occupied: usize = 0;
+                        for shot in shots.iter() {
+                            if measured_qubit(shot, qubit)? {
+                                occupied += 1;
+                            }
+                        }
+                        values.push(occupied as f64 / number_shots);
+                    }
+                    (readout, values)
+                }
+            };
+            // Standard error of the mean of a two-valued random variable for the sampled shots
+            let errors: Vec<f64> = values
+                .iter()
+                .map(|value| match estimate {
+                    AqtEstimate::PauliProduct { .. } => {
+                        ((1.0 - value * value) / number_shots).sqrt()
+                    }
+                    AqtEstimate::OccupationProbability { .. } => {
+                        (value * (1.0 - value) / number_shots).sqrt()
+                    }
+                })
+                .collect();
+            if let Some(reg) = float_registers.get_mut(readout) {
+                reg.push(values);
+                float_registers
+                    .entry(format!("{readout}_error"))
+                    .or_default()
+                    .push(errors);
+            }
         }
         Ok((bit_registers, float_registers, complex_registers))
     }
+
+    /// Expands the normalised samples of one circuit, see [MeasuredResults], to the measured bits of each shot
+    fn decode_shots(&self, measured_results: &[Vec<u32>]) -> Vec<Vec<bool>> {
+        AqtCounts::new(
+            self.device.number_qubits(),
+            measured_results.iter().flatten().copied().collect(),
+        )
+        .to_bit_register()
+    }
+}
+
+impl<T: AqtApi> EvaluatingBackend for Backend<T> {
+    fn run_circuit_iterator<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> RegisterResult {
+        let job = self.submit_circuit_iterator(circuit)?;
+        self.wait_for_job(&job)
+    }
+
+    fn run_measurement_registers<M>(&self, measurement: &M) -> RegisterResult
+    where
+        M: Measure,
+    {
+        let job = self.submit_measurement(measurement)?;
+        self.wait_for_job(&job)
+    }
+}
+
+/// Returns the circuits of a measurement with the constant circuit prepended to every circuit
+fn measurement_circuits<M: Measure>(measurement: &M) -> Vec<Circuit> {
+    measurement
+        .circuits()
+        .map(|circuit| match measurement.constant_circuit() {
+            Some(constant_circuit) => constant_circuit.clone() + circuit.clone(),
+            None => circuit.clone(),
+        })
+        .collect()
+}
+
+/// Replaces the placeholders in the template of a job label
+///
+/// # Arguments
+///
+/// `template` - The template with placeholders in braces
+/// `values` - The names of the supported placeholders with their values
+fn render_job_label(template: &str, values: &[(&str, &str)]) -> Result<String, RoqoqoBackendError> {
+    let supported = || {
+        values
+            .iter()
+            .map(|(placeholder, _)| format!("{{{placeholder}}}"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let mut label = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        let end = match (rest[start..].starts_with('{'), rest[start..].find('}')) {
+            (true, Some(end)) => start + end,
+            _ => {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!("Unmatched brace in job label template {template:?}"),
+                })
+            }
+        };
+        let placeholder = &rest[start + 1..end];
+        let value = values
+            .iter()
+            .find(|(name, _)| *name == placeholder)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Unknown placeholder {{{placeholder}}} in job label template {template:?}, supported placeholders are {}",
+                    supported()
+                ),
+            })?;
+        label.push_str(&rest[..start]);
+        label.push_str(value);
+        rest = &rest[end + 1..];
+    }
+    label.push_str(rest);
+    Ok(label)
 }
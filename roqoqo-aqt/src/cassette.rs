@@ -0,0 +1,236 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Record and replay of HTTP interactions with the AQT REST API
+//!
+//! A Backend can record the requests it sends to the remote host and the responses of the server to a cassette file.
+//! Replaying the cassette later answers the requests of the Backend from the file without any network access,
+//! so tests can be based on real server responses while running offline and deterministically.
+
+use roqoqo::RoqoqoBackendError;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Placeholder replacing the access token in recorded interactions
+const SCRUBBED_TOKEN: &str = "<AQT_ACCESS_TOKEN>";
+
+/// A single request to the AQT REST API and the response of the server
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CassetteInteraction {
+    /// HTTP method of the request
+    pub method: String,
+    /// Path of the request relative to the remote host of the device
+    pub path: String,
+    /// JSON body of the request, None for requests without a body
+    pub request_body: Option<serde_json::Value>,
+    /// HTTP status code of the response
+    pub status: u16,
+    /// Body of the response
+    pub response_body: String,
+}
+
+/// Recorded interactions with the AQT REST API in order of the requests
+///
+/// Headers are not recorded, so the bearer token of the Backend never ends up in a cassette.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Cassette {
+    /// The recorded interactions
+    pub interactions: Vec<CassetteInteraction>,
+}
+
+impl Cassette {
+    /// Reads a cassette from a JSON file
+    ///
+    /// # Arguments
+    ///
+    /// `path` - The path of the cassette file
+    pub fn from_file(path: &Path) -> Result<Self, RoqoqoBackendError> {
+        let content = fs::read_to_string(path).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!("Could not read cassette {}: {err:?}", path.display()),
+        })?;
+        serde_json::from_str(&content).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!("Could not parse cassette {}: {err:?}", path.display()),
+        })
+    }
+
+    /// Writes the cassette to a JSON file
+    ///
+    /// # Arguments
+    ///
+    /// `path` - The path of the cassette file
+    pub fn to_file(&self, path: &Path) -> Result<(), RoqoqoBackendError> {
+        let content =
+            serde_json::to_string_pretty(self).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not serialize cassette {err:?}"),
+            })?;
+        fs::write(path, content).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!("Could not write cassette {}: {err:?}", path.display()),
+        })
+    }
+}
+
+/// Whether a Backend records or replays its interactions with the AQT REST API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Requests are sent to the remote host and recorded
+    Record,
+    /// Requests are answered from the cassette without network access
+    Replay,
+}
+
+/// Cassette used by a Backend, shared between clones of the Backend
+#[derive(Debug, Clone)]
+pub(crate) struct CassettePlayer {
+    /// Whether interactions are recorded or replayed
+    mode: CassetteMode,
+    /// The path of the cassette file
+    path: PathBuf,
+    /// The recorded interactions and which of them have already been replayed
+    state: Arc<Mutex<(Cassette, Vec<bool>)>>,
+}
+
+impl PartialEq for CassettePlayer {
+    /// Compares the mode and file of the cassettes, the progress of recording or replaying is not compared
+    fn eq(&self, other: &Self) -> bool {
+        self.mode == other.mode && self.path == other.path
+    }
+}
+
+impl Eq for CassettePlayer {}
+
+impl CassettePlayer {
+    /// Starts recording to a new cassette, the file is written after every interaction
+    pub(crate) fn record(path: &Path) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            path: path.to_path_buf(),
+            state: Arc::new(Mutex::new((Cassette::default(), Vec::new()))),
+        }
+    }
+
+    /// Starts replaying a recorded cassette
+    pub(crate) fn replay(path: &Path) -> Result<Self, RoqoqoBackendError> {
+        let cassette = Cassette::from_file(path)?;
+        let replayed = vec![false; cassette.interactions.len()];
+        Ok(Self {
+            mode: CassetteMode::Replay,
+            path: path.to_path_buf(),
+            state: Arc::new(Mutex::new((cassette, replayed))),
+        })
+    }
+
+    /// Returns whether interactions are recorded or replayed
+    pub(crate) fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Adds an interaction to the cassette and writes the cassette file
+    ///
+    /// Every occurrence of the access token is replaced by a placeholder before the interaction is recorded.
+    pub(crate) fn record_interaction(
+        &self,
+        mut interaction: CassetteInteraction,
+        access_token: &str,
+    ) -> Result<(), RoqoqoBackendError> {
+        if !access_token.is_empty() {
+            interaction.path = interaction.path.replace(access_token, SCRUBBED_TOKEN);
+            interaction.response_body = interaction
+                .response_body
+                .replace(access_token, SCRUBBED_TOKEN);
+            interaction.request_body = scrub_request_body(interaction.request_body, access_token);
+        }
+        let mut state = self.lock();
+        state.0.interactions.push(interaction);
+        state.1.push(true);
+        state.0.to_file(&self.path)
+    }
+
+    /// Returns the status and body of the first response recorded for a request that has not been replayed yet
+    ///
+    /// Requests are matched by method and path, polling the same job therefore replays the recorded
+    /// responses in their original order. The body of the request must be equal to the recorded body
+    /// after the access token has been scrubbed.
+    ///
+    /// # Arguments
+    ///
+    /// `method` - The HTTP method of the request
+    /// `path` - The path of the request relative to the remote host
+    /// `request_body` - The JSON body of the request, None for requests without a body
+    /// `access_token` - The access token of the Backend that is scrubbed before comparing
+    ///
+    /// # Returns
+    ///
+    /// `RoqoqoBackendError::NetworkError` - No recorded response is left for the request
+    /// `RoqoqoBackendError::GenericError` - The body of the request differs from the recorded body
+    pub(crate) fn replay_interaction(
+        &self,
+        method: &str,
+        path: &str,
+        request_body: Option<serde_json::Value>,
+        access_token: &str,
+    ) -> Result<(u16, String), RoqoqoBackendError> {
+        let (path, request_body) = if access_token.is_empty() {
+            (path.to_string(), request_body)
+        } else {
+            (
+                path.replace(access_token, SCRUBBED_TOKEN),
+                scrub_request_body(request_body, access_token),
+            )
+        };
+        let mut state = self.lock();
+        let (cassette, replayed) = &mut *state;
+        let index = cassette
+            .interactions
+            .iter()
+            .zip(replayed.iter())
+            .position(|(interaction, replayed)| {
+                !replayed && interaction.method == method && interaction.path == path
+            })
+            .ok_or_else(|| RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "No recorded response left for {method} {path} in cassette {}",
+                    self.path.display()
+                ),
+            })?;
+        let interaction = &cassette.interactions[index];
+        if interaction.request_body != request_body {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Body of request {method} {path} does not match the recorded request in cassette {}",
+                    self.path.display()
+                ),
+            });
+        }
+        replayed[index] = true;
+        Ok((interaction.status, interaction.response_body.clone()))
+    }
+
+    /// Locks the shared state, a panic in another thread does not invalidate the state
+    fn lock(&self) -> MutexGuard<'_, (Cassette, Vec<bool>)> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Replaces every occurrence of the access token in the JSON body of a request by a placeholder
+fn scrub_request_body(
+    request_body: Option<serde_json::Value>,
+    access_token: &str,
+) -> Option<serde_json::Value> {
+    request_body.and_then(|body| {
+        let scrubbed = body.to_string().replace(access_token, SCRUBBED_TOKEN);
+        serde_json::from_str(&scrubbed).ok()
+    })
+}
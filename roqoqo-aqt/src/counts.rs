@@ -0,0 +1,243 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Counts of measured bitstrings
+//!
+//! AQT devices return every shot either as a packed integer or as a list with the measured bit of each qubit.
+//! The backend converts both layouts to packed integers with qubit 0 as the least significant bit.
+//! Instead of expanding every shot into a bit register, the samples can be kept as integers
+//! and summarised as counts of bitstrings or integers.
+
+use roqoqo::registers::BitOutputRegister;
+use roqoqo::RoqoqoBackendError;
+use std::collections::HashMap;
+
+/// Order of the qubits in the bitstrings and integer keys of counts
+///
+/// Only changes how counts are presented, the packed samples returned by the device are decoded with [PackedBitOrder].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum BitOrdering {
+    /// Qubit 0 is the first character of a bitstring and the most significant bit of an integer,
+    /// like the order of the qubits in bit registers
+    #[default]
+    QubitZeroFirst,
+    /// Qubit 0 is the last character of a bitstring and the least significant bit of an integer,
+    /// like the integers returned by AQT devices
+    QubitZeroLast,
+}
+
+/// Position of qubit 0 in the packed integer samples returned by AQT devices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum PackedBitOrder {
+    /// Qubit 0 is the least significant bit, like the integers returned by AQT devices
+    #[default]
+    QubitZeroLeastSignificant,
+    /// Qubit 0 is the most significant bit
+    QubitZeroMostSignificant,
+}
+
+/// Layout of the shots returned by AQT devices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum SampleLayout {
+    /// Shots with a single entry are packed integers, longer shots are lists of bits per qubit
+    #[default]
+    Auto,
+    /// Every shot is a single integer with the bits of all qubits
+    Packed,
+    /// Every shot is a list with the measured bit (0 or 1) of each qubit, starting with qubit 0
+    PerQubit,
+}
+
+/// Converts the shots of one circuit returned by an AQT device to packed integers with qubit 0 as the least significant bit.
+///
+/// # Arguments
+///
+/// `measured` - The shots of the circuit as returned by the device
+/// `number_qubits` - The number of qubits of the circuit
+/// `layout` - The layout of the shots
+/// `bit_order` - The position of qubit 0 in packed integers, lists of bits always start with qubit 0
+///
+/// # Returns
+///
+/// `Vec<u32>` - The packed integer of every shot, qubit 0 is the least significant bit
+/// `RoqoqoBackendError::GenericError` - A shot does not match the layout or the number of qubits
+pub fn pack_samples(
+    measured: &[Vec<u32>],
+    number_qubits: usize,
+    layout: SampleLayout,
+    bit_order: PackedBitOrder,
+) -> Result<Vec<u32>, RoqoqoBackendError> {
+    if number_qubits > 32 {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Samples of {number_qubits} qubits can not be packed into 32 bit integers"
+            ),
+        });
+    }
+    measured
+        .iter()
+        .map(|shot| {
+            let packed = match (layout, shot.as_slice()) {
+                (_, []) => {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: "AQT device returned an empty shot".to_string(),
+                    })
+                }
+                (SampleLayout::Auto, [value]) | (SampleLayout::Packed, [value]) => *value,
+                (SampleLayout::Packed, _) => {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: format!("Expected a packed integer per shot, got {shot:?}"),
+                    })
+                }
+                (SampleLayout::Auto, _) | (SampleLayout::PerQubit, _) => {
+                    return pack_bits(shot, number_qubits)
+                }
+            };
+            if number_qubits < 32 && packed >> number_qubits != 0 {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!("Sample {packed} is out of range for {number_qubits} qubits"),
+                });
+            }
+            Ok(match bit_order {
+                PackedBitOrder::QubitZeroLeastSignificant => packed,
+                PackedBitOrder::QubitZeroMostSignificant => (0..number_qubits)
+                    .filter(|bit| (packed >> bit) & 1 == 1)
+                    .map(|bit| 1 << (number_qubits - 1 - bit))
+                    .sum(),
+            })
+        })
+        .collect()
+}
+
+/// Packs a list of bits per qubit, starting with qubit 0, into an integer
+fn pack_bits(shot: &[u32], number_qubits: usize) -> Result<u32, RoqoqoBackendError> {
+    if shot.len() > number_qubits {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Shot with {} bits returned for {number_qubits} qubits",
+                shot.len()
+            ),
+        });
+    }
+    shot.iter()
+        .enumerate()
+        .try_fold(0, |packed, (qubit, bit)| match bit {
+            0 => Ok(packed),
+            1 => Ok(packed | (1 << qubit)),
+            _ => Err(RoqoqoBackendError::GenericError {
+                msg: format!("Expected a bit per qubit, got {shot:?}"),
+            }),
+        })
+}
+
+/// Measured samples of a readout register in the integer format returned by AQT devices
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AqtCounts {
+    /// Number of qubits measured in every shot
+    pub number_qubits: usize,
+    /// The measured integer of every shot, qubit 0 is the least significant bit
+    pub samples: Vec<u32>,
+}
+
+impl AqtCounts {
+    /// Creates counts from the measured integer samples.
+    ///
+    /// # Arguments
+    ///
+    /// `number_qubits` - The number of qubits measured in every shot
+    /// `samples` - The measured integer of every shot, qubit 0 is the least significant bit
+    pub fn new(number_qubits: usize, samples: Vec<u32>) -> Self {
+        Self {
+            number_qubits,
+            samples,
+        }
+    }
+
+    /// Returns the number of measured shots.
+    pub fn number_shots(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns the bitstring of a sample.
+    ///
+    /// # Arguments
+    ///
+    /// `sample` - The measured integer, qubit 0 is the least significant bit
+    /// `ordering` - The order of the qubits in the bitstring
+    pub fn bitstring(&self, sample: u32, ordering: BitOrdering) -> String {
+        let bit = |qubit: usize| {
+            if (sample as u64 >> qubit) & 1 == 1 {
+                '1'
+            } else {
+                '0'
+            }
+        };
+        match ordering {
+            BitOrdering::QubitZeroFirst => (0..self.number_qubits).map(bit).collect(),
+            BitOrdering::QubitZeroLast => (0..self.number_qubits).rev().map(bit).collect(),
+        }
+    }
+
+    /// Returns how often each bitstring has been measured.
+    ///
+    /// # Arguments
+    ///
+    /// `ordering` - The order of the qubits in the bitstrings
+    pub fn counts(&self, ordering: BitOrdering) -> HashMap<String, usize> {
+        self.sample_counts()
+            .into_iter()
+            .map(|(sample, count)| (self.bitstring(sample, ordering), count))
+            .collect()
+    }
+
+    /// Returns how often each integer has been measured.
+    ///
+    /// # Arguments
+    ///
+    /// `ordering` - The order of the qubits in the binary representation of the integers
+    pub fn integer_counts(&self, ordering: BitOrdering) -> HashMap<u64, usize> {
+        self.sample_counts()
+            .into_iter()
+            .map(|(sample, count)| {
+                let key = match ordering {
+                    BitOrdering::QubitZeroLast => sample as u64,
+                    BitOrdering::QubitZeroFirst => (0..self.number_qubits)
+                        .filter(|qubit| (sample as u64 >> qubit) & 1 == 1)
+                        .map(|qubit| 1 << (self.number_qubits - 1 - qubit))
+                        .sum(),
+                };
+                (key, count)
+            })
+            .collect()
+    }
+
+    /// Expands the samples into a bit register with one entry per qubit and shot.
+    pub fn to_bit_register(&self) -> BitOutputRegister {
+        self.samples
+            .iter()
+            .map(|sample| {
+                (0..self.number_qubits)
+                    .map(|qubit| (*sample as u64 >> qubit) & 1 == 1)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Counts the samples by their integer value
+    fn sample_counts(&self) -> HashMap<u32, usize> {
+        let mut counts: HashMap<u32, usize> = HashMap::new();
+        for sample in self.samples.iter() {
+            *counts.entry(*sample).or_default() += 1;
+        }
+        counts
+    }
+}
@@ -14,6 +14,8 @@
 //!
 //! Provides the device used to execute quantum programs with the AQT backend.
 
+use roqoqo::RoqoqoBackendError;
+
 /// AQT device
 ///
 /// Consists of information about the device such as the id, the number of qubits, and the endpoint that receives instructions that
@@ -22,19 +24,54 @@
 pub struct AqtDevice {
     /// Number of qubits supported by the device
     pub number_qubits: usize,
+    /// REST API endpoint replacing the AQT cloud, for example a mock server in tests
+    #[serde(default)]
+    pub remote_host: Option<String>,
 }
 
 impl AqtDevice {
     /// Create a new AQT device for the backend
     pub fn new(number_qubits: usize) -> Self {
-        Self { number_qubits }
+        Self {
+            number_qubits,
+            remote_host: None,
+        }
+    }
+
+    /// Replaces the REST API endpoint of the AQT cloud with a different endpoint
+    ///
+    /// # Arguments
+    ///
+    /// `remote_host` - The URL of the endpoint ending with a slash, None uses the AQT cloud
+    pub fn set_remote_host(mut self, remote_host: Option<String>) -> Self {
+        self.remote_host = remote_host;
+        self
+    }
+}
+
+/// Layout of the [AqtDevice] serialized by version 0.8.0
+///
+/// Formats that are not self-describing like bincode can not skip the `remote_host` added later,
+/// data serialized with 0.8.0 is deserialized to this layout and converted to an [AqtDevice].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LegacyAqtDevice {
+    /// Number of qubits supported by the device
+    pub number_qubits: usize,
+}
+
+impl From<LegacyAqtDevice> for AqtDevice {
+    fn from(device: LegacyAqtDevice) -> Self {
+        Self::new(device.number_qubits)
     }
 }
 
 impl AqtApi for AqtDevice {
     /// Returns REST API endpoint to make calls to the AQT device
     fn remote_host(&self) -> String {
-        "https://arnica.aqt.eu/api/v1/".to_string()
+        match &self.remote_host {
+            Some(remote_host) => remote_host.clone(),
+            None => "https://arnica.aqt.eu/api/v1/".to_string(),
+        }
     }
     /// Return number of qubits available
     fn number_qubits(&self) -> usize {
@@ -42,7 +79,7 @@ impl AqtApi for AqtDevice {
     }
     /// Returns whether the internal client sends request to an https server
     fn is_https(&self) -> bool {
-        true
+        self.remote_host().starts_with("https://")
     }
     /// Returns the id of the device
     fn id(&self) -> String {
@@ -50,6 +87,340 @@ impl AqtApi for AqtDevice {
     }
 }
 
+/// Local AQT simulator device
+///
+/// Simulates the translated AQT instructions with a statevector simulator instead of sending them to the AQT cloud.
+/// Results are returned in the same format as results of AQT devices, so the Backend can be used without an access token,
+/// for example for testing and debugging. At most [MAX_SIMULATED_QUBITS] qubits can be simulated.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SimulatorDevice {
+    /// Number of qubits supported by the device
+    pub number_qubits: usize,
+    /// Seed of the random number generator used to sample measurement results,
+    /// results are not reproducible when None
+    pub seed: Option<u64>,
+}
+
+impl SimulatorDevice {
+    /// Create a new local simulator device for the backend
+    pub fn new(number_qubits: usize, seed: Option<u64>) -> Self {
+        Self {
+            number_qubits,
+            seed,
+        }
+    }
+}
+
+impl AqtApi for SimulatorDevice {
+    /// Returns REST API endpoint to make calls to the AQT device, empty as no calls are made
+    fn remote_host(&self) -> String {
+        "".to_string()
+    }
+    /// Return number of qubits available
+    fn number_qubits(&self) -> usize {
+        self.number_qubits
+    }
+    /// Returns whether the internal client sends request to an https server
+    fn is_https(&self) -> bool {
+        false
+    }
+    /// Returns the id of the device
+    fn id(&self) -> String {
+        "local_simulator".to_string()
+    }
+    /// Returns whether circuits are simulated locally
+    fn is_local(&self) -> bool {
+        true
+    }
+    /// Returns the seed of the random number generator used for local simulations
+    fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+/// Noise model of trapped-ion hardware used by the [EmulatorDevice]
+///
+/// All errors are applied stochastically in every simulated trajectory, one trajectory is simulated per measurement.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "AqtNoiseModelSerialize")]
+pub struct AqtNoiseModel {
+    /// Probability of a random Pauli error after each R gate, RZ gates are virtual and noiseless
+    single_qubit_depolarising: f64,
+    /// Probability of a random two-qubit Pauli error after each RXX (Molmer-Sorensen) gate
+    ms_gate_infidelity: f64,
+    /// Probability of a Z error on every qubit that is idle during a gate
+    dephasing: f64,
+    /// Probability that a qubit is prepared in the one state instead of the zero state
+    preparation_error: f64,
+    /// Probability that the measured value of a qubit is flipped
+    measurement_error: f64,
+}
+
+// The constructor rejects probabilities that are NaN
+impl Eq for AqtNoiseModel {}
+
+/// Unvalidated noise model read by serde
+#[derive(serde::Deserialize)]
+struct AqtNoiseModelSerialize {
+    single_qubit_depolarising: f64,
+    ms_gate_infidelity: f64,
+    dephasing: f64,
+    preparation_error: f64,
+    measurement_error: f64,
+}
+
+impl TryFrom<AqtNoiseModelSerialize> for AqtNoiseModel {
+    type Error = RoqoqoBackendError;
+
+    fn try_from(value: AqtNoiseModelSerialize) -> Result<Self, Self::Error> {
+        Self::new(
+            value.single_qubit_depolarising,
+            value.ms_gate_infidelity,
+            value.dephasing,
+            value.preparation_error,
+            value.measurement_error,
+        )
+    }
+}
+
+impl AqtNoiseModel {
+    /// Create a new trapped-ion noise model
+    ///
+    /// # Arguments
+    ///
+    /// `single_qubit_depolarising` - Probability of a random Pauli error after each R gate
+    /// `ms_gate_infidelity` - Probability of a random two-qubit Pauli error after each RXX gate
+    /// `dephasing` - Probability of a Z error on every qubit that is idle during a gate
+    /// `preparation_error` - Probability that a qubit is prepared in the one state
+    /// `measurement_error` - Probability that the measured value of a qubit is flipped
+    ///
+    /// # Returns
+    ///
+    /// `AqtNoiseModel` - The noise model
+    /// `RoqoqoBackendError::GenericError` - A probability is not in the range [0, 1]
+    pub fn new(
+        single_qubit_depolarising: f64,
+        ms_gate_infidelity: f64,
+        dephasing: f64,
+        preparation_error: f64,
+        measurement_error: f64,
+    ) -> Result<Self, RoqoqoBackendError> {
+        for (name, probability) in [
+            ("single_qubit_depolarising", single_qubit_depolarising),
+            ("ms_gate_infidelity", ms_gate_infidelity),
+            ("dephasing", dephasing),
+            ("preparation_error", preparation_error),
+            ("measurement_error", measurement_error),
+        ] {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Noise model probability {name} must be in the range [0, 1], got {probability}"
+                    ),
+                });
+            }
+        }
+        Ok(Self {
+            single_qubit_depolarising,
+            ms_gate_infidelity,
+            dephasing,
+            preparation_error,
+            measurement_error,
+        })
+    }
+
+    /// Returns the probability of a random Pauli error after each R gate
+    pub fn single_qubit_depolarising(&self) -> f64 {
+        self.single_qubit_depolarising
+    }
+    /// Returns the probability of a random two-qubit Pauli error after each RXX gate
+    pub fn ms_gate_infidelity(&self) -> f64 {
+        self.ms_gate_infidelity
+    }
+    /// Returns the probability of a Z error on every qubit that is idle during a gate
+    pub fn dephasing(&self) -> f64 {
+        self.dephasing
+    }
+    /// Returns the probability that a qubit is prepared in the one state
+    pub fn preparation_error(&self) -> f64 {
+        self.preparation_error
+    }
+    /// Returns the probability that the measured value of a qubit is flipped
+    pub fn measurement_error(&self) -> f64 {
+        self.measurement_error
+    }
+}
+
+impl Default for AqtNoiseModel {
+    /// Creates a noise model without any errors
+    fn default() -> Self {
+        Self {
+            single_qubit_depolarising: 0.0,
+            ms_gate_infidelity: 0.0,
+            dephasing: 0.0,
+            preparation_error: 0.0,
+            measurement_error: 0.0,
+        }
+    }
+}
+
+/// Local emulator of noisy AQT trapped-ion hardware
+///
+/// Simulates the translated AQT instructions locally, applying the errors of an [AqtNoiseModel],
+/// so results of AQT hardware can be predicted without the AQT cloud or an access token.
+/// At most [MAX_SIMULATED_QUBITS] qubits can be emulated.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EmulatorDevice {
+    /// Number of qubits supported by the device
+    pub number_qubits: usize,
+    /// Noise model applied during the emulation
+    pub noise_model: AqtNoiseModel,
+    /// Seed of the random number generator used to sample errors and measurement results,
+    /// results are not reproducible when None
+    pub seed: Option<u64>,
+}
+
+impl EmulatorDevice {
+    /// Create a new local emulator device for the backend
+    pub fn new(number_qubits: usize, noise_model: AqtNoiseModel, seed: Option<u64>) -> Self {
+        Self {
+            number_qubits,
+            noise_model,
+            seed,
+        }
+    }
+
+    /// Create a local emulator of an AQT device using the number of qubits of the device
+    ///
+    /// The number of qubits is the only property of the [AqtDevice] that is used,
+    /// the noise model is not derived from the device and the remote host is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// `device` - The AQT device whose number of qubits is emulated
+    /// `noise_model` - Noise model applied during the emulation
+    /// `seed` - Seed of the random number generator, results are not reproducible when None
+    pub fn from_device(device: &AqtDevice, noise_model: AqtNoiseModel, seed: Option<u64>) -> Self {
+        Self::new(device.number_qubits, noise_model, seed)
+    }
+}
+
+impl AqtApi for EmulatorDevice {
+    /// Returns REST API endpoint to make calls to the AQT device, empty as no calls are made
+    fn remote_host(&self) -> String {
+        "".to_string()
+    }
+    /// Return number of qubits available
+    fn number_qubits(&self) -> usize {
+        self.number_qubits
+    }
+    /// Returns whether the internal client sends request to an https server
+    fn is_https(&self) -> bool {
+        false
+    }
+    /// Returns the id of the device
+    fn id(&self) -> String {
+        "local_simulator_noise".to_string()
+    }
+    /// Returns whether circuits are simulated locally
+    fn is_local(&self) -> bool {
+        true
+    }
+    /// Returns the seed of the random number generator used for local simulations
+    fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+    /// Returns the noise model applied in local simulations
+    fn noise_model(&self) -> Option<AqtNoiseModel> {
+        Some(self.noise_model.clone())
+    }
+}
+
+/// Device of a backend that is selected at runtime
+///
+/// Wraps the AQT cloud device and the local devices, so a single backend type can run on all of them,
+/// for example the python Backend.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AnyAqtDevice {
+    /// Device of the AQT cloud or a replacing REST API endpoint
+    Aqt(AqtDevice),
+    /// Local statevector simulator
+    Simulator(SimulatorDevice),
+    /// Local emulator of noisy trapped-ion hardware
+    Emulator(EmulatorDevice),
+}
+
+impl From<AqtDevice> for AnyAqtDevice {
+    fn from(device: AqtDevice) -> Self {
+        Self::Aqt(device)
+    }
+}
+
+impl From<SimulatorDevice> for AnyAqtDevice {
+    fn from(device: SimulatorDevice) -> Self {
+        Self::Simulator(device)
+    }
+}
+
+impl From<EmulatorDevice> for AnyAqtDevice {
+    fn from(device: EmulatorDevice) -> Self {
+        Self::Emulator(device)
+    }
+}
+
+impl AnyAqtDevice {
+    /// Returns the wrapped device as a trait object
+    fn device(&self) -> &dyn AqtApi {
+        match self {
+            Self::Aqt(device) => device,
+            Self::Simulator(device) => device,
+            Self::Emulator(device) => device,
+        }
+    }
+}
+
+impl AqtApi for AnyAqtDevice {
+    /// Returns REST API endpoint to make calls to the AQT device
+    fn remote_host(&self) -> String {
+        self.device().remote_host()
+    }
+    /// Return number of qubits available
+    fn number_qubits(&self) -> usize {
+        self.device().number_qubits()
+    }
+    /// Returns whether the internal client sends request to an https server
+    fn is_https(&self) -> bool {
+        self.device().is_https()
+    }
+    /// Returns the id of the device
+    fn id(&self) -> String {
+        self.device().id()
+    }
+    /// Returns whether circuits are simulated locally
+    fn is_local(&self) -> bool {
+        self.device().is_local()
+    }
+    /// Returns the seed of the random number generator used for local simulations
+    fn seed(&self) -> Option<u64> {
+        self.device().seed()
+    }
+    /// Returns the noise model applied in local simulations
+    fn noise_model(&self) -> Option<AqtNoiseModel> {
+        self.device().noise_model()
+    }
+    /// Returns the maximum number of instructions in a single circuit
+    fn max_number_instructions(&self) -> usize {
+        self.device().max_number_instructions()
+    }
+}
+
+/// Maximum number of qubits simulated by the local [SimulatorDevice] and [EmulatorDevice],
+/// the statevector of 24 qubits takes 256 MiB of memory
+pub const MAX_SIMULATED_QUBITS: usize = 24;
+
+/// Default maximum number of instructions in a single AQT circuit
+pub const MAX_NUMBER_INSTRUCTIONS: usize = 2000;
+
 /// Defines the AQT backend on which to run quantum simulations
 pub trait AqtApi {
     /// Returns REST API endpoint to make calls to the AQT device
@@ -60,4 +431,20 @@ pub trait AqtApi {
     fn is_https(&self) -> bool;
     /// Returns the id of the device
     fn id(&self) -> String;
+    /// Returns whether circuits are simulated locally instead of being sent to the remote host
+    fn is_local(&self) -> bool {
+        false
+    }
+    /// Returns the seed of the random number generator used for local simulations
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+    /// Returns the noise model applied in local simulations, None for noiseless simulations
+    fn noise_model(&self) -> Option<AqtNoiseModel> {
+        None
+    }
+    /// Returns the maximum number of instructions in a single circuit, including unrolled loops
+    fn max_number_instructions(&self) -> usize {
+        MAX_NUMBER_INSTRUCTIONS
+    }
 }
@@ -0,0 +1,155 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configuration of the HTTP client communicating with the AQT REST API
+//!
+//! The Backend creates its HTTP client from the configuration when the first request is sent
+//! and reuses the client for all following requests, so connections are kept alive between jobs.
+
+use reqwest::blocking;
+use reqwest::header::HeaderValue;
+use roqoqo::RoqoqoBackendError;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// User agent sent when no user agent is configured
+const DEFAULT_USER_AGENT: &str = concat!("roqoqo-aqt/", env!("CARGO_PKG_VERSION"));
+
+/// Settings of the HTTP client communicating with the AQT REST API
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct HttpClientConfig {
+    /// Timeout of every request from connecting until the response body has been read,
+    /// None keeps the default of 30 seconds
+    #[serde(default)]
+    pub timeout: Option<Duration>,
+    /// Timeout of connecting to the remote host, None waits as long as the request timeout allows
+    #[serde(default)]
+    pub connect_timeout: Option<Duration>,
+    /// URL of the proxy all requests are sent through, e.g. `http://proxy.example.com:8080`,
+    /// None uses the proxies set in the environment variables `HTTP_PROXY` and `HTTPS_PROXY`
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// PEM files with additional root certificates trusted besides the certificates of the system
+    #[serde(default)]
+    pub root_certificates: Vec<PathBuf>,
+    /// User agent identifying the requests, None sends `roqoqo-aqt/<version>`
+    #[serde(default)]
+    pub user_agent: Option<String>,
+}
+
+impl HttpClientConfig {
+    /// Checks the proxy URL, the root certificates and the user agent without creating a client.
+    pub fn validate(&self) -> Result<(), RoqoqoBackendError> {
+        self.client_builder(true).map(|_| ())
+    }
+
+    /// Creates a blocking HTTP client with the configured settings.
+    ///
+    /// # Arguments
+    ///
+    /// `https_only` - Whether requests are only sent with HTTPS
+    ///
+    /// # Returns
+    ///
+    /// `blocking::Client` - The HTTP client
+    /// `RoqoqoBackendError::NetworkError` - A setting is invalid or the client could not be created
+    pub fn build_client(&self, https_only: bool) -> Result<blocking::Client, RoqoqoBackendError> {
+        self.client_builder(https_only)?
+            .build()
+            .map_err(|x| RoqoqoBackendError::NetworkError {
+                msg: format!("could not create https client {x:?}"),
+            })
+    }
+
+    /// Applies the settings to a client builder
+    fn client_builder(
+        &self,
+        https_only: bool,
+    ) -> Result<blocking::ClientBuilder, RoqoqoBackendError> {
+        let user_agent = self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+        let user_agent =
+            HeaderValue::from_str(user_agent).map_err(|err| RoqoqoBackendError::NetworkError {
+                msg: format!("Invalid user agent {user_agent:?}: {err:?}"),
+            })?;
+        let mut builder = blocking::Client::builder()
+            .https_only(https_only)
+            .user_agent(user_agent);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            let proxy =
+                reqwest::Proxy::all(proxy).map_err(|err| RoqoqoBackendError::NetworkError {
+                    msg: format!("Invalid proxy {proxy:?}: {err:?}"),
+                })?;
+            builder = builder.proxy(proxy);
+        }
+        for path in self.root_certificates.iter() {
+            let pem = fs::read(path).map_err(|err| RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Could not read root certificate {}: {err:?}",
+                    path.display()
+                ),
+            })?;
+            let certificate = reqwest::Certificate::from_pem(&pem).map_err(|err| {
+                RoqoqoBackendError::NetworkError {
+                    msg: format!("Invalid root certificate {}: {err:?}", path.display()),
+                }
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        Ok(builder)
+    }
+}
+
+/// HTTP client of a Backend, created when the first request is sent
+///
+/// Clones of a Backend start without a client and create their own client when they send a request.
+#[derive(Debug, Default)]
+pub(crate) struct ClientCache(OnceLock<blocking::Client>);
+
+impl ClientCache {
+    /// Returns the cached client, creating it with the configuration when no client exists yet
+    pub(crate) fn get_or_build(
+        &self,
+        config: &HttpClientConfig,
+        https_only: bool,
+    ) -> Result<blocking::Client, RoqoqoBackendError> {
+        if let Some(client) = self.0.get() {
+            return Ok(client.clone());
+        }
+        let client = config.build_client(https_only)?;
+        // Another thread may have created a client in the meantime, its client is kept
+        Ok(self.0.get_or_init(|| client).clone())
+    }
+}
+
+impl Clone for ClientCache {
+    /// Clones start without a client
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for ClientCache {
+    /// The cached client is not part of the state of a Backend
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ClientCache {}
@@ -0,0 +1,71 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracing instrumentation of the backend
+//!
+//! With the `tracing` feature the backend emits [tracing](https://docs.rs/tracing) spans and events
+//! for resource lookups, submissions, polls, decoding and errors. Without the feature the macros
+//! expand to nothing, so the instrumentation has no cost.
+
+/// Emits a tracing event at the given level, e.g. `aqt_event!(debug, job_id = %id, "Polled job")`
+#[cfg(feature = "tracing")]
+macro_rules! aqt_event {
+    ($level:ident, $($arg:tt)+) => {
+        tracing::$level!($($arg)+)
+    };
+}
+
+/// Emits a tracing event at the given level, disabled without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+macro_rules! aqt_event {
+    ($level:ident, $($arg:tt)+) => {};
+}
+
+/// Enters a tracing span at the given level until the returned guard is dropped,
+/// e.g. `let _span = aqt_span!(DEBUG, "submit", resource_id = %id);`
+#[cfg(feature = "tracing")]
+macro_rules! aqt_span {
+    ($level:ident, $($arg:tt)+) => {
+        tracing::span!(tracing::Level::$level, $($arg)+).entered()
+    };
+}
+
+/// Enters a tracing span at the given level, disabled without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+macro_rules! aqt_span {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::instrumentation::DisabledSpan
+    };
+}
+
+/// Guard returned by `aqt_span!` without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct DisabledSpan;
+
+/// Placeholder replacing the access token in traced messages
+#[cfg(feature = "tracing")]
+const REDACTED_TOKEN: &str = "<redacted>";
+
+/// Replaces every occurrence of the access token in a traced message
+///
+/// # Arguments
+///
+/// `message` - The message that is traced
+/// `access_token` - The access token of the Backend
+#[cfg(feature = "tracing")]
+pub(crate) fn redact(message: &str, access_token: &str) -> String {
+    if access_token.is_empty() {
+        message.to_string()
+    } else {
+        message.replace(access_token, REDACTED_TOKEN)
+    }
+}
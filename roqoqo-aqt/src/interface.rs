@@ -10,6 +10,7 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::devices::MAX_NUMBER_INSTRUCTIONS;
 use roqoqo::operations::*;
 use roqoqo::Circuit;
 use roqoqo::RoqoqoBackendError;
@@ -28,7 +29,6 @@ const ALLOWED_OPERATIONS: &[&str; 12] = &[
     "PragmaRepeatedMeasurement",
     "PragmaStartDecompositionBlock",
     "PragmaStopDecompositionBlock",
-    // "PragmaLoop",                  // CHECK
     // "PhaseShiftedControlledPhase", // CHECK
 ];
 
@@ -76,13 +76,195 @@ pub enum AqtInstruction {
 pub fn call_circuit(circuit: &Circuit) -> Result<Vec<AqtInstruction>, RoqoqoBackendError> {
     let mut circuit_vec: Vec<AqtInstruction> = Vec::new();
     for op in circuit.iter() {
-        if let Some(instruction) = call_operation(op)? {
-            circuit_vec.push(instruction);
-        }
+        circuit_vec.extend(call_operation_sequence(op)?);
     }
     Ok(circuit_vec)
 }
 
+/// Converts a [roqoqo::operations::Operation] into a sequence of instructions for AQT Hardware or AQT Simulators.
+///
+/// Multi-qubit operations that have no native AQT instruction are decomposed:
+/// [roqoqo::operations::MultiQubitMS] is implemented with pairwise RXX instructions and single-qubit rotations,
+/// [roqoqo::operations::MultiQubitZZ] additionally changes the basis of all qubits.
+/// [roqoqo::operations::VariableMSXX] is implemented with RXX instructions whose angles lie in the range
+/// accepted by AQT devices, using the angle convention of [call_operation].
+/// [roqoqo::operations::PragmaLoop] is unrolled with [call_loop] and at most [MAX_NUMBER_INSTRUCTIONS] instructions.
+/// All other operations are converted with [call_operation].
+///
+/// # Arguments
+///
+/// `operation` - The [roqoqo::operations::Operation] that is converted
+///
+/// # Returns
+///
+/// `Vec<AqtInstruction>` - Converted instructions, empty for operations that are ignored
+/// `RoqoqoBackendError::OperationNotInBackend` - Error when [roqoqo::operations::Operation] can not be converted
+pub fn call_operation_sequence(
+    operation: &Operation,
+) -> Result<Vec<AqtInstruction>, RoqoqoBackendError> {
+    match operation {
+        Operation::MultiQubitMS(op) => Ok(multi_qubit_ms_instructions(
+            op.qubits(),
+            *op.theta().float()?,
+        )),
+        Operation::MultiQubitZZ(op) => {
+            // exp(-i theta/2 Z...Z) = RY(-pi/2) exp(-i theta/2 X...X) RY(pi/2) on all qubits
+            let mut instructions: Vec<AqtInstruction> = op
+                .qubits()
+                .iter()
+                .map(|qubit| r_instruction(0.5, 0.5, *qubit))
+                .collect();
+            instructions.extend(multi_qubit_ms_instructions(
+                op.qubits(),
+                *op.theta().float()?,
+            ));
+            instructions.extend(
+                op.qubits()
+                    .iter()
+                    .map(|qubit| r_instruction(-0.5, 0.5, *qubit)),
+            );
+            Ok(instructions)
+        }
+        // Variable MSXX is different in qoqo and aqt
+        Operation::VariableMSXX(op) => Ok(crate::optimisation::rxx_within_range(
+            &[*op.control() as u32, *op.target() as u32],
+            *op.theta().float()? / 2.0,
+        )),
+        Operation::PragmaLoop(op) => call_loop(op, MAX_NUMBER_INSTRUCTIONS),
+        _ => Ok(call_operation(operation)?.into_iter().collect()),
+    }
+}
+
+/// Unrolls a [roqoqo::operations::PragmaLoop] into instructions for AQT Hardware or AQT Simulators
+///
+/// Nested loops are unrolled recursively. Symbolic repetitions have to be substituted before the loop is unrolled.
+///
+/// # Arguments
+///
+/// `operation` - The [roqoqo::operations::PragmaLoop] that is unrolled
+/// `max_number_instructions` - The maximum number of instructions the unrolled loop may contain
+///
+/// # Returns
+///
+/// `Vec<AqtInstruction>` - The instructions of the loop body repeated for all repetitions
+/// `RoqoqoBackendError::GenericError` - Error when the repetitions are not a non-negative integer or the unrolled loop exceeds the maximum number of instructions
+pub fn call_loop(
+    operation: &PragmaLoop,
+    max_number_instructions: usize,
+) -> Result<Vec<AqtInstruction>, RoqoqoBackendError> {
+    let repetitions = *operation.repetitions().float()?;
+    if !repetitions.is_finite() || repetitions < 0.0 || repetitions.fract() != 0.0 {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "PragmaLoop repetitions must be a non-negative integer, got {repetitions}"
+            ),
+        });
+    }
+    if repetitions == 0.0 {
+        return Ok(Vec::new());
+    }
+    let mut body: Vec<AqtInstruction> = Vec::new();
+    for op in operation.circuit().iter() {
+        let remaining = max_number_instructions.saturating_sub(body.len());
+        match op {
+            Operation::PragmaLoop(inner) => body.extend(call_loop(inner, remaining)?),
+            _ => body.extend(call_operation_sequence(op)?),
+        }
+    }
+    if body.is_empty() {
+        return Ok(body);
+    }
+    // Compared as float so that huge repetition counts can not overflow
+    if body.len() as f64 * repetitions > max_number_instructions as f64 {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Unrolling PragmaLoop with {repetitions} repetitions of {} instructions exceeds the maximum of {max_number_instructions} instructions",
+                body.len()
+            ),
+        });
+    }
+    let mut instructions: Vec<AqtInstruction> = Vec::new();
+    for _ in 0..repetitions as usize {
+        instructions.extend(body.iter().cloned());
+    }
+    Ok(instructions)
+}
+
+/// Decomposes exp(-i theta/2 X...X) on several qubits into AQT instructions
+///
+/// With U = prod_k exp(-i pi/4 X_0 X_k), conjugating Y_0 with U gives +-P_0 X_1 ... X_n with P either Y or Z,
+/// so the gate is implemented as V U RY_0(+-theta) U^dagger V^dagger, where the single-qubit rotation V maps P to X.
+/// U^dagger = Z_0 U Z_0 is implemented with RXX instructions of angle 0.5 within the AQT range.
+fn multi_qubit_ms_instructions(qubits: &[usize], theta: f64) -> Vec<AqtInstruction> {
+    let theta = theta / std::f64::consts::PI;
+    match qubits {
+        [] => Vec::new(),
+        [qubit] => vec![r_instruction(theta, 0.0, *qubit)],
+        [first, second] => {
+            crate::optimisation::rxx_within_range(&[*first as u32, *second as u32], theta)
+        }
+        [first, others @ ..] => {
+            // Conjugating with each exp(-i pi/4 X_0 X_k) maps Y -> Z -> -Y -> -Z -> Y on the first qubit
+            let (sign, maps_z) = match others.len() % 4 {
+                0 => (1.0, false),
+                1 => (1.0, true),
+                2 => (-1.0, false),
+                _ => (-1.0, true),
+            };
+            let entangling: Vec<AqtInstruction> = others
+                .iter()
+                .map(|other| AqtInstruction::RXX {
+                    qubits: vec![*first as u32, *other as u32],
+                    theta: 0.5,
+                })
+                .collect();
+            let z_first = AqtInstruction::RZ {
+                phi: 1.0,
+                qubit: *first as u32,
+            };
+            let mut instructions: Vec<AqtInstruction> = Vec::new();
+            // V^dagger, with V = RY(pi/2) mapping Z to X or V = RZ(-pi/2) mapping Y to X
+            if maps_z {
+                instructions.push(r_instruction(-0.5, 0.5, *first));
+            } else {
+                instructions.push(AqtInstruction::RZ {
+                    phi: 0.5,
+                    qubit: *first as u32,
+                });
+            }
+            instructions.push(z_first.clone());
+            instructions.extend(entangling.iter().cloned());
+            instructions.push(z_first);
+            instructions.push(r_instruction(sign * theta, 0.5, *first));
+            instructions.extend(entangling);
+            if maps_z {
+                instructions.push(r_instruction(0.5, 0.5, *first));
+            } else {
+                instructions.push(AqtInstruction::RZ {
+                    phi: -0.5,
+                    qubit: *first as u32,
+                });
+            }
+            instructions
+        }
+    }
+}
+
+/// Creates an R instruction with theta in [0, 1] and phi in [0, 2), equivalent up to a global phase
+pub(crate) fn r_instruction(theta: f64, phi: f64, qubit: usize) -> AqtInstruction {
+    let theta = theta.rem_euclid(2.0);
+    let (theta, phi) = if theta > 1.0 {
+        (2.0 - theta, phi + 1.0)
+    } else {
+        (theta, phi)
+    };
+    AqtInstruction::R {
+        phi: phi.rem_euclid(2.0),
+        theta,
+        qubit: qubit as u32,
+    }
+}
+
 /// Converts a [roqoqo::operations::Operation] into an instruction for AQT Hardware or AQT Simulators.
 /// *Note* - Any measurment operation, regardless of the specific qubits defined, will always measure all the qubits.
 ///
@@ -110,6 +292,11 @@ pub fn call_operation(operation: &Operation) -> Result<Option<AqtInstruction>, R
             theta: *op.theta().float()? / std::f64::consts::PI,
             qubit: *op.qubit() as u32,
         })),
+        Operation::RotateXY(op) => Ok(Some(AqtInstruction::R {
+            phi: *op.phi().float()? / std::f64::consts::PI,
+            theta: *op.theta().float()? / std::f64::consts::PI,
+            qubit: *op.qubit() as u32,
+        })),
         Operation::PauliZ(op) => Ok(Some(AqtInstruction::RZ {
             phi: 1.0,
             qubit: *op.qubit() as u32,
@@ -148,3 +335,124 @@ pub fn call_operation(operation: &Operation) -> Result<Option<AqtInstruction>, R
         }
     }
 }
+
+/// Converts AQT instructions back into a [roqoqo::Circuit]
+///
+/// RZ instructions are converted to [RotateZ], R instructions to [RotateXY] and RXX instructions to [VariableMSXX].
+/// The angle of VariableMSXX is chosen consistently with [call_operation], so converting the circuit again
+/// reproduces the instructions. The circuit defines the bit register `readout` and every MEASURE instruction
+/// is converted to a [PragmaRepeatedMeasurement] writing to this register.
+///
+/// # Arguments
+///
+/// `instructions` - The AQT instructions that are converted
+/// `number_qubits` - The number of qubits of the readout register, None uses the highest qubit of the instructions
+/// `repetitions` - The number of times the circuit is measured
+/// `readout` - The name of the bit register the measurement results are written to
+///
+/// # Returns
+///
+/// `Circuit` - The reconstructed circuit
+/// `RoqoqoBackendError::GenericError` - Error when an instruction acts on a qubit outside the register or an RXX instruction does not act on two qubits
+pub fn call_instructions(
+    instructions: &[AqtInstruction],
+    number_qubits: Option<usize>,
+    repetitions: usize,
+    readout: &str,
+) -> Result<Circuit, RoqoqoBackendError> {
+    let highest_qubit = instructions
+        .iter()
+        .flat_map(|instruction| match instruction {
+            AqtInstruction::RZ { qubit, .. } | AqtInstruction::R { qubit, .. } => vec![*qubit],
+            AqtInstruction::RXX { qubits, .. } => qubits.clone(),
+            AqtInstruction::MEASURE => Vec::new(),
+        })
+        .max();
+    let number_qubits = match (number_qubits, highest_qubit) {
+        (Some(number_qubits), Some(qubit)) if qubit as usize >= number_qubits => {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Instruction acts on qubit {qubit} but the circuit only has {number_qubits} qubits"
+                ),
+            })
+        }
+        (Some(number_qubits), _) => number_qubits,
+        (None, highest_qubit) => highest_qubit.map(|qubit| qubit as usize + 1).unwrap_or(0),
+    };
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new(readout.to_string(), number_qubits, true);
+    for instruction in instructions {
+        circuit.add_operation(call_instruction(instruction, repetitions, readout)?);
+    }
+    Ok(circuit)
+}
+
+/// Converts an AQT instruction back into a [roqoqo::operations::Operation]
+///
+/// # Arguments
+///
+/// `instruction` - The AQT instruction that is converted
+/// `repetitions` - The number of times the circuit is measured, used for MEASURE instructions
+/// `readout` - The name of the bit register the measurement results are written to, used for MEASURE instructions
+///
+/// # Returns
+///
+/// `Operation` - Converted operation
+/// `RoqoqoBackendError::GenericError` - Error when an RXX instruction does not act on two different qubits
+pub fn call_instruction(
+    instruction: &AqtInstruction,
+    repetitions: usize,
+    readout: &str,
+) -> Result<Operation, RoqoqoBackendError> {
+    match instruction {
+        AqtInstruction::RZ { phi, qubit } => {
+            Ok(RotateZ::new(*qubit as usize, (phi * std::f64::consts::PI).into()).into())
+        }
+        AqtInstruction::R { phi, theta, qubit } => Ok(RotateXY::new(
+            *qubit as usize,
+            (theta * std::f64::consts::PI).into(),
+            (phi * std::f64::consts::PI).into(),
+        )
+        .into()),
+        // Variable MSXX is different in qoqo and aqt
+        AqtInstruction::RXX { qubits, theta } => match qubits.as_slice() {
+            [control, target] if control != target => {
+                Ok(
+                    VariableMSXX::new(*control as usize, *target as usize, (theta * 2.0).into())
+                        .into(),
+                )
+            }
+            _ => Err(RoqoqoBackendError::GenericError {
+                msg: format!("RXX instruction requires two different qubits, found {qubits:?}"),
+            }),
+        },
+        AqtInstruction::MEASURE => {
+            Ok(PragmaRepeatedMeasurement::new(readout.to_string(), repetitions, None).into())
+        }
+    }
+}
+
+/// Converts the JSON representation of AQT instructions created by [crate::Backend::to_aqt_json] back into a [roqoqo::Circuit]
+///
+/// # Arguments
+///
+/// `json` - The JSON list of AQT instructions
+/// `number_qubits` - The number of qubits of the readout register, None uses the highest qubit of the instructions
+/// `repetitions` - The number of times the circuit is measured
+/// `readout` - The name of the bit register the measurement results are written to
+///
+/// # Returns
+///
+/// `Circuit` - The reconstructed circuit
+pub fn aqt_json_to_circuit(
+    json: &str,
+    number_qubits: Option<usize>,
+    repetitions: usize,
+    readout: &str,
+) -> Result<Circuit, RoqoqoBackendError> {
+    let instructions: Vec<AqtInstruction> =
+        serde_json::from_str(json).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!("Could not parse AQT instructions {err:?}"),
+        })?;
+    call_instructions(&instructions, number_qubits, repetitions, readout)
+}
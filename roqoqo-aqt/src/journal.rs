@@ -0,0 +1,236 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Journal of submitted jobs
+//!
+//! Every job sent to the AQT servers is appended to a journal file together with its register layout,
+//! followed by a record for every change of its status. When the process dies while waiting for a job,
+//! the unfinished jobs can be read from the journal after a restart and their results fetched into registers.
+
+use crate::result_cache;
+use crate::AqtJobHandle;
+use roqoqo::RoqoqoBackendError;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Status of a journaled job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum JobStatus {
+    /// The job has been submitted and no final status has been received yet
+    Submitted,
+    /// The job has finished and its results have been fetched
+    Finished,
+    /// The AQT servers reported an error for the job
+    Failed,
+    /// The job has been cancelled
+    Cancelled,
+}
+
+/// Journaled state of a submitted job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// Handle of the job with the register layout of the submitted circuits
+    pub job: AqtJobHandle,
+    /// Fingerprint of the circuits of the job, the remote host and the resource id, see [crate::result_cache::ResultCache::fingerprint]
+    pub fingerprint: String,
+    /// Time the job was submitted in seconds since the Unix epoch
+    pub submitted: u64,
+    /// Time of the last change of the status in seconds since the Unix epoch
+    pub updated: u64,
+    /// The last status of the job
+    pub status: JobStatus,
+}
+
+/// A line of the journal file
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum JournalRecord {
+    /// A job has been submitted
+    Submitted {
+        job: Box<AqtJobHandle>,
+        fingerprint: String,
+        time: u64,
+    },
+    /// The status of a job has changed
+    Status {
+        job_id: String,
+        status: JobStatus,
+        time: u64,
+    },
+}
+
+/// Journal of submitted jobs in a file with one JSON record per line
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct JobJournal {
+    /// The path of the journal file
+    path: PathBuf,
+}
+
+impl JobJournal {
+    /// Creates a journal of submitted jobs, records are appended to existing journal files.
+    ///
+    /// # Arguments
+    ///
+    /// `path` - The path of the journal file
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Returns the path of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records a submitted job.
+    ///
+    /// # Arguments
+    ///
+    /// `job` - Handle of the submitted job
+    /// `fingerprint` - Fingerprint of the circuits of the job, the remote host and the resource id
+    pub fn record_submitted(
+        &self,
+        job: &AqtJobHandle,
+        fingerprint: &str,
+    ) -> Result<(), RoqoqoBackendError> {
+        self.append(&JournalRecord::Submitted {
+            job: Box::new(job.clone()),
+            fingerprint: fingerprint.to_string(),
+            time: result_cache::now(),
+        })
+    }
+
+    /// Records a change of the status of a job.
+    ///
+    /// # Arguments
+    ///
+    /// `job_id` - The id of the job
+    /// `status` - The new status of the job
+    pub fn record_status(&self, job_id: &str, status: JobStatus) -> Result<(), RoqoqoBackendError> {
+        self.append(&JournalRecord::Status {
+            job_id: job_id.to_string(),
+            status,
+            time: result_cache::now(),
+        })
+    }
+
+    /// Reads the state of all journaled jobs in order of submission.
+    ///
+    /// Records that were cut off because the process died while writing them are skipped.
+    ///
+    /// # Returns
+    ///
+    /// `Vec<JournalEntry>` - The state of every job, empty when the journal file does not exist
+    pub fn entries(&self) -> Result<Vec<JournalEntry>, RoqoqoBackendError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content =
+            fs::read_to_string(&self.path).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Could not read job journal {}: {err:?}",
+                    self.path.display()
+                ),
+            })?;
+        let mut entries: Vec<JournalEntry> = Vec::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let record: JournalRecord = match serde_json::from_str(line) {
+                Ok(record) => record,
+                // A record cut off by a crash while it was written is skipped
+                Err(err) if err.is_eof() => continue,
+                Err(err) => {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: format!(
+                            "Could not parse job journal {}: {err:?}",
+                            self.path.display()
+                        ),
+                    })
+                }
+            };
+            match record {
+                JournalRecord::Submitted {
+                    job,
+                    fingerprint,
+                    time,
+                } => entries.push(JournalEntry {
+                    job: *job,
+                    fingerprint,
+                    submitted: time,
+                    updated: time,
+                    status: JobStatus::Submitted,
+                }),
+                JournalRecord::Status {
+                    job_id,
+                    status,
+                    time,
+                } => {
+                    if let Some(entry) = entries
+                        .iter_mut()
+                        .rev()
+                        .find(|entry| entry.job.job_id() == job_id)
+                    {
+                        entry.status = status;
+                        entry.updated = time;
+                    }
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Reads the journaled jobs that have not finished, failed or been cancelled.
+    ///
+    /// # Returns
+    ///
+    /// `Vec<JournalEntry>` - The state of every unfinished job in order of submission
+    pub fn unfinished(&self) -> Result<Vec<JournalEntry>, RoqoqoBackendError> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.status == JobStatus::Submitted)
+            .collect())
+    }
+
+    /// Appends a record to the journal file
+    fn append(&self, record: &JournalRecord) -> Result<(), RoqoqoBackendError> {
+        let mut line =
+            serde_json::to_string(record).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not serialize job journal record {err:?}"),
+            })?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| {
+                // Start a new line when the last record was cut off while it was written
+                if file.metadata()?.len() > 0 {
+                    let mut last = [0u8];
+                    file.seek(SeekFrom::End(-1))?;
+                    file.read_exact(&mut last)?;
+                    if last[0] != b'\n' {
+                        line.insert(0, '\n');
+                    }
+                }
+                file.write_all(line.as_bytes())
+            })
+            .map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Could not write job journal {}: {err:?}",
+                    self.path.display()
+                ),
+            })
+    }
+}
@@ -23,9 +23,29 @@
 //!
 //! roqoqo-aqt provides backends to send roqoqo quantum circuits to AQT machines
 
+#[macro_use]
+mod instrumentation;
 mod interface;
-pub use interface::{call_circuit, call_operation, AqtInstruction};
+pub use interface::{
+    aqt_json_to_circuit, call_circuit, call_instruction, call_instructions, call_loop,
+    call_operation, call_operation_sequence, AqtInstruction,
+};
 mod backend;
-pub use backend::Backend;
+pub use backend::{
+    AqtJobHandle, AqtRunData, AqtRunResult, Backend, JobMetadata, LegacyBackend,
+    ZeroNoiseExtrapolationJob,
+};
+pub mod cassette;
+pub mod counts;
 pub mod devices;
 pub use devices::AqtApi;
+pub mod http_client;
+pub mod journal;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
+pub mod noise_amplification;
+pub mod optimisation;
+pub mod readout_mitigation;
+pub mod report;
+pub mod result_cache;
+mod simulator;
@@ -0,0 +1,427 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mock AQT REST server
+//!
+//! Provides a local HTTP server implementing the parts of the AQT API used by the [crate::Backend],
+//! so that downstream projects can test the full submission and polling path without the AQT cloud.
+//! Submitted jobs pass through the states queued, ongoing and finished, error or cancelled,
+//! and their results are produced by the local simulator.
+
+use crate::backend::AqtRunData;
+use crate::devices::{AqtDevice, AqtNoiseModel};
+use crate::simulator;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use roqoqo::RoqoqoBackendError;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Configuration of a [MockAqtServer]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockServerConfig {
+    /// Id of the resource served by the mock server, [AqtDevice] uses `simulator_noise`
+    pub resource_id: String,
+    /// Number of qubits reported as available by the resource
+    pub available_qubits: usize,
+    /// Whether the resource is reported as online
+    pub online: bool,
+    /// Number of result queries of a job answered with the status queued
+    pub queued_polls: usize,
+    /// Number of result queries of a job answered with the status ongoing after it has been queued
+    pub ongoing_polls: usize,
+    /// Delay before every response of the server
+    pub latency: Duration,
+    /// Noise model used when simulating submitted circuits, None for noiseless simulations
+    pub noise_model: Option<AqtNoiseModel>,
+    /// Seed of the random number generator used when simulating submitted circuits
+    pub seed: Option<u64>,
+}
+
+impl Default for MockServerConfig {
+    /// Creates the configuration of an online noiseless resource with 20 qubits
+    fn default() -> Self {
+        Self {
+            resource_id: "simulator_noise".to_string(),
+            available_qubits: 20,
+            online: true,
+            queued_polls: 1,
+            ongoing_polls: 1,
+            latency: Duration::ZERO,
+            noise_model: None,
+            seed: None,
+        }
+    }
+}
+
+/// Job stored by the mock server
+#[derive(Debug)]
+struct MockJob {
+    /// Id of the resource the job was submitted to
+    resource_id: String,
+    /// Label of the submitted job
+    label: String,
+    /// Simulated results of the circuits of the job
+    result: HashMap<u32, Vec<Vec<u32>>>,
+    /// Number of circuits of the job
+    number_circuits: usize,
+    /// Number of result queries answered so far
+    number_polls: usize,
+    /// Whether the job has been cancelled
+    cancelled: bool,
+    /// Error message reported instead of the result
+    error: Option<String>,
+}
+
+impl MockJob {
+    /// Returns the status of the job at the current number of result queries
+    fn status(&self, config: &MockServerConfig) -> &'static str {
+        if self.cancelled {
+            "cancelled"
+        } else if self.number_polls < config.queued_polls {
+            "queued"
+        } else if self.number_polls < config.queued_polls + config.ongoing_polls {
+            "ongoing"
+        } else if self.error.is_some() {
+            "error"
+        } else {
+            "finished"
+        }
+    }
+}
+
+/// Shared state of the mock server
+#[derive(Debug)]
+struct MockState {
+    /// Configuration of the server
+    config: MockServerConfig,
+    /// Jobs submitted to the server, in order of submission
+    jobs: Vec<(String, MockJob)>,
+    /// HTTP status codes returned by the next submissions instead of accepting the jobs
+    failing_submissions: Vec<u16>,
+    /// Error messages of the next accepted jobs
+    failing_jobs: Vec<String>,
+    /// Random number generator used when simulating submitted circuits
+    rng: StdRng,
+}
+
+impl MockState {
+    /// Returns the job with the given id
+    fn job_mut(&mut self, job_id: &str) -> Option<&mut MockJob> {
+        self.jobs
+            .iter_mut()
+            .find(|(id, _)| id == job_id)
+            .map(|(_, job)| job)
+    }
+
+    /// Answers a request to the mock server
+    fn respond(&mut self, request: &Request) -> ResponseTemplate {
+        let path: Vec<&str> = request
+            .url
+            .path()
+            .trim_start_matches('/')
+            .split('/')
+            .collect();
+        if !request.headers.contains_key("authorization") {
+            return ResponseTemplate::new(401).set_body_json(json!({"detail": "Unauthorized"}));
+        }
+        match (request.method.as_str(), path.as_slice()) {
+            ("GET", ["resources", resource_id]) => self.resource(resource_id),
+            ("POST", ["submit", _, resource_id]) => self.submit(resource_id, &request.body),
+            ("GET", ["result", job_id]) => self.result(job_id),
+            ("DELETE", ["jobs", job_id]) => self.cancel(job_id),
+            _ => ResponseTemplate::new(404).set_body_json(json!({"detail": "Not Found"})),
+        }
+    }
+
+    /// Returns the details of the resource
+    fn resource(&self, resource_id: &str) -> ResponseTemplate {
+        if resource_id != self.config.resource_id {
+            return ResponseTemplate::new(404).set_body_json(json!({"detail": "Not Found"}));
+        }
+        ResponseTemplate::new(200).set_body_json(json!({
+            "id": self.config.resource_id,
+            "name": "Mock Simulator",
+            "type": "simulator",
+            "status": if self.config.online { "online" } else { "offline" },
+            "available_qubits": self.config.available_qubits,
+        }))
+    }
+
+    /// Simulates the circuits of a submitted job and stores the job
+    fn submit(&mut self, resource_id: &str, body: &[u8]) -> ResponseTemplate {
+        if resource_id != self.config.resource_id {
+            return ResponseTemplate::new(404).set_body_json(json!({"detail": "Not Found"}));
+        }
+        if !self.failing_submissions.is_empty() {
+            let status_code = self.failing_submissions.remove(0);
+            return ResponseTemplate::new(status_code)
+                .set_body_json(json!({"detail": "Injected failure"}));
+        }
+        let run_data: AqtRunData = match serde_json::from_slice(body) {
+            Ok(run_data) => run_data,
+            Err(err) => {
+                return ResponseTemplate::new(422)
+                    .set_body_json(json!({"detail": format!("Invalid job: {err}")}))
+            }
+        };
+        let mut result: HashMap<u32, Vec<Vec<u32>>> = HashMap::new();
+        for (index, circuit) in run_data.payload.circuits.iter().enumerate() {
+            let measured = match &self.config.noise_model {
+                Some(noise_model) => simulator::run_noisy_instructions(
+                    circuit.number_of_qubits as usize,
+                    &circuit.quantum_circuit,
+                    circuit.repetitions as usize,
+                    noise_model,
+                    &mut self.rng,
+                ),
+                None => simulator::run_instructions(
+                    circuit.number_of_qubits as usize,
+                    &circuit.quantum_circuit,
+                    circuit.repetitions as usize,
+                    &mut self.rng,
+                ),
+            };
+            match measured {
+                Ok(measured) => {
+                    result.insert(index as u32, measured);
+                }
+                Err(err) => {
+                    return ResponseTemplate::new(422)
+                        .set_body_json(json!({"detail": format!("Invalid job: {err:?}")}))
+                }
+            }
+        }
+        let error = if self.failing_jobs.is_empty() {
+            None
+        } else {
+            Some(self.failing_jobs.remove(0))
+        };
+        let job_id = format!("mock_job_{}", self.jobs.len());
+        let job = MockJob {
+            resource_id: resource_id.to_string(),
+            label: run_data.label,
+            result,
+            number_circuits: run_data.payload.circuits.len(),
+            number_polls: 0,
+            cancelled: false,
+            error,
+        };
+        let response = ResponseTemplate::new(200).set_body_json(json!({
+            "job": job_description(&job_id, &job),
+            "response": {"status": "queued"},
+        }));
+        self.jobs.push((job_id, job));
+        response
+    }
+
+    /// Returns the status of a job, advancing its state
+    fn result(&mut self, job_id: &str) -> ResponseTemplate {
+        let config = self.config.clone();
+        let job = match self.job_mut(job_id) {
+            Some(job) => job,
+            None => {
+                return ResponseTemplate::new(404).set_body_json(json!({"detail": "Not Found"}))
+            }
+        };
+        let status = job.status(&config);
+        let response = match status {
+            "ongoing" => json!({"status": status, "finished_count": 0}),
+            "finished" => json!({"status": status, "result": job.result}),
+            "error" => json!({"status": status, "message": job.error}),
+            _ => json!({"status": status}),
+        };
+        job.number_polls += 1;
+        ResponseTemplate::new(200).set_body_json(json!({
+            "job": job_description(job_id, job),
+            "response": response,
+        }))
+    }
+
+    /// Cancels a job that has not finished yet
+    fn cancel(&mut self, job_id: &str) -> ResponseTemplate {
+        let config = self.config.clone();
+        match self.job_mut(job_id) {
+            Some(job) if matches!(job.status(&config), "queued" | "ongoing") => {
+                job.cancelled = true;
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "job": job_description(job_id, job),
+                    "response": {"status": "cancelled"},
+                }))
+            }
+            _ => ResponseTemplate::new(404).set_body_json(json!({"detail": "Not Found"})),
+        }
+    }
+}
+
+/// Returns the description of a job in the format of the AQT API
+fn job_description(job_id: &str, job: &MockJob) -> serde_json::Value {
+    json!({
+        "job_id": job_id,
+        "job_type": "quantum_circuit",
+        "label": job.label,
+        "resource_id": job.resource_id,
+        "workspace_id": "qoqo-integration",
+        "number_of_circuits": job.number_circuits,
+    })
+}
+
+/// Responder dispatching the requests of the mock server to the shared state
+#[derive(Debug)]
+struct MockResponder {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Respond for MockResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let latency = state.config.latency;
+        state.respond(request).set_delay(latency)
+    }
+}
+
+/// Mock AQT REST server
+///
+/// The server runs in a background thread until it is dropped.
+/// It must be started outside of an asynchronous runtime, for example in a normal `#[test]`.
+#[derive(Debug)]
+pub struct MockAqtServer {
+    /// The underlying HTTP server
+    server: MockServer,
+    /// Shared state of the server
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockAqtServer {
+    /// Starts a new mock server
+    ///
+    /// # Arguments
+    ///
+    /// `config` - The configuration of the mock server
+    ///
+    /// # Returns
+    ///
+    /// `MockAqtServer` - The running mock server
+    pub fn start(config: MockServerConfig) -> Result<Self, RoqoqoBackendError> {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let state = Arc::new(Mutex::new(MockState {
+            config,
+            jobs: Vec::new(),
+            failing_submissions: Vec::new(),
+            failing_jobs: Vec::new(),
+            rng,
+        }));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not start mock server {err:?}"),
+            })?;
+        let responder = MockResponder {
+            state: state.clone(),
+        };
+        let server = runtime.block_on(async {
+            let server = MockServer::builder().start().await;
+            Mock::given(any())
+                .respond_with(responder)
+                .mount(&server)
+                .await;
+            server
+        });
+        Ok(Self { server, state })
+    }
+
+    /// Returns the URL of the mock server that replaces the remote host of a device
+    pub fn remote_host(&self) -> String {
+        format!("{}/", self.server.uri())
+    }
+
+    /// Returns an [AqtDevice] sending its requests to the mock server
+    ///
+    /// # Arguments
+    ///
+    /// `number_qubits` - The number of qubits of the device
+    pub fn device(&self, number_qubits: usize) -> AqtDevice {
+        AqtDevice::new(number_qubits).set_remote_host(Some(self.remote_host()))
+    }
+
+    /// Answers the next submissions with an HTTP error instead of accepting the jobs
+    ///
+    /// # Arguments
+    ///
+    /// `number_submissions` - The number of failing submissions
+    /// `status_code` - The HTTP status code returned for the failing submissions
+    pub fn fail_next_submissions(&self, number_submissions: usize, status_code: u16) {
+        self.lock()
+            .failing_submissions
+            .extend(std::iter::repeat(status_code).take(number_submissions));
+    }
+
+    /// Lets the next accepted jobs end with the status error instead of finishing
+    ///
+    /// # Arguments
+    ///
+    /// `number_jobs` - The number of failing jobs
+    /// `message` - The error message reported for the failing jobs
+    pub fn fail_next_jobs(&self, number_jobs: usize, message: &str) {
+        self.lock()
+            .failing_jobs
+            .extend(std::iter::repeat(message.to_string()).take(number_jobs));
+    }
+
+    /// Sets whether the resource is reported as online
+    pub fn set_online(&self, online: bool) {
+        self.lock().config.online = online;
+    }
+
+    /// Sets the delay before every response of the server
+    pub fn set_latency(&self, latency: Duration) {
+        self.lock().config.latency = latency;
+    }
+
+    /// Returns the ids of all jobs submitted to the server, in order of submission
+    pub fn job_ids(&self) -> Vec<String> {
+        self.lock()
+            .jobs
+            .iter()
+            .map(|(job_id, _)| job_id.clone())
+            .collect()
+    }
+
+    /// Returns the current status of a submitted job, None when the job does not exist
+    pub fn job_status(&self, job_id: &str) -> Option<String> {
+        let state = self.lock();
+        state
+            .jobs
+            .iter()
+            .find(|(id, _)| id == job_id)
+            .map(|(_, job)| job.status(&state.config).to_string())
+    }
+
+    /// Locks the shared state, a panic in another thread does not invalidate the state
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Noise amplification and zero-noise extrapolation
+//!
+//! The noise of a circuit is amplified by unitary folding: a gate G is replaced by G (G^dagger G)^k,
+//! which implements the same unitary with 2k + 1 times the noise of the gate.
+//! Only `R` and `RXX` instructions are folded, `RZ` instructions are virtual on AQT hardware.
+//! Expectation values measured at several noise scales are extrapolated to the zero-noise limit.
+
+use crate::AqtInstruction;
+use roqoqo::RoqoqoBackendError;
+use std::collections::HashMap;
+
+/// Expectation values of a measurement at several noise scales and their zero-noise extrapolation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ZeroNoiseExtrapolation {
+    /// The noise scale factors the measurement was run with
+    pub scale_factors: Vec<f64>,
+    /// The expectation values for each scale factor by name
+    pub values: HashMap<String, Vec<f64>>,
+    /// The expectation values extrapolated to zero noise by name
+    pub extrapolated: HashMap<String, f64>,
+}
+
+/// Amplifies the noise of AQT instructions by unitary folding.
+///
+/// For a scale factor s, round(n (s - 1) / 2) folds are distributed over the n foldable instructions,
+/// each instruction is folded at most once more than any other, starting with the first instructions.
+/// Odd integer scale factors are therefore implemented exactly.
+///
+/// # Arguments
+///
+/// `instructions` - The AQT instructions that are folded
+/// `scale_factor` - The factor the noise is amplified by, at least 1
+///
+/// # Returns
+///
+/// `Vec<AqtInstruction>` - The folded instructions implementing the same unitary
+/// `RoqoqoBackendError::GenericError` - The scale factor is smaller than 1
+pub fn fold_instructions(
+    instructions: &[AqtInstruction],
+    scale_factor: f64,
+) -> Result<Vec<AqtInstruction>, RoqoqoBackendError> {
+    if !scale_factor.is_finite() || scale_factor < 1.0 {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!("Noise scale factor must be at least 1, got {scale_factor}"),
+        });
+    }
+    let number_foldable = instructions
+        .iter()
+        .filter(|instruction| inverse(instruction).is_some())
+        .count();
+    if number_foldable == 0 {
+        return Ok(instructions.to_vec());
+    }
+    let number_folds = (number_foldable as f64 * (scale_factor - 1.0) / 2.0).round() as usize;
+    let (full_folds, partial_folds) = (
+        number_folds / number_foldable,
+        number_folds % number_foldable,
+    );
+    let mut folded: Vec<AqtInstruction> = Vec::new();
+    let mut foldable_index = 0;
+    for instruction in instructions {
+        folded.push(instruction.clone());
+        if let Some(inverse) = inverse(instruction) {
+            let folds = full_folds + usize::from(foldable_index < partial_folds);
+            for _ in 0..folds {
+                folded.extend(inverse.iter().cloned());
+                folded.push(instruction.clone());
+            }
+            foldable_index += 1;
+        }
+    }
+    Ok(folded)
+}
+
+/// Extrapolates values measured at several noise scales to zero noise.
+///
+/// A polynomial of the given order is fitted to the values with least squares and evaluated at zero.
+/// Order 1 is a linear extrapolation, an order of one less than the number of scale factors is Richardson extrapolation.
+///
+/// # Arguments
+///
+/// `scale_factors` - The noise scale factors
+/// `values` - The values measured at each noise scale factor
+/// `order` - The order of the fitted polynomial
+///
+/// # Returns
+///
+/// `f64` - The extrapolated value
+/// `RoqoqoBackendError::GenericError` - The number of values does not match or is too small for the order
+pub fn extrapolate_to_zero(
+    scale_factors: &[f64],
+    values: &[f64],
+    order: usize,
+) -> Result<f64, RoqoqoBackendError> {
+    if scale_factors.len() != values.len() {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Got {} values for {} noise scale factors",
+                values.len(),
+                scale_factors.len()
+            ),
+        });
+    }
+    let mut distinct: Vec<f64> = scale_factors.to_vec();
+    distinct.sort_by(f64::total_cmp);
+    distinct.dedup();
+    if distinct.len() <= order {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Extrapolation of order {order} needs at least {} different noise scale factors",
+                order + 1
+            ),
+        });
+    }
+    // Normal equations of the least squares fit
+    let size = order + 1;
+    let mut matrix: Vec<Vec<f64>> = vec![vec![0.0; size + 1]; size];
+    for (scale, value) in scale_factors.iter().zip(values) {
+        let powers: Vec<f64> = (0..size).map(|power| scale.powi(power as i32)).collect();
+        for (row, row_power) in matrix.iter_mut().zip(powers.iter()) {
+            for (entry, column_power) in row.iter_mut().zip(powers.iter()) {
+                *entry += row_power * column_power;
+            }
+            row[size] += row_power * value;
+        }
+    }
+    // Gaussian elimination with partial pivoting
+    for column in 0..size {
+        let pivot = (column..size)
+            .max_by(|a, b| {
+                matrix[*a][column]
+                    .abs()
+                    .total_cmp(&matrix[*b][column].abs())
+            })
+            .unwrap_or(column);
+        matrix.swap(column, pivot);
+        let (upper, lower) = matrix.split_at_mut(column + 1);
+        let pivot_row = &upper[column];
+        for row in lower.iter_mut() {
+            let factor = row[column] / pivot_row[column];
+            for (entry, pivot_entry) in row.iter_mut().zip(pivot_row.iter()).skip(column) {
+                *entry -= factor * pivot_entry;
+            }
+        }
+    }
+    let mut coefficients = vec![0.0; size];
+    for row in (0..size).rev() {
+        let sum: f64 = (row + 1..size)
+            .map(|index| matrix[row][index] * coefficients[index])
+            .sum();
+        coefficients[row] = (matrix[row][size] - sum) / matrix[row][row];
+    }
+    Ok(coefficients[0])
+}
+
+/// Returns the instructions implementing the inverse of a foldable instruction
+fn inverse(instruction: &AqtInstruction) -> Option<Vec<AqtInstruction>> {
+    match instruction {
+        AqtInstruction::R { phi, theta, qubit } => Some(vec![AqtInstruction::R {
+            phi: (phi + 1.0).rem_euclid(2.0),
+            theta: *theta,
+            qubit: *qubit,
+        }]),
+        // Z on one qubit anticommutes with XX, so conjugating with RZ(1) inverts the rotation
+        AqtInstruction::RXX { qubits, theta } => {
+            let z = AqtInstruction::RZ {
+                phi: 1.0,
+                qubit: *qubits.first()?,
+            };
+            Some(vec![
+                z.clone(),
+                AqtInstruction::RXX {
+                    qubits: qubits.clone(),
+                    theta: *theta,
+                },
+                z,
+            ])
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,500 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Optimisation of AQT instructions
+//!
+//! The translator emits one AQT instruction per roqoqo operation. The optimisation passes reduce
+//! the number of instructions sent to the device, which reduces both the runtime and the errors on ion hardware.
+
+use crate::simulator::StateVector;
+use crate::AqtInstruction;
+use num_complex::Complex64;
+use roqoqo::RoqoqoBackendError;
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::PI;
+
+/// Tolerance below which angles and matrix elements are treated as zero
+const TOLERANCE: f64 = 1e-9;
+
+/// Largest angle of an RXX instruction accepted by AQT devices, in units of pi
+pub const MAX_RXX_ANGLE: f64 = 0.5;
+
+/// Largest number of qubits for which the equivalence of instructions can be verified
+pub const MAX_VERIFICATION_QUBITS: usize = 10;
+
+/// 2x2 unitary matrix of a single-qubit gate
+type Matrix = [[Complex64; 2]; 2];
+
+/// Reduction of the number of instructions achieved by an optimisation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct OptimisationReport {
+    /// Number of instructions before the optimisation
+    pub instructions_before: usize,
+    /// Number of instructions after the optimisation
+    pub instructions_after: usize,
+    /// Number of single-qubit R and RZ instructions before the optimisation
+    pub single_qubit_instructions_before: usize,
+    /// Number of single-qubit R and RZ instructions after the optimisation
+    pub single_qubit_instructions_after: usize,
+    /// Number of two-qubit RXX instructions before the optimisation
+    pub two_qubit_instructions_before: usize,
+    /// Number of two-qubit RXX instructions after the optimisation
+    pub two_qubit_instructions_after: usize,
+}
+
+impl OptimisationReport {
+    /// Creates the report comparing the instructions before and after an optimisation
+    fn new(before: &[AqtInstruction], after: &[AqtInstruction]) -> Self {
+        Self {
+            instructions_before: before.len(),
+            instructions_after: after.len(),
+            single_qubit_instructions_before: count_single_qubit_instructions(before),
+            single_qubit_instructions_after: count_single_qubit_instructions(after),
+            two_qubit_instructions_before: count_two_qubit_instructions(before),
+            two_qubit_instructions_after: count_two_qubit_instructions(after),
+        }
+    }
+
+    /// Returns the number of instructions removed by the optimisation
+    pub fn removed_instructions(&self) -> usize {
+        self.instructions_before
+            .saturating_sub(self.instructions_after)
+    }
+}
+
+/// Applies all optimisation passes to the AQT instructions of a circuit
+///
+/// Single-qubit rotations are fused before and after merging the RXX instructions,
+/// so rotations that become adjacent when RXX instructions cancel are fused as well.
+///
+/// # Arguments
+///
+/// `instructions` - The AQT instructions of a circuit
+///
+/// # Returns
+///
+/// `(Vec<AqtInstruction>, OptimisationReport)` - The optimised instructions and the achieved reduction
+pub fn optimise_instructions(
+    instructions: &[AqtInstruction],
+) -> (Vec<AqtInstruction>, OptimisationReport) {
+    let (fused, _) = fuse_single_qubit_gates(instructions);
+    let (merged, _) = merge_two_qubit_gates(&fused);
+    let (optimised, _) = fuse_single_qubit_gates(&merged);
+    let report = OptimisationReport::new(instructions, &optimised);
+    (optimised, report)
+}
+
+/// Fuses single-qubit rotations and removes virtual Z rotations
+///
+/// Consecutive R and RZ instructions on a qubit are fused into at most one R followed by one RZ instruction.
+/// The RZ rotations are virtual: they are postponed as long as possible and dropped before the
+/// final measurement, which is not affected by rotations around the Z axis.
+/// Z rotations by pi on both qubits of an RXX instruction commute with the gate and are postponed further.
+/// Rotations equal to the identity are dropped.
+///
+/// # Arguments
+///
+/// `instructions` - The AQT instructions of a circuit
+///
+/// # Returns
+///
+/// `(Vec<AqtInstruction>, OptimisationReport)` - The optimised instructions and the achieved reduction
+pub fn fuse_single_qubit_gates(
+    instructions: &[AqtInstruction],
+) -> (Vec<AqtInstruction>, OptimisationReport) {
+    // Accumulated single-qubit rotation of each qubit that has not been emitted yet
+    let mut pending: BTreeMap<u32, Matrix> = BTreeMap::new();
+    let mut optimised: Vec<AqtInstruction> = Vec::with_capacity(instructions.len());
+    for instruction in instructions {
+        match instruction {
+            AqtInstruction::RZ { phi, qubit } => {
+                let accumulated = pending.entry(*qubit).or_insert_with(identity);
+                *accumulated = multiply(&rz_matrix(*phi), accumulated);
+            }
+            AqtInstruction::R { phi, theta, qubit } => {
+                let accumulated = pending.entry(*qubit).or_insert_with(identity);
+                *accumulated = multiply(&r_matrix(*theta, *phi), accumulated);
+            }
+            AqtInstruction::RXX { qubits, .. } => {
+                // Z rotations by pi on both qubits commute with the RXX gate
+                let commuting_z = qubits.len() == 2
+                    && qubits.iter().all(|qubit| {
+                        pending.get(qubit).is_some_and(|matrix| {
+                            let (theta, _, gamma) = decompose(matrix);
+                            theta < TOLERANCE && (gamma.abs() - 1.0).abs() < TOLERANCE
+                        })
+                    });
+                if !commuting_z {
+                    for qubit in qubits {
+                        if let Some(matrix) = pending.remove(qubit) {
+                            emit_rotation(&mut optimised, *qubit, &matrix, true);
+                        }
+                    }
+                }
+                optimised.push(instruction.clone());
+            }
+            AqtInstruction::MEASURE => {
+                // Z rotations directly before the measurement do not change the measured probabilities
+                for (qubit, matrix) in std::mem::take(&mut pending) {
+                    emit_rotation(&mut optimised, qubit, &matrix, false);
+                }
+                optimised.push(instruction.clone());
+            }
+        }
+    }
+    for (qubit, matrix) in pending {
+        emit_rotation(&mut optimised, qubit, &matrix, true);
+    }
+    let report = OptimisationReport::new(instructions, &optimised);
+    (optimised, report)
+}
+
+/// Merges adjacent RXX instructions acting on the same pair of qubits
+///
+/// RXX instructions on the same pair of qubits that are not separated by instructions acting on one of the qubits
+/// are merged into a single instruction with the summed angle. Instructions composing to the identity cancel.
+/// The merged angle is kept within [0, [MAX_RXX_ANGLE]]: larger angles are split into several RXX instructions
+/// and negative angles are implemented by conjugating with Z rotations.
+/// Merged instructions are only replaced when this does not increase the number of instructions.
+///
+/// # Arguments
+///
+/// `instructions` - The AQT instructions of a circuit
+///
+/// # Returns
+///
+/// `(Vec<AqtInstruction>, OptimisationReport)` - The optimised instructions and the achieved reduction
+pub fn merge_two_qubit_gates(
+    instructions: &[AqtInstruction],
+) -> (Vec<AqtInstruction>, OptimisationReport) {
+    // Instructions in order, merged RXX instructions are collected in a single entry
+    let mut entries: Vec<Option<Vec<AqtInstruction>>> = Vec::with_capacity(instructions.len());
+    // Indices of the entries acting on each qubit
+    let mut qubit_entries: HashMap<u32, Vec<usize>> = HashMap::new();
+    // Index of the last MEASURE entry, which acts on all qubits
+    let mut barrier: Option<usize> = None;
+    for instruction in instructions {
+        match instruction {
+            AqtInstruction::RXX { qubits, .. } if qubits.len() == 2 => {
+                let last_entries: Vec<Option<usize>> = qubits
+                    .iter()
+                    .map(|qubit| {
+                        qubit_entries
+                            .get(qubit)
+                            .and_then(|indices| indices.last().copied())
+                            .filter(|index| barrier.map_or(true, |barrier| *index > barrier))
+                    })
+                    .collect();
+                let mergeable = match (last_entries[0], last_entries[1]) {
+                    (Some(first), Some(second)) if first == second => entries[first]
+                        .as_ref()
+                        .and_then(|group| group.first())
+                        .is_some_and(|previous| same_pair(previous, qubits))
+                        .then_some(first),
+                    _ => None,
+                };
+                match mergeable {
+                    Some(index) => {
+                        let group = entries[index].get_or_insert_with(Vec::new);
+                        group.push(instruction.clone());
+                        if rxx_angle(group).rem_euclid(2.0) < TOLERANCE
+                            || 2.0 - rxx_angle(group).rem_euclid(2.0) < TOLERANCE
+                        {
+                            // The merged instructions cancel, earlier instructions become adjacent again
+                            entries[index] = None;
+                            for qubit in qubits {
+                                if let Some(indices) = qubit_entries.get_mut(qubit) {
+                                    indices.pop();
+                                }
+                            }
+                        }
+                    }
+                    None => {
+                        entries.push(Some(vec![instruction.clone()]));
+                        for qubit in qubits {
+                            qubit_entries
+                                .entry(*qubit)
+                                .or_default()
+                                .push(entries.len() - 1);
+                        }
+                    }
+                }
+            }
+            AqtInstruction::RZ { qubit, .. } | AqtInstruction::R { qubit, .. } => {
+                entries.push(Some(vec![instruction.clone()]));
+                qubit_entries
+                    .entry(*qubit)
+                    .or_default()
+                    .push(entries.len() - 1);
+            }
+            _ => {
+                entries.push(Some(vec![instruction.clone()]));
+                barrier = Some(entries.len() - 1);
+            }
+        }
+    }
+    let mut optimised: Vec<AqtInstruction> = Vec::with_capacity(instructions.len());
+    for group in entries.into_iter().flatten() {
+        match group.first() {
+            Some(AqtInstruction::RXX { qubits, .. }) if group.len() > 1 => {
+                let merged = rxx_within_range(qubits, rxx_angle(&group));
+                if merged.len() <= group.len() {
+                    optimised.extend(merged);
+                } else {
+                    optimised.extend(group);
+                }
+            }
+            _ => optimised.extend(group),
+        }
+    }
+    let report = OptimisationReport::new(instructions, &optimised);
+    (optimised, report)
+}
+
+/// Verifies that two lists of AQT instructions are equivalent
+///
+/// The unitaries of the instructions are compared up to a global phase. When both lists end with a measurement,
+/// the unitaries only need to agree up to Z rotations before the measurement, which do not change the measured probabilities.
+///
+/// # Arguments
+///
+/// `original` - The instructions before the optimisation
+/// `optimised` - The instructions after the optimisation
+///
+/// # Returns
+///
+/// `bool` - Whether the instructions are equivalent
+/// `RoqoqoBackendError::GenericError` - Error when the instructions act on more than [MAX_VERIFICATION_QUBITS] qubits
+pub fn verify_equivalence(
+    original: &[AqtInstruction],
+    optimised: &[AqtInstruction],
+) -> Result<bool, RoqoqoBackendError> {
+    let number_qubits = original
+        .iter()
+        .chain(optimised.iter())
+        .flat_map(|instruction| match instruction {
+            AqtInstruction::RZ { qubit, .. } | AqtInstruction::R { qubit, .. } => vec![*qubit],
+            AqtInstruction::RXX { qubits, .. } => qubits.clone(),
+            AqtInstruction::MEASURE => Vec::new(),
+        })
+        .max()
+        .map(|qubit| qubit as usize + 1)
+        .unwrap_or(0);
+    if number_qubits > MAX_VERIFICATION_QUBITS {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Equivalence can only be verified for up to {MAX_VERIFICATION_QUBITS} qubits, instructions act on {number_qubits} qubits"
+            ),
+        });
+    }
+    let measured = original.last() == Some(&AqtInstruction::MEASURE)
+        && optimised.last() == Some(&AqtInstruction::MEASURE);
+    let dimension = 1 << number_qubits;
+    // Overlap of the columns of the two unitaries, summed over all columns or for each row
+    let mut trace = Complex64::new(0.0, 0.0);
+    let mut row_overlaps = vec![Complex64::new(0.0, 0.0); dimension];
+    for column in 0..dimension {
+        let mut original_state = StateVector::basis_state(number_qubits, column);
+        for instruction in original {
+            original_state.apply_instruction(instruction)?;
+        }
+        let mut optimised_state = StateVector::basis_state(number_qubits, column);
+        for instruction in optimised {
+            optimised_state.apply_instruction(instruction)?;
+        }
+        for (row, (original_amplitude, optimised_amplitude)) in original_state
+            .amplitudes()
+            .iter()
+            .zip(optimised_state.amplitudes().iter())
+            .enumerate()
+        {
+            let overlap = original_amplitude.conj() * optimised_amplitude;
+            trace += overlap;
+            row_overlaps[row] += overlap;
+        }
+    }
+    let tolerance = 1e-6 * dimension as f64;
+    if measured {
+        // Each row may differ by a phase, all rows of a unitary are normalised
+        Ok(row_overlaps
+            .iter()
+            .all(|overlap| (overlap.norm() - 1.0).abs() < 1e-6))
+    } else {
+        Ok((trace.norm() - dimension as f64).abs() < tolerance)
+    }
+}
+
+/// Returns whether an RXX instruction acts on the same pair of qubits, in any order
+fn same_pair(instruction: &AqtInstruction, qubits: &[u32]) -> bool {
+    match instruction {
+        AqtInstruction::RXX {
+            qubits: other_qubits,
+            ..
+        } => {
+            other_qubits.len() == 2
+                && ((other_qubits[0] == qubits[0] && other_qubits[1] == qubits[1])
+                    || (other_qubits[0] == qubits[1] && other_qubits[1] == qubits[0]))
+        }
+        _ => false,
+    }
+}
+
+/// Returns the summed angle of merged RXX instructions
+fn rxx_angle(group: &[AqtInstruction]) -> f64 {
+    group
+        .iter()
+        .map(|instruction| match instruction {
+            AqtInstruction::RXX { theta, .. } => *theta,
+            _ => 0.0,
+        })
+        .sum()
+}
+
+/// Implements an RXX rotation with RXX instructions whose angles lie in [0, [MAX_RXX_ANGLE]]
+///
+/// The rotation is defined up to a global phase, so angles are taken modulo 2.
+pub(crate) fn rxx_within_range(qubits: &[u32], theta: f64) -> Vec<AqtInstruction> {
+    let theta = theta.rem_euclid(2.0);
+    if theta < TOLERANCE || 2.0 - theta < TOLERANCE {
+        return Vec::new();
+    }
+    if theta > 1.0 + TOLERANCE {
+        // RXX(-t) = RZ(1) RXX(t) RZ(1) on one of the qubits, up to a global phase
+        let mut instructions = vec![AqtInstruction::RZ {
+            phi: 1.0,
+            qubit: qubits[0],
+        }];
+        instructions.extend(rxx_within_range(qubits, 2.0 - theta));
+        instructions.push(AqtInstruction::RZ {
+            phi: 1.0,
+            qubit: qubits[0],
+        });
+        return instructions;
+    }
+    let mut remaining = theta;
+    let mut instructions = Vec::new();
+    while remaining > TOLERANCE {
+        let angle = remaining.min(MAX_RXX_ANGLE);
+        instructions.push(AqtInstruction::RXX {
+            qubits: qubits.to_vec(),
+            theta: angle,
+        });
+        remaining -= angle;
+    }
+    instructions
+}
+
+/// Counts the two-qubit RXX instructions
+fn count_two_qubit_instructions(instructions: &[AqtInstruction]) -> usize {
+    instructions
+        .iter()
+        .filter(|instruction| matches!(instruction, AqtInstruction::RXX { .. }))
+        .count()
+}
+
+/// Counts the single-qubit R and RZ instructions
+fn count_single_qubit_instructions(instructions: &[AqtInstruction]) -> usize {
+    instructions
+        .iter()
+        .filter(|instruction| {
+            matches!(
+                instruction,
+                AqtInstruction::R { .. } | AqtInstruction::RZ { .. }
+            )
+        })
+        .count()
+}
+
+/// Emits a fused single-qubit rotation as one R and one RZ instruction, leaving out identities
+fn emit_rotation(
+    optimised: &mut Vec<AqtInstruction>,
+    qubit: u32,
+    matrix: &Matrix,
+    include_rz: bool,
+) {
+    let (theta, phi, gamma) = decompose(matrix);
+    if theta > TOLERANCE {
+        optimised.push(AqtInstruction::R { phi, theta, qubit });
+    }
+    if include_rz && gamma.abs() > TOLERANCE {
+        optimised.push(AqtInstruction::RZ { phi: gamma, qubit });
+    }
+}
+
+/// Decomposes a single-qubit unitary into RZ(gamma) R(theta, phi) up to a global phase
+///
+/// All angles are in units of pi like the angles of AQT instructions,
+/// theta lies in [0, 1], phi in [0, 2) and gamma in (-1, 1].
+fn decompose(matrix: &Matrix) -> (f64, f64, f64) {
+    let a = matrix[0][0];
+    let b = matrix[0][1];
+    let c = matrix[1][0];
+    let d = matrix[1][1];
+    let theta = 2.0 * c.norm().atan2(a.norm()) / PI;
+    let (phi, gamma) = if a.norm() < TOLERANCE {
+        // Only the combination of gamma and phi is defined for a rotation by pi
+        (((c.arg() - b.arg()) / 2.0) / PI, 0.0)
+    } else {
+        let gamma = (d.arg() - a.arg()) / PI;
+        if c.norm() < TOLERANCE {
+            (0.0, gamma)
+        } else {
+            ((c.arg() - a.arg() + PI / 2.0) / PI - gamma, gamma)
+        }
+    };
+    let phi = phi.rem_euclid(2.0);
+    let phi = if 2.0 - phi < TOLERANCE { 0.0 } else { phi };
+    let gamma = gamma.rem_euclid(2.0);
+    let gamma = if gamma > 1.0 { gamma - 2.0 } else { gamma };
+    let gamma = if (gamma + 1.0).abs() < TOLERANCE {
+        1.0
+    } else {
+        gamma
+    };
+    (theta, phi, gamma)
+}
+
+/// Returns the identity matrix
+fn identity() -> Matrix {
+    let zero = Complex64::new(0.0, 0.0);
+    let one = Complex64::new(1.0, 0.0);
+    [[one, zero], [zero, one]]
+}
+
+/// Returns the matrix of an RZ instruction, exp(-i phi pi/2 Z)
+fn rz_matrix(phi: f64) -> Matrix {
+    let zero = Complex64::new(0.0, 0.0);
+    let phase = Complex64::from_polar(1.0, phi * PI / 2.0);
+    [[phase.conj(), zero], [zero, phase]]
+}
+
+/// Returns the matrix of an R instruction, exp(-i theta pi/2 (cos(phi pi) X + sin(phi pi) Y))
+fn r_matrix(theta: f64, phi: f64) -> Matrix {
+    let cos = Complex64::new((theta * PI / 2.0).cos(), 0.0);
+    let minus_i_sin = Complex64::new(0.0, -(theta * PI / 2.0).sin());
+    let phase = Complex64::from_polar(1.0, phi * PI);
+    [
+        [cos, minus_i_sin * phase.conj()],
+        [minus_i_sin * phase, cos],
+    ]
+}
+
+/// Multiplies two 2x2 matrices
+fn multiply(left: &Matrix, right: &Matrix) -> Matrix {
+    let mut product = [[Complex64::new(0.0, 0.0); 2]; 2];
+    for (row, product_row) in product.iter_mut().enumerate() {
+        for (column, element) in product_row.iter_mut().enumerate() {
+            *element = left[row][0] * right[0][column] + left[row][1] * right[1][column];
+        }
+    }
+    product
+}
@@ -0,0 +1,406 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Readout error mitigation
+//!
+//! State detection of trapped ions confuses bright and dark states with a small probability.
+//! The errors are calibrated by measuring circuits preparing all qubits in 0 and all qubits in 1,
+//! which determines a confusion matrix for each qubit. The tensor product of the per-qubit matrices
+//! is inverted to correct measured distributions and the expectation values of PauliZProduct measurements.
+
+use qoqo_calculator::Calculator;
+use roqoqo::measurements::{PauliProductsToExpVal, PauliZProduct};
+use roqoqo::operations::{DefinitionBit, PauliX, PragmaRepeatedMeasurement};
+use roqoqo::registers::BitOutputRegister;
+use roqoqo::{Circuit, RoqoqoBackendError};
+use std::collections::HashMap;
+
+/// Name of the readout register of the calibration circuits
+pub const CALIBRATION_READOUT: &str = "readout_calibration";
+
+/// Method used to correct measured distributions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum MitigationMethod {
+    /// Applies the inverse of the confusion matrix, the corrected quasi-probabilities can be negative
+    Inverse,
+    /// Applies the inverse of the confusion matrix and projects the result onto the closest probability
+    /// distribution in the least squares sense
+    LeastSquares,
+}
+
+/// Calibrated readout errors of the qubits of a device
+///
+/// The confusion matrices are validated when the calibration is created or deserialized,
+/// so all probabilities are finite.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "ReadoutCalibrationSerialize")]
+pub struct ReadoutCalibration {
+    /// Id of the device the calibration was measured on
+    device_id: String,
+    /// Confusion matrix of each qubit, entry [measured][prepared] is the probability to measure a state when the other was prepared
+    confusion_matrices: Vec<[[f64; 2]; 2]>,
+}
+
+// The constructor rejects confusion matrices with NaN entries
+impl Eq for ReadoutCalibration {}
+
+/// Unvalidated calibration read by serde
+#[derive(serde::Deserialize)]
+struct ReadoutCalibrationSerialize {
+    device_id: String,
+    confusion_matrices: Vec<[[f64; 2]; 2]>,
+}
+
+impl TryFrom<ReadoutCalibrationSerialize> for ReadoutCalibration {
+    type Error = RoqoqoBackendError;
+
+    fn try_from(value: ReadoutCalibrationSerialize) -> Result<Self, Self::Error> {
+        Self::new(&value.device_id, value.confusion_matrices)
+    }
+}
+
+impl ReadoutCalibration {
+    /// Creates a calibration from the confusion matrices of the qubits.
+    ///
+    /// # Arguments
+    ///
+    /// `device_id` - The id of the device the calibration was measured on
+    /// `confusion_matrices` - Confusion matrix of each qubit, entry [measured][prepared] is the probability
+    ///                        to measure a state when the other was prepared
+    ///
+    /// # Returns
+    ///
+    /// `ReadoutCalibration` - The calibration
+    /// `RoqoqoBackendError::GenericError` - An entry is not a probability, a column does not sum to 1
+    ///                                      or a matrix can not be inverted
+    pub fn new(
+        device_id: &str,
+        confusion_matrices: Vec<[[f64; 2]; 2]>,
+    ) -> Result<Self, RoqoqoBackendError> {
+        for (qubit, matrix) in confusion_matrices.iter().enumerate() {
+            let invalid = matrix
+                .iter()
+                .flatten()
+                .any(|entry| !(0.0..=1.0).contains(entry))
+                || (0..2)
+                    .any(|prepared| (matrix[0][prepared] + matrix[1][prepared] - 1.0).abs() > 1e-9);
+            if invalid {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Confusion matrix {matrix:?} of qubit {qubit} does not contain probabilities summing to 1 for each prepared state"
+                    ),
+                });
+            }
+        }
+        let calibration = Self {
+            device_id: device_id.to_string(),
+            confusion_matrices,
+        };
+        // Checks that all confusion matrices can be inverted
+        calibration.inverse_matrices()?;
+        Ok(calibration)
+    }
+
+    /// Returns the id of the device the calibration was measured on.
+    pub fn device_id(&self) -> &str {
+        &self.device_id
+    }
+
+    /// Returns the confusion matrix of each qubit, entry [measured][prepared] is the probability
+    /// to measure a state when the other was prepared.
+    pub fn confusion_matrices(&self) -> &[[[f64; 2]; 2]] {
+        &self.confusion_matrices
+    }
+
+    /// Returns the circuits preparing all qubits in 0 and all qubits in 1 that calibrate the readout errors
+    ///
+    /// # Arguments
+    ///
+    /// `number_qubits` - The number of qubits that are calibrated
+    /// `number_measurements` - The number of measurements of each calibration circuit
+    pub fn calibration_circuits(number_qubits: usize, number_measurements: usize) -> [Circuit; 2] {
+        let mut prepared_zero = Circuit::new();
+        prepared_zero += DefinitionBit::new(CALIBRATION_READOUT.to_string(), number_qubits, true);
+        let mut prepared_one = prepared_zero.clone();
+        for qubit in 0..number_qubits {
+            prepared_one += PauliX::new(qubit);
+        }
+        let measurement = PragmaRepeatedMeasurement::new(
+            CALIBRATION_READOUT.to_string(),
+            number_measurements,
+            None,
+        );
+        prepared_zero += measurement.clone();
+        prepared_one += measurement;
+        [prepared_zero, prepared_one]
+    }
+
+    /// Creates the calibration from the measured results of the calibration circuits
+    ///
+    /// # Arguments
+    ///
+    /// `device_id` - The id of the device the calibration circuits were measured on
+    /// `prepared_zero` - The measured shots of the circuit preparing all qubits in 0
+    /// `prepared_one` - The measured shots of the circuit preparing all qubits in 1
+    pub fn from_shots(
+        device_id: &str,
+        prepared_zero: &[Vec<bool>],
+        prepared_one: &[Vec<bool>],
+    ) -> Result<Self, RoqoqoBackendError> {
+        let number_qubits = prepared_zero
+            .first()
+            .map(|shot| shot.len())
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "Readout calibration needs measured shots".to_string(),
+            })?;
+        if prepared_one.is_empty() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "Readout calibration needs measured shots".to_string(),
+            });
+        }
+        let flip_probability = |shots: &[Vec<bool>], qubit: usize, prepared: bool| {
+            shots
+                .iter()
+                .filter(|shot| shot.get(qubit).copied().unwrap_or(false) != prepared)
+                .count() as f64
+                / shots.len() as f64
+        };
+        let confusion_matrices = (0..number_qubits)
+            .map(|qubit| {
+                let error_zero = flip_probability(prepared_zero, qubit, false);
+                let error_one = flip_probability(prepared_one, qubit, true);
+                [[1.0 - error_zero, error_one], [error_zero, 1.0 - error_one]]
+            })
+            .collect();
+        Self::new(device_id, confusion_matrices)
+    }
+
+    /// Returns the confusion matrix of the qubits as the tensor product of the per-qubit confusion matrices
+    ///
+    /// The basis states are indexed with qubit 0 as the least significant bit.
+    ///
+    /// # Arguments
+    ///
+    /// `qubits` - The qubits included in the matrix
+    pub fn tensored_confusion_matrix(
+        &self,
+        qubits: &[usize],
+    ) -> Result<Vec<Vec<f64>>, RoqoqoBackendError> {
+        let mut matrix = vec![vec![1.0]];
+        for (position, qubit) in qubits.iter().enumerate() {
+            let confusion = self.confusion_matrices.get(*qubit).ok_or_else(|| {
+                RoqoqoBackendError::GenericError {
+                    msg: format!("Qubit {qubit} is not included in the readout calibration"),
+                }
+            })?;
+            let size = 1 << position;
+            let mut tensored = vec![vec![0.0; 2 * size]; 2 * size];
+            for (measured, confusion_row) in confusion.iter().enumerate() {
+                for (prepared, confusion_entry) in confusion_row.iter().enumerate() {
+                    for (row, matrix_row) in matrix.iter().enumerate() {
+                        for (column, matrix_entry) in matrix_row.iter().enumerate() {
+                            tensored[measured * size + row][prepared * size + column] =
+                                confusion_entry * matrix_entry;
+                        }
+                    }
+                }
+            }
+            matrix = tensored;
+        }
+        Ok(matrix)
+    }
+
+    /// Corrects the distribution of measured shots for readout errors
+    ///
+    /// # Arguments
+    ///
+    /// `shots` - The measured shots
+    /// `method` - The method used for the correction
+    ///
+    /// # Returns
+    ///
+    /// `HashMap<Vec<bool>, f64>` - The corrected probability of each measured bitstring
+    pub fn correct_distribution(
+        &self,
+        shots: &[Vec<bool>],
+        method: MitigationMethod,
+    ) -> Result<HashMap<Vec<bool>, f64>, RoqoqoBackendError> {
+        let inverse_matrices = self.inverse_matrices()?;
+        let number_qubits = shots.first().map(|shot| shot.len()).unwrap_or(0);
+        if number_qubits > inverse_matrices.len() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Shots of {number_qubits} qubits exceed the {} calibrated qubits",
+                    inverse_matrices.len()
+                ),
+            });
+        }
+        let mut distribution: HashMap<Vec<bool>, f64> = HashMap::new();
+        for shot in shots {
+            if shot.len() != number_qubits {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: "All shots must have the same number of qubits".to_string(),
+                });
+            }
+            *distribution.entry(shot.clone()).or_default() += 1.0;
+        }
+        for count in distribution.values_mut() {
+            *count /= shots.len() as f64;
+        }
+        // The inverse of the tensor product is applied qubit by qubit
+        for (qubit, inverse) in inverse_matrices.iter().enumerate().take(number_qubits) {
+            let mut corrected: HashMap<Vec<bool>, f64> = HashMap::new();
+            for (bits, probability) in distribution {
+                let measured = bits[qubit] as usize;
+                for (prepared, inverse_row) in inverse.iter().enumerate() {
+                    let mut prepared_bits = bits.clone();
+                    prepared_bits[qubit] = prepared == 1;
+                    *corrected.entry(prepared_bits).or_default() +=
+                        inverse_row[measured] * probability;
+                }
+            }
+            distribution = corrected;
+        }
+        // Removes the bitstrings whose probability only differs from zero by rounding errors
+        distribution.retain(|_, probability| probability.abs() > 1e-12);
+        if method == MitigationMethod::LeastSquares {
+            distribution = closest_probability_distribution(distribution);
+        }
+        Ok(distribution)
+    }
+
+    /// Evaluates the expectation values of a PauliZProduct measurement with readout errors corrected
+    ///
+    /// Equivalent to the evaluation of the measurement, except that the Pauli products are obtained
+    /// from the corrected distributions of the readout registers.
+    ///
+    /// # Arguments
+    ///
+    /// `measurement` - The measurement that is evaluated
+    /// `bit_registers` - The measured bit registers of the measurement
+    /// `method` - The method used for the correction
+    pub fn evaluate_pauli_z_product(
+        &self,
+        measurement: &PauliZProduct,
+        bit_registers: &HashMap<String, BitOutputRegister>,
+        method: MitigationMethod,
+    ) -> Result<Option<HashMap<String, f64>>, RoqoqoBackendError> {
+        let input = &measurement.input;
+        let flipped_and_extension: &[(bool, &str)] = if input.use_flipped_measurement {
+            &[(false, ""), (true, "_flipped")]
+        } else {
+            &[(false, "")]
+        };
+        let mut pauli_products = vec![0.0; input.number_pauli_products];
+        for (register_name, mask) in input.pauli_product_qubit_masks.iter() {
+            for (flip_measurement, extension) in flipped_and_extension {
+                let name = format!("{register_name}{extension}");
+                let register =
+                    bit_registers
+                        .get(&name)
+                        .ok_or_else(|| RoqoqoBackendError::GenericError {
+                            msg: format!("bit register {name} not found"),
+                        })?;
+                let distribution = self.correct_distribution(register, method)?;
+                for (index, qubits) in mask.iter() {
+                    let value: f64 = distribution
+                        .iter()
+                        .map(|(bits, probability)| {
+                            let parity = qubits
+                                .iter()
+                                .filter(|qubit| {
+                                    bits.get(**qubit).copied().unwrap_or(false) ^ flip_measurement
+                                })
+                                .count();
+                            if parity % 2 == 0 {
+                                *probability
+                            } else {
+                                -probability
+                            }
+                        })
+                        .sum();
+                    pauli_products[*index] += value / flipped_and_extension.len() as f64;
+                }
+            }
+        }
+        let mut results: HashMap<String, f64> = HashMap::new();
+        for (name, evaluation) in input.measured_exp_vals.iter() {
+            let value = match evaluation {
+                PauliProductsToExpVal::Linear(coefficients) => coefficients
+                    .iter()
+                    .map(|(index, coefficient)| pauli_products[*index] * coefficient)
+                    .sum(),
+                PauliProductsToExpVal::Symbolic(expression) => {
+                    let mut calculator = Calculator::new();
+                    for (index, value) in pauli_products.iter().enumerate() {
+                        calculator.set_variable(&format!("pauli_product_{index}"), *value);
+                    }
+                    calculator.parse_get(expression.clone()).map_err(|err| {
+                        RoqoqoBackendError::GenericError {
+                            msg: format!("Could not evaluate expectation value {name}: {err:?}"),
+                        }
+                    })?
+                }
+            };
+            results.insert(name.clone(), value);
+        }
+        Ok(Some(results))
+    }
+
+    /// Returns the inverse of the confusion matrix of each qubit
+    fn inverse_matrices(&self) -> Result<Vec<[[f64; 2]; 2]>, RoqoqoBackendError> {
+        self.confusion_matrices
+            .iter()
+            .enumerate()
+            .map(|(qubit, [[a, b], [c, d]])| {
+                let determinant = a * d - b * c;
+                if determinant.abs() < 1e-9 {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: format!("Confusion matrix of qubit {qubit} can not be inverted"),
+                    });
+                }
+                Ok([
+                    [d / determinant, -b / determinant],
+                    [-c / determinant, a / determinant],
+                ])
+            })
+            .collect()
+    }
+}
+
+/// Projects quasi-probabilities summing to one onto the closest probability distribution in the euclidean norm
+///
+/// Negative entries are set to zero in ascending order and their weight is distributed equally
+/// over the remaining entries, see Smolin, Gambetta and Smith, Phys. Rev. Lett. 108, 070502 (2012).
+fn closest_probability_distribution(
+    distribution: HashMap<Vec<bool>, f64>,
+) -> HashMap<Vec<bool>, f64> {
+    let mut entries: Vec<(Vec<bool>, f64)> = distribution.into_iter().collect();
+    entries.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let mut accumulated = 0.0;
+    let number_entries = entries.len();
+    for (index, entry) in entries.iter_mut().enumerate() {
+        let remaining = (number_entries - index) as f64;
+        if entry.1 + accumulated / remaining < 0.0 {
+            accumulated += entry.1;
+            entry.1 = 0.0;
+        } else {
+            accumulated /= remaining;
+            break;
+        }
+    }
+    entries
+        .into_iter()
+        .filter(|(_, probability)| *probability > 0.0)
+        .map(|(bits, probability)| (bits, probability + accumulated))
+        .collect()
+}
@@ -0,0 +1,57 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Translation report
+//!
+//! Dry-run analysis of the translation of a circuit to AQT instructions, created by [crate::Backend::translation_report].
+
+use std::collections::HashMap;
+
+/// An operation of a circuit that cannot be translated to AQT instructions
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct UntranslatedOperation {
+    /// Position of the operation in the circuit
+    pub position: usize,
+    /// Name of the operation
+    pub hqslang: String,
+    /// Reason why the operation cannot be translated
+    pub reason: String,
+}
+
+/// Result of the dry-run analysis of the translation of a circuit
+///
+/// Unlike the translation itself, the analysis does not stop at the first operation that cannot be translated.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct TranslationReport {
+    /// Number of operations in the circuit
+    pub number_operations: usize,
+    /// Operations that are not supported by the AQT backend
+    pub unsupported_operations: Vec<UntranslatedOperation>,
+    /// Number of unsupported operations by name
+    pub unsupported_counts: HashMap<String, usize>,
+    /// Operations with symbolic parameters that have to be replaced before the circuit can be translated
+    pub symbolic_parameters: Vec<UntranslatedOperation>,
+    /// Number of operations ignored by the translation, such as definitions and pragmas, by name
+    pub ignored_operations: HashMap<String, usize>,
+    /// Number of AQT instructions of the translated circuit by instruction name,
+    /// including the circuits emulating pragmas
+    pub native_gate_counts: HashMap<String, usize>,
+    /// Size of the JSON body of the job in bytes, the translatable operations are used when some operations are not translatable
+    pub payload_size: usize,
+}
+
+impl TranslationReport {
+    /// Returns whether the whole circuit can be translated to AQT instructions
+    pub fn is_translatable(&self) -> bool {
+        self.unsupported_operations.is_empty() && self.symbolic_parameters.is_empty()
+    }
+}
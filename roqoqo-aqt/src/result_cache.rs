@@ -0,0 +1,250 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! On-disk cache of job results
+//!
+//! Results of finished jobs are stored in a directory, one JSON file per job named by the fingerprint of the job.
+//! Submitting a job with the same circuits to the same resource again returns the stored results
+//! instead of sending the job to the AQT servers.
+
+use crate::AqtRunData;
+use roqoqo::RoqoqoBackendError;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// When stored results are read from and new results are written to the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum CachePolicy {
+    /// The cache is neither read nor written
+    #[default]
+    Never,
+    /// Stored results are returned, new results are not stored
+    ReadOnly,
+    /// Stored results are returned and new results are stored
+    ReadWrite,
+    /// Like ReadWrite, but results older than the maximal age are ignored and replaced
+    Expiring(Duration),
+}
+
+impl CachePolicy {
+    /// Returns whether stored results are returned
+    pub fn reads(&self) -> bool {
+        !matches!(self, CachePolicy::Never)
+    }
+
+    /// Returns whether new results are stored
+    pub fn writes(&self) -> bool {
+        matches!(self, CachePolicy::ReadWrite | CachePolicy::Expiring(_))
+    }
+}
+
+/// Stored result of a finished job
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CacheEntry {
+    /// Id of the job the result was returned for
+    pub job_id: String,
+    /// Time the result was stored in seconds since the Unix epoch
+    pub created: u64,
+    /// The measured results of the circuits in the job as returned by the AQT device
+    pub result: HashMap<u32, Vec<Vec<u32>>>,
+}
+
+/// Cache of job results in a directory
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ResultCache {
+    /// The directory the results are stored in
+    directory: PathBuf,
+    /// When results are read from and written to the cache
+    policy: CachePolicy,
+}
+
+impl ResultCache {
+    /// Creates a cache of job results in a directory.
+    ///
+    /// The directory is created when the first result is stored.
+    ///
+    /// # Arguments
+    ///
+    /// `directory` - The directory the results are stored in
+    /// `policy` - When results are read from and written to the cache
+    pub fn new(directory: &Path, policy: CachePolicy) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+            policy,
+        }
+    }
+
+    /// Returns the directory the results are stored in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns when results are read from and written to the cache.
+    pub fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    /// Returns the fingerprint of a job on a resource.
+    ///
+    /// The fingerprint is the SHA-256 hash of the serialised circuits and job type together with the remote host
+    /// and the resource id, so resources with the same id on different hosts do not share results.
+    /// The label of the job does not change its results and is not part of the fingerprint.
+    ///
+    /// # Arguments
+    ///
+    /// `run_data` - The job sent to the AQT device
+    /// `remote_host` - The REST API endpoint the job is sent to
+    /// `resource_id` - The id of the AQT resource running the job
+    ///
+    /// # Returns
+    ///
+    /// `String` - The hexadecimal fingerprint
+    pub fn fingerprint(
+        run_data: &AqtRunData,
+        remote_host: &str,
+        resource_id: &str,
+    ) -> Result<String, RoqoqoBackendError> {
+        let payload = serde_json::to_string(&run_data.payload).map_err(|err| {
+            RoqoqoBackendError::GenericError {
+                msg: format!("Could not serialize job for result cache {err:?}"),
+            }
+        })?;
+        let mut hasher = Sha256::new();
+        for part in [remote_host, resource_id, &run_data.job_type, &payload] {
+            // Length prefixes keep the concatenation of the parts unambiguous
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        Ok(hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect())
+    }
+
+    /// Returns the stored result of a job when the policy allows reading it.
+    ///
+    /// # Arguments
+    ///
+    /// `fingerprint` - The fingerprint of the job
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Some(CacheEntry))` - The stored result
+    /// `Ok(None)` - No result is stored, it has expired or the cache is not read
+    /// `Err(RoqoqoBackendError)` - The stored result could not be read
+    pub fn load(&self, fingerprint: &str) -> Result<Option<CacheEntry>, RoqoqoBackendError> {
+        if !self.policy.reads() {
+            return Ok(None);
+        }
+        let path = self.entry_path(fingerprint);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content =
+            fs::read_to_string(&path).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not read cached result {}: {err:?}", path.display()),
+            })?;
+        let entry: CacheEntry =
+            serde_json::from_str(&content).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not parse cached result {}: {err:?}", path.display()),
+            })?;
+        if let CachePolicy::Expiring(max_age) = self.policy {
+            if now().saturating_sub(entry.created) > max_age.as_secs() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(entry))
+    }
+
+    /// Stores the result of a finished job when the policy allows writing it.
+    ///
+    /// # Arguments
+    ///
+    /// `fingerprint` - The fingerprint of the job
+    /// `job_id` - The id of the job
+    /// `result` - The measured results of the circuits in the job as returned by the AQT device
+    pub fn store(
+        &self,
+        fingerprint: &str,
+        job_id: &str,
+        result: &HashMap<u32, Vec<Vec<u32>>>,
+    ) -> Result<(), RoqoqoBackendError> {
+        if !self.policy.writes() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.directory).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Could not create result cache {}: {err:?}",
+                self.directory.display()
+            ),
+        })?;
+        let entry = CacheEntry {
+            job_id: job_id.to_string(),
+            created: now(),
+            result: result.clone(),
+        };
+        let content =
+            serde_json::to_string(&entry).map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not serialize cached result {err:?}"),
+            })?;
+        let path = self.entry_path(fingerprint);
+        // The result is written to a temporary file that is renamed, so other processes never read a partial entry
+        let temporary_path = self
+            .directory
+            .join(format!("{fingerprint}.{}.tmp", std::process::id()));
+        fs::write(&temporary_path, content)
+            .and_then(|_| fs::rename(&temporary_path, &path))
+            .map_err(|err| {
+                let _ = fs::remove_file(&temporary_path);
+                RoqoqoBackendError::GenericError {
+                    msg: format!("Could not write cached result {}: {err:?}", path.display()),
+                }
+            })
+    }
+
+    /// Removes all stored results from the cache directory.
+    pub fn clear(&self) -> Result<(), RoqoqoBackendError> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(()),
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                fs::remove_file(&path).map_err(|err| RoqoqoBackendError::GenericError {
+                    msg: format!("Could not remove cached result {}: {err:?}", path.display()),
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the path of the file storing the result of a job
+    fn entry_path(&self, fingerprint: &str) -> PathBuf {
+        self.directory.join(format!("{fingerprint}.json"))
+    }
+}
+
+/// Returns the current time in seconds since the Unix epoch
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,356 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statevector simulator for AQT instructions
+//!
+//! Simulates translated AQT circuits locally so that devices can be emulated without the AQT cloud.
+
+use crate::devices::{AqtNoiseModel, MAX_SIMULATED_QUBITS};
+use crate::AqtInstruction;
+use num_complex::Complex64;
+use rand::Rng;
+use roqoqo::RoqoqoBackendError;
+use std::f64::consts::PI;
+
+/// Statevector of the qubits of an AQT circuit
+///
+/// Qubit `q` corresponds to bit `q` of the index of a basis state,
+/// matching the integer representation of measurement results returned by AQT devices.
+#[derive(Debug, Clone)]
+pub(crate) struct StateVector {
+    /// Number of simulated qubits
+    number_qubits: usize,
+    /// Amplitudes of all basis states
+    amplitudes: Vec<Complex64>,
+}
+
+impl StateVector {
+    /// Creates the statevector of all qubits in the zero state
+    pub(crate) fn new(number_qubits: usize) -> Self {
+        let mut amplitudes = vec![Complex64::new(0.0, 0.0); 1 << number_qubits];
+        amplitudes[0] = Complex64::new(1.0, 0.0);
+        Self {
+            number_qubits,
+            amplitudes,
+        }
+    }
+
+    /// Creates the statevector of a computational basis state
+    pub(crate) fn basis_state(number_qubits: usize, index: usize) -> Self {
+        let mut amplitudes = vec![Complex64::new(0.0, 0.0); 1 << number_qubits];
+        amplitudes[index] = Complex64::new(1.0, 0.0);
+        Self {
+            number_qubits,
+            amplitudes,
+        }
+    }
+
+    /// Returns the amplitudes of all basis states
+    pub(crate) fn amplitudes(&self) -> &[Complex64] {
+        &self.amplitudes
+    }
+
+    /// Applies an AQT instruction to the statevector, MEASURE instructions are ignored
+    pub(crate) fn apply_instruction(
+        &mut self,
+        instruction: &AqtInstruction,
+    ) -> Result<(), RoqoqoBackendError> {
+        match instruction {
+            AqtInstruction::RZ { phi, qubit } => {
+                let qubit = self.checked_qubit(*qubit)?;
+                let phase = Complex64::from_polar(1.0, phi * PI / 2.0);
+                self.apply_single_qubit_matrix(
+                    qubit,
+                    [
+                        [phase.conj(), Complex64::new(0.0, 0.0)],
+                        [Complex64::new(0.0, 0.0), phase],
+                    ],
+                );
+            }
+            AqtInstruction::R { phi, theta, qubit } => {
+                let qubit = self.checked_qubit(*qubit)?;
+                let cos = Complex64::new((theta * PI / 2.0).cos(), 0.0);
+                let minus_i_sin = Complex64::new(0.0, -(theta * PI / 2.0).sin());
+                let phase = Complex64::from_polar(1.0, phi * PI);
+                self.apply_single_qubit_matrix(
+                    qubit,
+                    [
+                        [cos, minus_i_sin * phase.conj()],
+                        [minus_i_sin * phase, cos],
+                    ],
+                );
+            }
+            AqtInstruction::RXX { qubits, theta } => {
+                let (first, second) = match qubits.as_slice() {
+                    [first, second] if first != second => {
+                        (self.checked_qubit(*first)?, self.checked_qubit(*second)?)
+                    }
+                    _ => {
+                        return Err(RoqoqoBackendError::GenericError {
+                            msg: format!(
+                                "RXX instruction requires two different qubits, found {qubits:?}"
+                            ),
+                        })
+                    }
+                };
+                self.apply_rxx(first, second, *theta);
+            }
+            AqtInstruction::MEASURE => (),
+        }
+        Ok(())
+    }
+
+    /// Applies the Pauli operator with index 1 (X), 2 (Y) or 3 (Z) to a qubit, other indices apply the identity
+    fn apply_pauli(&mut self, qubit: usize, pauli: usize) {
+        let zero = Complex64::new(0.0, 0.0);
+        let one = Complex64::new(1.0, 0.0);
+        let i = Complex64::new(0.0, 1.0);
+        match pauli {
+            1 => self.apply_single_qubit_matrix(qubit, [[zero, one], [one, zero]]),
+            2 => self.apply_single_qubit_matrix(qubit, [[zero, -i], [i, zero]]),
+            3 => self.apply_single_qubit_matrix(qubit, [[one, zero], [zero, -one]]),
+            _ => (),
+        }
+    }
+
+    /// Returns the probabilities of measuring each basis state
+    pub(crate) fn probabilities(&self) -> Vec<f64> {
+        self.amplitudes
+            .iter()
+            .map(|amplitude| amplitude.norm_sqr())
+            .collect()
+    }
+
+    /// Checks that the qubit of an instruction is part of the simulated qubits
+    fn checked_qubit(&self, qubit: u32) -> Result<usize, RoqoqoBackendError> {
+        let qubit = qubit as usize;
+        if qubit >= self.number_qubits {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Instruction acts on qubit {qubit} but only {} qubits are simulated",
+                    self.number_qubits
+                ),
+            });
+        }
+        Ok(qubit)
+    }
+
+    /// Applies a single qubit unitary given as a 2x2 matrix
+    fn apply_single_qubit_matrix(&mut self, qubit: usize, matrix: [[Complex64; 2]; 2]) {
+        let mask = 1 << qubit;
+        for index in 0..self.amplitudes.len() {
+            if index & mask == 0 {
+                let zero = self.amplitudes[index];
+                let one = self.amplitudes[index | mask];
+                self.amplitudes[index] = matrix[0][0] * zero + matrix[0][1] * one;
+                self.amplitudes[index | mask] = matrix[1][0] * zero + matrix[1][1] * one;
+            }
+        }
+    }
+
+    /// Applies exp(-i theta pi/2 X X) to two qubits
+    fn apply_rxx(&mut self, first: usize, second: usize, theta: f64) {
+        let cos = Complex64::new((theta * PI / 2.0).cos(), 0.0);
+        let minus_i_sin = Complex64::new(0.0, -(theta * PI / 2.0).sin());
+        let mask = (1 << first) | (1 << second);
+        for index in 0..self.amplitudes.len() {
+            let partner = index ^ mask;
+            if index < partner {
+                let amplitude = self.amplitudes[index];
+                let partner_amplitude = self.amplitudes[partner];
+                self.amplitudes[index] = cos * amplitude + minus_i_sin * partner_amplitude;
+                self.amplitudes[partner] = cos * partner_amplitude + minus_i_sin * amplitude;
+            }
+        }
+    }
+}
+
+/// Samples basis states from a probability distribution
+///
+/// # Arguments
+///
+/// `probabilities` - The probabilities of measuring each basis state
+/// `number_samples` - The number of sampled basis states
+/// `rng` - The random number generator used for sampling
+///
+/// # Returns
+///
+/// `Vec<u32>` - The indices of the sampled basis states
+pub(crate) fn sample_basis_states<R: Rng>(
+    probabilities: &[f64],
+    number_samples: usize,
+    rng: &mut R,
+) -> Vec<u32> {
+    let mut cumulative: Vec<f64> = Vec::with_capacity(probabilities.len());
+    let mut total = 0.0;
+    for probability in probabilities {
+        total += probability;
+        cumulative.push(total);
+    }
+    (0..number_samples)
+        .map(|_| {
+            let value = rng.gen::<f64>() * total;
+            cumulative
+                .partition_point(|bound| *bound <= value)
+                .min(probabilities.len() - 1) as u32
+        })
+        .collect()
+}
+
+/// Checks that the statevector of the qubits can be simulated
+///
+/// # Arguments
+///
+/// `number_qubits` - The number of qubits of the device
+pub(crate) fn check_number_qubits(number_qubits: usize) -> Result<(), RoqoqoBackendError> {
+    if number_qubits > MAX_SIMULATED_QUBITS {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Local simulation of {number_qubits} qubits exceeds the limit of {MAX_SIMULATED_QUBITS} qubits"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that no gate follows the measurement of a circuit, AQT devices only measure at the end of a circuit
+fn check_measurement_last(instructions: &[AqtInstruction]) -> Result<(), RoqoqoBackendError> {
+    if let Some(position) = instructions
+        .iter()
+        .position(|instruction| instruction == &AqtInstruction::MEASURE)
+    {
+        if let Some(instruction) = instructions[position..]
+            .iter()
+            .find(|instruction| *instruction != &AqtInstruction::MEASURE)
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Instruction {instruction:?} after the measurement can not be simulated, AQT devices measure at the end of a circuit"
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Simulates a translated AQT circuit and samples its measurement results
+///
+/// # Arguments
+///
+/// `number_qubits` - The number of qubits of the device
+/// `instructions` - The AQT instructions of the circuit
+/// `repetitions` - The number of times the circuit is measured
+/// `rng` - The random number generator used for sampling
+///
+/// # Returns
+///
+/// `Vec<Vec<u32>>` - The measured basis state of each repetition in the integer format returned by AQT devices,
+///                   empty when the circuit does not measure the qubits
+/// `RoqoqoBackendError::GenericError` - Error when the circuit has too many qubits or a gate follows the measurement
+pub(crate) fn run_instructions<R: Rng>(
+    number_qubits: usize,
+    instructions: &[AqtInstruction],
+    repetitions: usize,
+    rng: &mut R,
+) -> Result<Vec<Vec<u32>>, RoqoqoBackendError> {
+    check_number_qubits(number_qubits)?;
+    check_measurement_last(instructions)?;
+    let mut state = StateVector::new(number_qubits);
+    for instruction in instructions {
+        state.apply_instruction(instruction)?;
+    }
+    if !instructions.contains(&AqtInstruction::MEASURE) {
+        return Ok(Vec::new());
+    }
+    Ok(
+        sample_basis_states(&state.probabilities(), repetitions, rng)
+            .into_iter()
+            .map(|basis_state| vec![basis_state])
+            .collect(),
+    )
+}
+
+/// Simulates a translated AQT circuit with a trapped-ion noise model and samples its measurement results
+///
+/// Every repetition is simulated as a separate trajectory in which the errors of the noise model occur at random.
+///
+/// # Arguments
+///
+/// `number_qubits` - The number of qubits of the device
+/// `instructions` - The AQT instructions of the circuit
+/// `repetitions` - The number of times the circuit is measured
+/// `noise_model` - The noise model applied in each trajectory
+/// `rng` - The random number generator used for sampling errors and measurements
+///
+/// # Returns
+///
+/// `Vec<Vec<u32>>` - The measured basis state of each repetition in the integer format returned by AQT devices,
+///                   empty when the circuit does not measure the qubits
+/// `RoqoqoBackendError::GenericError` - Error when the circuit has too many qubits or a gate follows the measurement
+pub(crate) fn run_noisy_instructions<R: Rng>(
+    number_qubits: usize,
+    instructions: &[AqtInstruction],
+    repetitions: usize,
+    noise_model: &AqtNoiseModel,
+    rng: &mut R,
+) -> Result<Vec<Vec<u32>>, RoqoqoBackendError> {
+    check_number_qubits(number_qubits)?;
+    check_measurement_last(instructions)?;
+    if !instructions.contains(&AqtInstruction::MEASURE) {
+        return Ok(Vec::new());
+    }
+    let mut measured: Vec<Vec<u32>> = Vec::with_capacity(repetitions);
+    for _ in 0..repetitions {
+        let mut state = StateVector::new(number_qubits);
+        for qubit in 0..number_qubits {
+            if rng.gen::<f64>() < noise_model.preparation_error() {
+                state.apply_pauli(qubit, 1);
+            }
+        }
+        for instruction in instructions {
+            state.apply_instruction(instruction)?;
+            let active_qubits: Vec<usize> = match instruction {
+                AqtInstruction::R { qubit, .. } => {
+                    if rng.gen::<f64>() < noise_model.single_qubit_depolarising() {
+                        state.apply_pauli(*qubit as usize, rng.gen_range(1..4));
+                    }
+                    vec![*qubit as usize]
+                }
+                AqtInstruction::RZ { qubit, .. } => vec![*qubit as usize],
+                AqtInstruction::RXX { qubits, .. } => {
+                    if rng.gen::<f64>() < noise_model.ms_gate_infidelity() {
+                        // Random two-qubit Pauli operator excluding the identity
+                        let pauli: usize = rng.gen_range(1..16);
+                        for (position, qubit) in qubits.iter().enumerate() {
+                            state.apply_pauli(*qubit as usize, (pauli >> (2 * position)) % 4);
+                        }
+                    }
+                    qubits.iter().map(|qubit| *qubit as usize).collect()
+                }
+                AqtInstruction::MEASURE => continue,
+            };
+            for qubit in (0..number_qubits).filter(|qubit| !active_qubits.contains(qubit)) {
+                if rng.gen::<f64>() < noise_model.dephasing() {
+                    state.apply_pauli(qubit, 3);
+                }
+            }
+        }
+        let mut basis_state = sample_basis_states(&state.probabilities(), 1, rng)[0];
+        for qubit in 0..number_qubits {
+            if rng.gen::<f64>() < noise_model.measurement_error() {
+                basis_state ^= 1 << qubit;
+            }
+        }
+        measured.push(vec![basis_state]);
+    }
+    Ok(measured)
+}
@@ -10,11 +10,16 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use roqoqo::measurements::ClassicalRegister;
 use roqoqo::prelude::*;
 use roqoqo::registers::BitRegister;
 use roqoqo::{operations::*, Circuit};
-use roqoqo_aqt::Backend;
-use roqoqo_aqt::{devices::AqtDevice, AqtApi};
+use roqoqo_aqt::counts::{PackedBitOrder, SampleLayout};
+use roqoqo_aqt::{
+    devices::{AqtDevice, AqtNoiseModel, EmulatorDevice, LegacyAqtDevice, SimulatorDevice},
+    AqtApi,
+};
+use roqoqo_aqt::{AqtJobHandle, Backend, LegacyBackend};
 use roqoqo_test::prepare_monte_carlo_gate_test;
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -713,11 +718,1096 @@ async fn api_backend_mock_test() {
     server.reset().await;
 }
 
+// Test submitting, polling and waiting for a job with a mock device
+#[tokio::test]
+async fn api_backend_job_mock_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "dummy",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+    let aqt_run_response_ongoing = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "ongoing",
+      "finished_count": 0
+    }
+      });
+    let aqt_run_response_finished = json!({
+      "job": {
+        "job_id": "dummy_test_id",
+      },
+      "response": {
+        "status": "finished",
+        "result": {
+          "0": [[1], [1], [1]]
+        }
+      }
+    });
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_ongoing))
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 1,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += DefinitionFloat::new("fl".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    let backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+
+    let backend_cloned = backend.clone();
+    let job = spawn_blocking(move || backend_cloned.submit_circuit(&circuit))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.job_id(), "dummy_test_id");
+    assert_eq!(job.readout(), "ro");
+    assert_eq!(job.readouts(), &["ro".to_string()]);
+
+    let backend_cloned = backend.clone();
+    let job_cloned = job.clone();
+    let res = spawn_blocking(move || backend_cloned.poll_job(&job_cloned))
+        .await
+        .unwrap();
+    assert_eq!(res, Ok(None));
+
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+
+    let backend_cloned = backend.clone();
+    let job_cloned = job.clone();
+    let res = spawn_blocking(move || backend_cloned.wait_for_job(&job_cloned))
+        .await
+        .unwrap();
+    let (bit_registers, float_registers, complex_registers) = res.unwrap();
+    let mut expected_br = HashMap::<String, Vec<BitRegister>>::new();
+    expected_br.insert("ro".to_string(), vec![vec![true], vec![true], vec![true]]);
+    assert_eq!(bit_registers, expected_br);
+    assert_eq!(float_registers.get("fl"), Some(&Vec::new()));
+    assert!(complex_registers.is_empty());
+
+    server.verify().await;
+}
+
+// Test the metadata of a job returned together with the registers
+#[tokio::test]
+async fn api_backend_metadata_mock_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "dummy",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+    let aqt_run_response_finished = json!({
+      "job": {
+        "job_id": "dummy_test_id",
+        "job_type": "quantum_circuit",
+        "label": "server_label",
+        "resource_id": "dummy",
+        "workspace_id": "server_workspace"
+      },
+      "response": {
+        "status": "finished",
+        "result": {
+          "0": [[1], [1], [1]]
+        }
+      }
+    });
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 1,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    let mut backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(std::time::Duration::from_millis(10));
+
+    let backend_cloned = backend.clone();
+    let (job, result) = spawn_blocking(move || {
+        let job = backend_cloned.submit_circuit(&circuit)?;
+        let result = backend_cloned.wait_for_job_with_metadata(&job)?;
+        Ok::<_, RoqoqoBackendError>((job, result))
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    let submitted = job.metadata();
+    assert_eq!(submitted.job_id, "dummy_test_id");
+    assert_eq!(submitted.resource_id, "dummy");
+    assert_eq!(submitted.workspace_id, "qoqo-integration");
+    assert_eq!(submitted.label, "qoqo_aqt_backend");
+    assert!(submitted.submitted.is_some());
+    assert_eq!(submitted.finished, None);
+
+    assert_eq!(result.registers.0.get("ro"), Some(&vec![vec![true]; 3]));
+    let metadata = result.metadata;
+    assert_eq!(metadata.job_id, "dummy_test_id");
+    assert_eq!(metadata.resource_id, "dummy");
+    assert_eq!(metadata.workspace_id, "server_workspace");
+    assert_eq!(metadata.label, "server_label");
+    assert_eq!(metadata.submitted, submitted.submitted);
+    assert!(metadata.finished >= metadata.submitted);
+}
+
+// Test decoding the sample layouts and bit orderings returned by AQT devices
+#[tokio::test]
+async fn api_backend_sample_layout_mock_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "dummy",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+    let finished = |result: Value| {
+        json!({
+          "job": {
+            "job_id": "dummy_test_id",
+          },
+          "response": {
+            "status": "finished",
+            "result": {
+              "0": result
+            }
+          }
+        })
+    };
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 3,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 2, None);
+    let mut backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(std::time::Duration::from_millis(10));
+    assert_eq!(
+        backend.packed_bit_order(),
+        PackedBitOrder::QubitZeroLeastSignificant
+    );
+    assert_eq!(backend.sample_layout(), SampleLayout::Auto);
+
+    let backend_cloned = backend.clone();
+    let job = spawn_blocking(move || backend_cloned.submit_circuit(&circuit))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Qubit 0 measured in 1 in every shot
+    let cases = [
+        (
+            json!([[1], [1]]),
+            PackedBitOrder::QubitZeroLeastSignificant,
+            SampleLayout::Auto,
+        ),
+        (
+            json!([[1, 0, 0], [1, 0, 0]]),
+            PackedBitOrder::QubitZeroLeastSignificant,
+            SampleLayout::Auto,
+        ),
+        (
+            json!([[1, 0], [1, 0]]),
+            PackedBitOrder::QubitZeroLeastSignificant,
+            SampleLayout::PerQubit,
+        ),
+        (
+            json!([[4], [4]]),
+            PackedBitOrder::QubitZeroMostSignificant,
+            SampleLayout::Packed,
+        ),
+        (
+            json!([[1, 0, 0], [1, 0, 0]]),
+            PackedBitOrder::QubitZeroMostSignificant,
+            SampleLayout::Auto,
+        ),
+    ];
+    for (result, packed_bit_order, sample_layout) in cases {
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/mock/result/dummy_test_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(finished(result)))
+            .mount(&server)
+            .await;
+        backend.set_packed_bit_order(packed_bit_order);
+        backend.set_sample_layout(sample_layout);
+        let backend_cloned = backend.clone();
+        let job_cloned = job.clone();
+        let (bit_registers, _, _) = spawn_blocking(move || backend_cloned.poll_job(&job_cloned))
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            bit_registers.get("ro"),
+            Some(&vec![vec![true, false, false]; 2])
+        );
+        let backend_cloned = backend.clone();
+        let job_cloned = job.clone();
+        let counts = spawn_blocking(move || backend_cloned.wait_for_job_counts(&job_cloned))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(counts["ro"].samples, vec![1, 1]);
+    }
+
+    // Shots not matching the layout or the number of qubits are rejected
+    let cases = [
+        (json!([[1, 0, 0]]), SampleLayout::Packed),
+        (json!([[1, 0, 0, 0]]), SampleLayout::Auto),
+        (json!([[2, 0, 0]]), SampleLayout::PerQubit),
+        (json!([[8]]), SampleLayout::Auto),
+        (json!([[]]), SampleLayout::Auto),
+    ];
+    for (result, sample_layout) in cases {
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/mock/result/dummy_test_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(finished(result)))
+            .mount(&server)
+            .await;
+        backend.set_sample_layout(sample_layout);
+        let backend_cloned = backend.clone();
+        let job_cloned = job.clone();
+        let res = spawn_blocking(move || backend_cloned.poll_job(&job_cloned))
+            .await
+            .unwrap();
+        assert!(res.is_err());
+    }
+}
+
+// Test submitting several circuits in one job with a mock device
+#[tokio::test]
+async fn api_backend_multiple_circuits_mock_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "dummy",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+    let aqt_run_response_finished = json!({
+      "job": {
+        "job_id": "dummy_test_id",
+      },
+      "response": {
+        "status": "finished",
+        "result": {
+          "0": [[1], [1]],
+          "1": [[0], [0], [0]]
+        }
+      }
+    });
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 1,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let mut first_circuit = Circuit::new();
+    first_circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    first_circuit += PauliX::new(0);
+    first_circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 2, None);
+    let mut second_circuit = Circuit::new();
+    second_circuit += DefinitionBit::new("ro_second".to_string(), 1, true);
+    second_circuit += PragmaRepeatedMeasurement::new("ro_second".to_string(), 3, None);
+    let backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+
+    let backend_cloned = backend.clone();
+    let job =
+        spawn_blocking(move || backend_cloned.submit_circuits(&[first_circuit, second_circuit]))
+            .await
+            .unwrap()
+            .unwrap();
+    assert_eq!(job.readouts(), &["ro".to_string(), "ro_second".to_string()]);
+
+    let backend_cloned = backend.clone();
+    let res = spawn_blocking(move || backend_cloned.wait_for_job(&job))
+        .await
+        .unwrap();
+    let (bit_registers, _, _) = res.unwrap();
+    let mut expected_br = HashMap::<String, Vec<BitRegister>>::new();
+    expected_br.insert("ro".to_string(), vec![vec![true], vec![true]]);
+    expected_br.insert(
+        "ro_second".to_string(),
+        vec![vec![false], vec![false], vec![false]],
+    );
+    assert_eq!(bit_registers, expected_br);
+
+    server.verify().await;
+}
+
+// Test running all circuits of a measurement in one job with a mock device
+#[tokio::test]
+async fn api_backend_measurement_mock_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "dummy",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+    let aqt_run_response_finished = json!({
+      "job": {
+        "job_id": "dummy_test_id",
+      },
+      "response": {
+        "status": "finished",
+        "result": {
+          "0": [[1], [1]],
+          "1": [[0], [1]]
+        }
+      }
+    });
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 1,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let mut constant_circuit = Circuit::new();
+    constant_circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    constant_circuit += PauliX::new(0);
+    let mut first_circuit = Circuit::new();
+    first_circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 2, None);
+    let mut second_circuit = Circuit::new();
+    second_circuit += RotateX::new(0, std::f64::consts::FRAC_PI_2.into());
+    second_circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 2, None);
+    let measurement = ClassicalRegister {
+        constant_circuit: Some(constant_circuit),
+        circuits: vec![first_circuit, second_circuit],
+    };
+    let backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+
+    let res = spawn_blocking(move || backend.run_measurement_registers(&measurement))
+        .await
+        .unwrap();
+    let (bit_registers, _, _) = res.unwrap();
+    let mut expected_br = HashMap::<String, Vec<BitRegister>>::new();
+    expected_br.insert(
+        "ro".to_string(),
+        vec![vec![true], vec![true], vec![false], vec![true]],
+    );
+    assert_eq!(bit_registers, expected_br);
+
+    server.verify().await;
+}
+
+// Test emulating PragmaGetPauliProduct and PragmaGetOccupationProbability with a mock device
+#[tokio::test]
+async fn api_backend_pragma_emulation_mock_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "dummy",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_expect_post_body = json!({
+        "job_type": "quantum_circuit",
+        "label": "qoqo_aqt_backend",
+        "payload": {
+            "circuits": [
+            {
+                "number_of_qubits": 2,
+                "quantum_circuit": [
+                {
+                    "operation": "R",
+                    "phi": 0.0,
+                    "qubit": 0,
+                    "theta": 1.0,
+                },
+                // RotateY(-pi/2) with theta in the range [0, 1] accepted by AQT devices
+                {
+                    "operation": "R",
+                    "phi": 1.5,
+                    "qubit": 1,
+                    "theta": 0.5,
+                },
+                {
+                    "operation": "MEASURE"
+                }
+                ],
+                "repetitions": 4
+            },
+            {
+                "number_of_qubits": 2,
+                "quantum_circuit": [
+                {
+                    "operation": "R",
+                    "phi": 0.0,
+                    "qubit": 0,
+                    "theta": 1.0,
+                },
+                {
+                    "operation": "MEASURE"
+                }
+                ],
+                "repetitions": 4
+            }
+            ]
+        }
+        }
+    );
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+    let aqt_run_response_finished = json!({
+      "job": {
+        "job_id": "dummy_test_id",
+      },
+      "response": {
+        "status": "finished",
+        "result": {
+          "0": [[1], [3], [1], [3]],
+          "1": [[1], [1], [1], [1]]
+        }
+      }
+    });
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/dummy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/dummy"))
+        .and(body_json(&aqt_expect_post_body))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 2,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("pauli_product".to_string(), 1, true);
+    circuit += DefinitionFloat::new("occupation".to_string(), 2, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaGetPauliProduct::new(
+        HashMap::from([(0, 3), (1, 1)]),
+        "pauli_product".to_string(),
+        Circuit::new(),
+    );
+    circuit += PragmaGetOccupationProbability::new("occupation".to_string(), None);
+    let mut backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+    assert!(backend.run_circuit(&circuit).is_err());
+
+    backend.set_pragma_emulation_shots(Some(4));
+    assert_eq!(backend.pragma_emulation_shots(), Some(4));
+    let res = spawn_blocking(move || backend.run_circuit(&circuit))
+        .await
+        .unwrap();
+    let (bit_registers, float_registers, _) = res.unwrap();
+    assert!(bit_registers.is_empty());
+    assert_eq!(float_registers.get("pauli_product"), Some(&vec![vec![0.0]]));
+    assert_eq!(
+        float_registers.get("pauli_product_error"),
+        Some(&vec![vec![0.5]])
+    );
+    assert_eq!(
+        float_registers.get("occupation"),
+        Some(&vec![vec![1.0, 0.0]])
+    );
+    assert_eq!(
+        float_registers.get("occupation_error"),
+        Some(&vec![vec![0.0, 0.0]])
+    );
+
+    server.verify().await;
+}
+
+// Test cancelling a job and interrupting the wait for a job with a mock device
+#[tokio::test]
+async fn api_backend_cancel_mock_test() {
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/mock/jobs/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 1,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+    let job: AqtJobHandle = serde_json::from_value(json!({
+        "job_id": "dummy_test_id",
+        "readout": "ro",
+        "bit_registers": ["ro"],
+        "float_registers": [],
+        "complex_registers": []
+    }))
+    .unwrap();
+
+    let backend_cloned = backend.clone();
+    let job_cloned = job.clone();
+    let res = spawn_blocking(move || {
+        let mut calls = 0;
+        backend_cloned.wait_for_job_interruptible(&job_cloned, || {
+            calls += 1;
+            if calls < 3 {
+                Ok(())
+            } else {
+                Err(RoqoqoBackendError::GenericError {
+                    msg: "interrupted".to_string(),
+                })
+            }
+        })
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        res,
+        Err(RoqoqoBackendError::GenericError {
+            msg: "interrupted".to_string()
+        })
+    );
+
+    let backend_cloned = backend.clone();
+    let job_cloned = job.clone();
+    let res = spawn_blocking(move || backend_cloned.cancel_job(&job_cloned))
+        .await
+        .unwrap();
+    assert!(res.is_ok());
+
+    server.verify().await;
+    server.reset().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/mock/jobs/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let res = spawn_blocking(move || backend.cancel_job(&job))
+        .await
+        .unwrap();
+    let expected_error = RoqoqoBackendError::NetworkError {
+        msg: "Failed to cancel job on server. Request to server failed with HTTP status code 404"
+            .to_string(),
+    };
+    assert_eq!(res, Err(expected_error));
+}
+
+// Test that the timeout error reports the total time waited for a job with a mock device
+#[tokio::test]
+async fn api_backend_timeout_mock_test() {
+    let aqt_run_response_queued = json!({"job": {
+          "job_id": "dummy_test_id",
+      },
+      "response": {
+      "status": "queued"
+    }
+      });
+
+    let server = MockServer::start().await;
+    let uri = server.uri();
+
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .expect(100)
+        .mount(&server)
+        .await;
+
+    let mock_device = MockAqtDevice {
+        number_qubits: 1,
+        mock_host: format!("{uri}/mock/"),
+    };
+    let mut backend = Backend::new(mock_device, Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(std::time::Duration::from_millis(5));
+    let job: AqtJobHandle = serde_json::from_value(json!({
+        "job_id": "dummy_test_id",
+        "readouts": ["ro"],
+        "bit_registers": ["ro"],
+        "float_registers": [],
+        "complex_registers": []
+    }))
+    .unwrap();
+
+    let res = spawn_blocking(move || backend.wait_for_job(&job))
+        .await
+        .unwrap();
+    match res {
+        Err(RoqoqoBackendError::Timeout { msg }) => {
+            assert!(msg.starts_with("AQT backend timed out waiting for job dummy_test_id after "));
+            assert!(msg.ends_with("s (100 queries every 0.005s)"));
+            // 100 queries every 5ms take at least 0.5s
+            let elapsed: f64 = msg
+                .split(" after ")
+                .nth(1)
+                .and_then(|rest| rest.split('s').next())
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!(elapsed >= 0.5);
+        }
+        _ => panic!("Expected a timeout, got {res:?}"),
+    }
+
+    server.verify().await;
+}
+
+// Test running circuits with the local simulator device
+// Test that the translation report lists all operations that cannot be translated
+#[test]
+fn translation_report_test() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += CNOT::new(0, 1);
+    circuit += RotateX::new(0, "theta".into());
+    circuit += PauliX::new(0);
+    circuit += CNOT::new(1, 0);
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += PragmaGlobalPhase::new(1.0.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let backend = Backend::new(AqtDevice::new(2), Some("DummyToken".to_string())).unwrap();
+    let report = backend.translation_report(&circuit);
+
+    assert!(!report.is_translatable());
+    assert_eq!(report.number_operations, 8);
+    let unsupported: Vec<(usize, &str)> = report
+        .unsupported_operations
+        .iter()
+        .map(|op| (op.position, op.hqslang.as_str()))
+        .collect();
+    assert_eq!(unsupported, vec![(1, "CNOT"), (4, "CNOT")]);
+    assert_eq!(
+        report.unsupported_counts,
+        HashMap::from([("CNOT".to_string(), 2)])
+    );
+    assert_eq!(report.symbolic_parameters.len(), 1);
+    assert_eq!(report.symbolic_parameters[0].position, 2);
+    assert_eq!(report.symbolic_parameters[0].hqslang, "RotateX");
+    assert_eq!(
+        report.ignored_operations,
+        HashMap::from([
+            ("DefinitionBit".to_string(), 1),
+            ("PragmaGlobalPhase".to_string(), 1)
+        ])
+    );
+    assert_eq!(
+        report.native_gate_counts,
+        HashMap::from([
+            ("R".to_string(), 1),
+            ("RXX".to_string(), 1),
+            ("MEASURE".to_string(), 1)
+        ])
+    );
+    assert!(report.payload_size > 0);
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("zz".to_string(), 1, true);
+    circuit +=
+        PragmaGetPauliProduct::new(HashMap::from([(0, 3)]), "zz".to_string(), Circuit::new());
+    let report = backend.translation_report(&circuit);
+    assert_eq!(
+        report.unsupported_counts.get("PragmaGetPauliProduct"),
+        Some(&1)
+    );
+    let mut backend = backend;
+    backend.set_pragma_emulation_shots(Some(100));
+    let report = backend.translation_report(&circuit);
+    assert!(report.is_translatable());
+    assert_eq!(report.native_gate_counts.get("MEASURE"), Some(&1));
+}
+
+#[test]
+fn simulator_backend_test() {
+    let device = SimulatorDevice::new(3, Some(11));
+    let backend = Backend::new(device, None).unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += PauliX::new(0);
+    circuit += RotateX::new(2, std::f64::consts::FRAC_PI_2.into());
+    circuit += RotateX::new(2, std::f64::consts::FRAC_PI_2.into());
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let (bit_registers, float_registers, complex_registers) =
+        backend.run_circuit(&circuit).unwrap();
+    assert_eq!(
+        bit_registers.get("ro"),
+        Some(&vec![vec![false, true, true]; 10])
+    );
+    assert!(float_registers.is_empty());
+    assert!(complex_registers.is_empty());
+
+    let job = backend.submit_circuit(&circuit).unwrap();
+    assert!(job.job_id().starts_with("local_"));
+    assert!(backend.poll_job(&job).unwrap().is_some());
+    assert!(backend.cancel_job(&job).is_ok());
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += RotateY::new(0, std::f64::consts::FRAC_PI_2.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 1000, None);
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    let measured = bit_registers.get("ro").unwrap();
+    assert_eq!(measured.len(), 1000);
+    let number_ones = measured.iter().filter(|shot| shot[0]).count();
+    assert!((400..600).contains(&number_ones));
+    assert!(measured.iter().all(|shot| !shot[1] && !shot[2]));
+    // The same seed reproduces the same results
+    let (bit_registers_repeated, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bit_registers, bit_registers_repeated);
+
+    let mut backend = backend;
+    backend.set_pragma_emulation_shots(Some(100));
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("pauli_product".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaGetPauliProduct::new(
+        HashMap::from([(0, 3), (1, 3)]),
+        "pauli_product".to_string(),
+        Circuit::new(),
+    );
+    let (_, float_registers, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(
+        float_registers.get("pauli_product"),
+        Some(&vec![vec![-1.0]])
+    );
+    assert_eq!(
+        float_registers.get("pauli_product_error"),
+        Some(&vec![vec![0.0]])
+    );
+
+    // Local jobs have finished when they are submitted
+    let result = backend.run_circuit_with_metadata(&circuit).unwrap();
+    assert_eq!(result.registers.1, float_registers);
+    assert!(result.metadata.job_id.starts_with("local_"));
+    assert_eq!(result.metadata.resource_id, "local_simulator");
+    assert_eq!(result.metadata.workspace_id, "");
+    assert_eq!(result.metadata.finished, result.metadata.submitted);
+
+    // Qubits of emulated pragmas that are not measured are not read as zero
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("pauli_product".to_string(), 1, true);
+    circuit += PragmaGetPauliProduct::new(
+        HashMap::from([(0, 3), (5, 3)]),
+        "pauli_product".to_string(),
+        Circuit::new(),
+    );
+    assert!(matches!(
+        backend.run_circuit(&circuit),
+        Err(RoqoqoBackendError::GenericError { .. })
+    ));
+
+    // Loops in the circuits of emulated pragmas are limited by the instructions remaining for the device
+    let mut loop_body = Circuit::new();
+    loop_body += PauliX::new(0);
+    let mut pragma_circuit = Circuit::new();
+    pragma_circuit += PragmaLoop::new(20.0.into(), loop_body.clone());
+    let mut circuit = Circuit::new();
+    circuit += DefinitionFloat::new("occupation".to_string(), 3, true);
+    circuit += PragmaLoop::new(1990.0.into(), loop_body);
+    circuit += PragmaGetOccupationProbability::new("occupation".to_string(), Some(pragma_circuit));
+    match backend.convert_circuit_to_aqt_instructions(circuit.iter()) {
+        Err(RoqoqoBackendError::GenericError { msg }) => {
+            assert!(msg.starts_with("Unrolling PragmaLoop"))
+        }
+        _ => panic!("Expected the loop in the pragma circuit to exceed the instruction limit"),
+    }
+}
+
+// Test configuring the label of submitted jobs
+#[test]
+fn job_label_test() {
+    let device = SimulatorDevice::new(1, Some(11));
+    let mut backend = Backend::new(device, None).unwrap();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 1, None);
+
+    assert_eq!(backend.job_label(), "qoqo_aqt_backend");
+    let job = backend.submit_circuit(&circuit).unwrap();
+    assert_eq!(job.metadata().label, "qoqo_aqt_backend");
+
+    backend.set_job_label("{tag}/{name}@{resource_id}").unwrap();
+    backend.set_job_tag("experiment");
+    assert_eq!(backend.job_label(), "{tag}/{name}@{resource_id}");
+    assert_eq!(backend.job_tag(), "experiment");
+    let (aqt_run_data, _, _) = backend
+        .convert_circuit_to_aqt_instructions(circuit.iter())
+        .unwrap();
+    assert_eq!(aqt_run_data.label(), "experiment/circuit@local_simulator");
+    let job = backend.submit_circuit_with_name(&circuit, "bell").unwrap();
+    assert_eq!(job.metadata().label, "experiment/bell@local_simulator");
+    let job = backend
+        .submit_circuits_with_name(&[circuit.clone(), circuit.clone()], "sweep")
+        .unwrap();
+    assert_eq!(job.metadata().label, "experiment/sweep@local_simulator");
+    let measurement = ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![circuit.clone()],
+    };
+    let job = backend.submit_measurement(&measurement).unwrap();
+    assert_eq!(
+        job.metadata().label,
+        "experiment/measurement@local_simulator"
+    );
+    let job = backend
+        .submit_measurement_with_name(&measurement, "scan")
+        .unwrap();
+    assert_eq!(job.metadata().label, "experiment/scan@local_simulator");
+
+    for template in ["{unknown}", "{name", "name}"] {
+        assert!(backend.set_job_label(template).is_err());
+    }
+    assert_eq!(backend.job_label(), "{tag}/{name}@{resource_id}");
+}
+
+// Test running circuits with the noisy local emulator device
+#[test]
+fn emulator_backend_test() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 1000, None);
+
+    // Without noise the emulator agrees with the ideal simulator
+    let device = EmulatorDevice::new(2, AqtNoiseModel::default(), Some(3));
+    let backend = Backend::new(device, None).unwrap();
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(
+        bit_registers.get("ro"),
+        Some(&vec![vec![true, false]; 1000])
+    );
+
+    // Every measured value is flipped
+    let device = EmulatorDevice::new(
+        2,
+        AqtNoiseModel::new(0.0, 0.0, 0.0, 0.0, 1.0).unwrap(),
+        Some(3),
+    );
+    let backend = Backend::new(device, None).unwrap();
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(
+        bit_registers.get("ro"),
+        Some(&vec![vec![false, true]; 1000])
+    );
+
+    // Every qubit is prepared in the one state
+    let device = EmulatorDevice::new(
+        2,
+        AqtNoiseModel::new(0.0, 0.0, 0.0, 1.0, 0.0).unwrap(),
+        Some(3),
+    );
+    let backend = Backend::new(device, None).unwrap();
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(
+        bit_registers.get("ro"),
+        Some(&vec![vec![false, true]; 1000])
+    );
+
+    // Two of the three Pauli errors flip the qubit
+    let device = EmulatorDevice::new(
+        2,
+        AqtNoiseModel::new(0.3, 0.0, 0.0, 0.0, 0.0).unwrap(),
+        Some(3),
+    );
+    let backend = Backend::new(device, None).unwrap();
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    let measured = bit_registers.get("ro").unwrap();
+    let number_flipped = measured.iter().filter(|shot| !shot[0]).count();
+    assert!((150..250).contains(&number_flipped));
+    assert!(measured.iter().all(|shot| !shot[1]));
+
+    // A Z error on the idle second qubit during the PauliX gate turns the final rotation into a flip
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateY::new(1, std::f64::consts::FRAC_PI_2.into());
+    circuit += PauliX::new(0);
+    circuit += RotateY::new(1, (-std::f64::consts::FRAC_PI_2).into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 1000, None);
+    let device = EmulatorDevice::new(
+        2,
+        AqtNoiseModel::new(0.0, 0.0, 1.0, 0.0, 0.0).unwrap(),
+        Some(3),
+    );
+    let backend = Backend::new(device, None).unwrap();
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert!(bit_registers
+        .get("ro")
+        .unwrap()
+        .iter()
+        .all(|shot| shot[0] && shot[1]));
+}
+
 // Test backend run on AQT simulator with small circuit
 #[test]
 #[ignore = "API key problems, temporarily disabled"]
 fn api_backend_test_small() {
-    let device = AqtDevice { number_qubits: 1 };
+    let device = AqtDevice::new(1);
     let backend = Backend::new(device, None).unwrap();
 
     let mut circuit = Circuit::new();
@@ -739,7 +1829,7 @@ fn api_backend_test_small() {
 #[test]
 #[ignore = "API key problems, temporarily disabled"]
 fn api_backend_test_small_two() {
-    let device = AqtDevice { number_qubits: 1 };
+    let device = AqtDevice::new(1);
     let backend = Backend::new(device, None).unwrap();
 
     let mut circuit = Circuit::new();
@@ -787,3 +1877,30 @@ fn test_full_simple_gate() {
         }
     }
 }
+
+// Test that data serialized with version 0.8.0 can be deserialized
+#[test]
+fn test_legacy_serialization() {
+    let legacy_device = LegacyAqtDevice { number_qubits: 3 };
+    let legacy_backend = LegacyBackend {
+        device: legacy_device.clone(),
+        access_token: "dummy".to_string(),
+    };
+    let backend = Backend::new(AqtDevice::new(3), Some("dummy".to_string())).unwrap();
+
+    // The bincode layout changed, the old layout is only read with the legacy types
+    let device_bytes = bincode::serialize(&legacy_device).unwrap();
+    assert!(bincode::deserialize::<AqtDevice>(&device_bytes).is_err());
+    let device: LegacyAqtDevice = bincode::deserialize(&device_bytes).unwrap();
+    assert_eq!(AqtDevice::from(device), AqtDevice::new(3));
+
+    let backend_bytes = bincode::serialize(&legacy_backend).unwrap();
+    assert!(bincode::deserialize::<Backend<AqtDevice>>(&backend_bytes).is_err());
+    let deserialized: LegacyBackend = bincode::deserialize(&backend_bytes).unwrap();
+    assert_eq!(Backend::<AqtDevice>::from(deserialized), backend);
+
+    // Missing fields take their default values in json
+    let backend_json = serde_json::to_string(&legacy_backend).unwrap();
+    let deserialized: Backend<AqtDevice> = serde_json::from_str(&backend_json).unwrap();
+    assert_eq!(deserialized, backend);
+}
@@ -0,0 +1,171 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::registers::BitRegister;
+use roqoqo::{operations::*, Circuit, RoqoqoBackendError};
+use roqoqo_aqt::cassette::{Cassette, CassetteMode};
+use roqoqo_aqt::devices::AqtDevice;
+use roqoqo_aqt::Backend;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::task::spawn_blocking;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    circuit
+}
+
+// Test recording a job to a cassette and replaying it without the server
+#[tokio::test]
+async fn record_and_replay_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "simulator_noise",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({
+      "job": {"job_id": "dummy_test_id"},
+      "response": {"status": "queued"}
+    });
+    let aqt_run_response_finished = json!({
+      "job": {"job_id": "dummy_test_id"},
+      "response": {
+        "status": "finished",
+        "result": {"0": [[1], [1], [1]]}
+      }
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+
+    let directory =
+        std::env::temp_dir().join(format!("roqoqo_aqt_cassette_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let cassette_path = directory.join("cassette.json");
+
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("SecretAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    backend.record_cassette(&cassette_path);
+    assert_eq!(backend.cassette_mode(), Some(CassetteMode::Record));
+
+    let backend_cloned = backend.clone();
+    let recorded = spawn_blocking(move || {
+        let job = backend_cloned.submit_circuit(&test_circuit())?;
+        backend_cloned.wait_for_job(&job)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    let mut expected_br = HashMap::<String, Vec<BitRegister>>::new();
+    expected_br.insert("ro".to_string(), vec![vec![true], vec![true], vec![true]]);
+    assert_eq!(recorded.0, expected_br);
+
+    let content = std::fs::read_to_string(&cassette_path).unwrap();
+    assert!(!content.contains("SecretAccessToken"));
+    let cassette = Cassette::from_file(&cassette_path).unwrap();
+    let requests: Vec<(&str, &str)> = cassette
+        .interactions
+        .iter()
+        .map(|interaction| (interaction.method.as_str(), interaction.path.as_str()))
+        .collect();
+    assert_eq!(
+        requests,
+        vec![
+            ("GET", "resources/simulator_noise"),
+            ("POST", "submit/qoqo-integration/simulator_noise"),
+            ("GET", "result/dummy_test_id"),
+            ("GET", "result/dummy_test_id"),
+        ]
+    );
+    assert!(cassette.interactions[1].request_body.is_some());
+
+    // Replaying does not contact the server, which only expects the recorded requests
+    let device = AqtDevice::new(1).set_remote_host(Some("http://127.0.0.1:1/".to_string()));
+    let mut backend = Backend::new(device, Some("OtherAccessToken".to_string())).unwrap();
+    backend.replay_cassette(&cassette_path).unwrap();
+    assert_eq!(backend.cassette_mode(), Some(CassetteMode::Replay));
+
+    // A request with a different body than the recorded request is rejected
+    let mut other_circuit = test_circuit();
+    other_circuit += PauliX::new(0);
+    let backend_cloned = backend.clone();
+    let result = spawn_blocking(move || backend_cloned.submit_circuit(&other_circuit))
+        .await
+        .unwrap();
+    match result {
+        Err(RoqoqoBackendError::GenericError { msg }) => {
+            assert!(msg.contains("does not match the recorded request"))
+        }
+        _ => panic!("Request with a different body was replayed"),
+    }
+    // The resource check of the rejected submission has been replayed, the replay is restarted
+    backend.replay_cassette(&cassette_path).unwrap();
+
+    let backend_cloned = backend.clone();
+    let replayed = spawn_blocking(move || {
+        let job = backend_cloned.submit_circuit(&test_circuit())?;
+        backend_cloned.wait_for_job(&job)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(replayed, recorded);
+
+    // All recorded interactions have been used up
+    let backend_cloned = backend.clone();
+    let result = spawn_blocking(move || backend_cloned.submit_circuit(&test_circuit()))
+        .await
+        .unwrap();
+    assert!(result.is_err());
+
+    backend.stop_cassette();
+    assert_eq!(backend.cassette_mode(), None);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+// Test that replaying a missing cassette returns an error
+#[test]
+fn replay_missing_cassette_test() {
+    let mut backend = Backend::new(AqtDevice::new(1), Some("DummyToken".to_string())).unwrap();
+    let result = backend.replay_cassette(std::path::Path::new("does_not_exist.json"));
+    assert!(result.is_err());
+    assert_eq!(backend.cassette_mode(), None);
+}
@@ -0,0 +1,145 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::backends::EvaluatingBackend;
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::counts::{pack_samples, AqtCounts, BitOrdering, PackedBitOrder, SampleLayout};
+use roqoqo_aqt::devices::SimulatorDevice;
+use roqoqo_aqt::Backend;
+use std::collections::HashMap;
+
+#[test]
+fn test_counts() {
+    // Qubit 0 and qubit 2 measured as 1 in four shots, qubit 1 measured as 1 in one shot
+    let counts = AqtCounts::new(3, vec![5, 5, 2, 5, 5]);
+    assert_eq!(counts.number_shots(), 5);
+    assert_eq!(counts.bitstring(1, BitOrdering::QubitZeroFirst), "100");
+    assert_eq!(counts.bitstring(1, BitOrdering::QubitZeroLast), "001");
+
+    let first = counts.counts(BitOrdering::QubitZeroFirst);
+    assert_eq!(
+        first,
+        HashMap::from([("101".to_string(), 4), ("010".to_string(), 1)])
+    );
+    let integers_last = counts.integer_counts(BitOrdering::QubitZeroLast);
+    assert_eq!(integers_last, HashMap::from([(5, 4), (2, 1)]));
+
+    let asymmetric = AqtCounts::new(3, vec![1, 6]);
+    assert_eq!(
+        asymmetric.counts(BitOrdering::QubitZeroLast),
+        HashMap::from([("001".to_string(), 1), ("110".to_string(), 1)])
+    );
+    assert_eq!(
+        asymmetric.integer_counts(BitOrdering::QubitZeroFirst),
+        HashMap::from([(4, 1), (3, 1)])
+    );
+    assert_eq!(
+        asymmetric.to_bit_register(),
+        vec![vec![true, false, false], vec![false, true, true]]
+    );
+}
+
+#[test]
+fn test_run_circuit_counts() {
+    let device = SimulatorDevice::new(3, Some(11));
+    let backend = Backend::new(device, None).unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+
+    let counts = backend.run_circuit_counts(&circuit).unwrap();
+    let ro = counts.get("ro").unwrap();
+    assert_eq!(ro.number_qubits, 3);
+    assert_eq!(ro.samples, vec![1; 10]);
+    assert_eq!(
+        ro.counts(BitOrdering::QubitZeroFirst),
+        HashMap::from([("100".to_string(), 10)])
+    );
+
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(bit_registers.get("ro").unwrap(), &ro.to_bit_register());
+}
+
+#[test]
+fn test_pack_samples() {
+    let packed = vec![vec![1], vec![6]];
+    let per_qubit = vec![vec![1, 0, 0], vec![0, 1, 1]];
+    for (measured, layout) in [
+        (&packed, SampleLayout::Packed),
+        (&packed, SampleLayout::Auto),
+        (&per_qubit, SampleLayout::PerQubit),
+        (&per_qubit, SampleLayout::Auto),
+    ] {
+        let samples = pack_samples(
+            measured,
+            3,
+            layout,
+            PackedBitOrder::QubitZeroLeastSignificant,
+        )
+        .unwrap();
+        assert_eq!(samples, vec![1, 6]);
+    }
+    // Qubit 0 in the most significant bit
+    let samples = pack_samples(
+        &packed,
+        3,
+        SampleLayout::Packed,
+        PackedBitOrder::QubitZeroMostSignificant,
+    );
+    assert_eq!(samples.unwrap(), vec![4, 3]);
+    // Lists of bits always start with qubit 0
+    let samples = pack_samples(
+        &per_qubit,
+        3,
+        SampleLayout::Auto,
+        PackedBitOrder::QubitZeroMostSignificant,
+    );
+    assert_eq!(samples.unwrap(), vec![1, 6]);
+
+    assert!(pack_samples(
+        &per_qubit,
+        3,
+        SampleLayout::Packed,
+        PackedBitOrder::QubitZeroLeastSignificant
+    )
+    .is_err());
+    assert!(pack_samples(
+        &per_qubit,
+        2,
+        SampleLayout::Auto,
+        PackedBitOrder::QubitZeroLeastSignificant
+    )
+    .is_err());
+    assert!(pack_samples(
+        &packed,
+        2,
+        SampleLayout::Auto,
+        PackedBitOrder::QubitZeroLeastSignificant
+    )
+    .is_err());
+    assert!(pack_samples(
+        &[vec![]],
+        2,
+        SampleLayout::Auto,
+        PackedBitOrder::QubitZeroLeastSignificant
+    )
+    .is_err());
+    assert!(pack_samples(
+        &[vec![0, 2]],
+        2,
+        SampleLayout::Auto,
+        PackedBitOrder::QubitZeroLeastSignificant
+    )
+    .is_err());
+}
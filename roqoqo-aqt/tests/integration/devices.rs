@@ -10,7 +10,15 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
-use roqoqo_aqt::{devices::AqtDevice, AqtApi};
+use roqoqo::backends::EvaluatingBackend;
+use roqoqo::{operations::*, Circuit, RoqoqoBackendError};
+use roqoqo_aqt::{
+    devices::{
+        AnyAqtDevice, AqtDevice, AqtNoiseModel, EmulatorDevice, SimulatorDevice,
+        MAX_SIMULATED_QUBITS,
+    },
+    AqtApi, Backend,
+};
 
 // Test the functions of the trait AqtApi
 #[test]
@@ -23,3 +31,125 @@ fn test_aqt_api() {
         "https://arnica.aqt.eu/api/v1/".to_string()
     )
 }
+
+// Test the functions of the trait AqtApi for the local simulator device
+#[test]
+fn test_simulator_device() {
+    let device = SimulatorDevice::new(3, Some(42));
+    assert_eq!(device.number_qubits(), 3);
+    assert!(!device.is_https());
+    assert!(device.is_local());
+    assert_eq!(device.seed(), Some(42));
+    assert_eq!(device.id(), "local_simulator".to_string());
+    assert!(!AqtDevice::new(2).is_local());
+    assert_eq!(AqtDevice::new(2).seed(), None);
+}
+
+// Test the functions of the trait AqtApi for the local emulator device
+#[test]
+fn test_emulator_device() {
+    let noise_model = AqtNoiseModel::new(0.01, 0.02, 0.001, 0.003, 0.004).unwrap();
+    let device = EmulatorDevice::from_device(&AqtDevice::new(4), noise_model.clone(), None);
+    assert_eq!(device.number_qubits(), 4);
+    assert!(device.is_local());
+    assert_eq!(device.seed(), None);
+    assert_eq!(device.noise_model(), Some(noise_model.clone()));
+    assert_eq!(device.id(), "local_simulator_noise".to_string());
+    assert_eq!(AqtDevice::new(2).noise_model(), None);
+    assert_eq!(
+        AqtNoiseModel::default(),
+        AqtNoiseModel::new(0.0, 0.0, 0.0, 0.0, 0.0).unwrap()
+    );
+    assert_eq!(noise_model.single_qubit_depolarising(), 0.01);
+    assert_eq!(noise_model.ms_gate_infidelity(), 0.02);
+    assert_eq!(noise_model.dephasing(), 0.001);
+    assert_eq!(noise_model.preparation_error(), 0.003);
+    assert_eq!(noise_model.measurement_error(), 0.004);
+}
+
+// Test that noise models only accept probabilities
+#[test]
+fn test_noise_model_validation() {
+    for probability in [-0.1, 1.5, f64::NAN] {
+        assert!(AqtNoiseModel::new(probability, 0.0, 0.0, 0.0, 0.0).is_err());
+        assert!(AqtNoiseModel::new(0.0, 0.0, 0.0, 0.0, probability).is_err());
+    }
+    assert!(AqtNoiseModel::new(1.0, 1.0, 1.0, 1.0, 1.0).is_ok());
+
+    let mut serialized = serde_json::to_value(AqtNoiseModel::default()).unwrap();
+    assert!(serde_json::from_value::<AqtNoiseModel>(serialized.clone()).is_ok());
+    serialized["dephasing"] = serde_json::json!(2.0);
+    assert!(serde_json::from_value::<AqtNoiseModel>(serialized).is_err());
+}
+
+// Test that a device selected at runtime behaves like the wrapped device
+#[test]
+fn test_any_device() {
+    let device: AnyAqtDevice = AqtDevice::new(2).into();
+    assert_eq!(device.id(), "simulator_noise");
+    assert!(!device.is_local());
+    assert!(device.is_https());
+    let device: AnyAqtDevice = SimulatorDevice::new(3, Some(1)).into();
+    assert_eq!(device.number_qubits(), 3);
+    assert!(device.is_local());
+    assert_eq!(device.seed(), Some(1));
+    assert_eq!(device.noise_model(), None);
+    let noise_model = AqtNoiseModel::new(0.1, 0.0, 0.0, 0.0, 0.0).unwrap();
+    let device: AnyAqtDevice = EmulatorDevice::new(2, noise_model.clone(), None).into();
+    assert_eq!(device.id(), "local_simulator_noise");
+    assert_eq!(device.noise_model(), Some(noise_model));
+    let backend = Backend::new(device, None).unwrap();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    assert!(backend.run_circuit(&circuit).is_ok());
+}
+
+// Test that local devices are limited to the number of qubits that can be simulated
+#[test]
+fn test_simulated_qubit_limit() {
+    assert!(Backend::new(SimulatorDevice::new(MAX_SIMULATED_QUBITS, None), None).is_ok());
+    for number_qubits in [MAX_SIMULATED_QUBITS + 1, 64] {
+        assert!(Backend::new(SimulatorDevice::new(number_qubits, None), None).is_err());
+        let device = EmulatorDevice::new(number_qubits, AqtNoiseModel::default(), None);
+        assert!(Backend::new(device, None).is_err());
+    }
+
+    // A deserialized backend with too many qubits fails when the circuit is run
+    let backend = Backend::new(SimulatorDevice::new(1, Some(3)), None).unwrap();
+    let mut serialized = serde_json::to_value(&backend).unwrap();
+    serialized["device"]["number_qubits"] = serde_json::json!(64);
+    let backend: Backend<SimulatorDevice> = serde_json::from_value(serialized).unwrap();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    assert!(backend.run_circuit(&circuit).is_err());
+}
+
+// Test that local devices reject gates after the measurement like AQT devices
+#[test]
+fn test_simulated_gates_after_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    let simulator = Backend::new(SimulatorDevice::new(2, Some(3)), None).unwrap();
+    let emulator = Backend::new(
+        EmulatorDevice::new(2, AqtNoiseModel::default(), Some(3)),
+        None,
+    )
+    .unwrap();
+    // Several measurements at the end of the circuit are accepted
+    assert!(simulator.run_circuit(&circuit).is_ok());
+    assert!(emulator.run_circuit(&circuit).is_ok());
+
+    circuit += PauliX::new(0);
+    assert!(matches!(
+        simulator.run_circuit(&circuit),
+        Err(RoqoqoBackendError::GenericError { .. })
+    ));
+    assert!(matches!(
+        emulator.run_circuit(&circuit),
+        Err(RoqoqoBackendError::GenericError { .. })
+    ));
+}
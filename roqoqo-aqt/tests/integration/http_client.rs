@@ -0,0 +1,201 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::AqtDevice;
+use roqoqo_aqt::http_client::HttpClientConfig;
+use roqoqo_aqt::Backend;
+use serde_json::json;
+use std::time::Duration;
+use tokio::task::spawn_blocking;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    circuit
+}
+
+/// Mounts the responses of a job that finishes at the first poll
+async fn mount_job(server: &MockServer, user_agent: &str, delay: Duration) {
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .and(header("user-agent", user_agent))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "id": "simulator_noise",
+                    "status": "online",
+                    "available_qubits": 12
+                }))
+                .set_delay(delay),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "dummy_test_id"},
+            "response": {"status": "queued"}
+        })))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "dummy_test_id"},
+            "response": {"status": "finished", "result": {"0": [[1], [1], [1]]}}
+        })))
+        .mount(server)
+        .await;
+}
+
+#[test]
+fn test_http_client_config() {
+    let mut backend = Backend::new(AqtDevice::new(1), Some("dummy".to_string())).unwrap();
+    assert_eq!(backend.http_client_config(), &HttpClientConfig::default());
+
+    let config = HttpClientConfig {
+        timeout: Some(Duration::from_secs(5)),
+        connect_timeout: Some(Duration::from_secs(1)),
+        proxy: Some("http://proxy.example.com:8080".to_string()),
+        root_certificates: Vec::new(),
+        user_agent: Some("qoqo-tests/1.0".to_string()),
+    };
+    backend.set_http_client_config(config.clone()).unwrap();
+    assert_eq!(backend.http_client_config(), &config);
+
+    // The settings are serialised with the Backend
+    let serialised = serde_json::to_string(&backend).unwrap();
+    let deserialised: Backend<AqtDevice> = serde_json::from_str(&serialised).unwrap();
+    assert_eq!(deserialised.http_client_config(), &config);
+    assert_eq!(deserialised, backend);
+
+    let missing_certificate = std::env::temp_dir().join("roqoqo_aqt_missing_certificate.pem");
+    let invalid_certificate = std::env::temp_dir().join("roqoqo_aqt_invalid_certificate.pem");
+    std::fs::write(&invalid_certificate, "not a certificate").unwrap();
+    let invalid_configs = [
+        HttpClientConfig {
+            proxy: Some("not a proxy url".to_string()),
+            ..HttpClientConfig::default()
+        },
+        HttpClientConfig {
+            root_certificates: vec![missing_certificate],
+            ..HttpClientConfig::default()
+        },
+        HttpClientConfig {
+            root_certificates: vec![invalid_certificate.clone()],
+            ..HttpClientConfig::default()
+        },
+        HttpClientConfig {
+            user_agent: Some("line\nbreak".to_string()),
+            ..HttpClientConfig::default()
+        },
+    ];
+    for invalid in invalid_configs {
+        assert!(invalid.validate().is_err());
+        assert!(backend.set_http_client_config(invalid).is_err());
+        assert_eq!(backend.http_client_config(), &config);
+    }
+    std::fs::remove_file(invalid_certificate).unwrap();
+}
+
+// Test that the configured user agent is sent and the default identifies roqoqo-aqt
+#[tokio::test]
+async fn user_agent_test() {
+    let server = MockServer::start().await;
+    mount_job(&server, "qoqo-tests/1.0", Duration::ZERO).await;
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("dummy".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+
+    let default_backend = backend.clone();
+    let result = spawn_blocking(move || default_backend.submit_circuit(&test_circuit()))
+        .await
+        .unwrap();
+    assert!(result.is_err());
+
+    backend
+        .set_http_client_config(HttpClientConfig {
+            user_agent: Some("qoqo-tests/1.0".to_string()),
+            ..HttpClientConfig::default()
+        })
+        .unwrap();
+    let (bit_registers, _, _) = spawn_blocking(move || {
+        let job = backend.submit_circuit(&test_circuit())?;
+        backend.wait_for_job(&job)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(bit_registers.get("ro"), Some(&vec![vec![true]; 3]));
+
+    server.reset().await;
+    let default_user_agent = format!("roqoqo-aqt/{}", env!("CARGO_PKG_VERSION"));
+    mount_job(&server, &default_user_agent, Duration::ZERO).await;
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let backend = Backend::new(device, Some("dummy".to_string())).unwrap();
+    let result = spawn_blocking(move || backend.submit_circuit(&test_circuit()))
+        .await
+        .unwrap();
+    assert!(result.is_ok());
+}
+
+// Test that requests exceeding the timeout fail
+#[tokio::test]
+async fn timeout_test() {
+    let server = MockServer::start().await;
+    let user_agent = format!("roqoqo-aqt/{}", env!("CARGO_PKG_VERSION"));
+    mount_job(&server, &user_agent, Duration::from_millis(500)).await;
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("dummy".to_string())).unwrap();
+    backend
+        .set_http_client_config(HttpClientConfig {
+            timeout: Some(Duration::from_millis(100)),
+            ..HttpClientConfig::default()
+        })
+        .unwrap();
+    let result = spawn_blocking(move || backend.submit_circuit(&test_circuit()))
+        .await
+        .unwrap();
+    assert!(result.is_err());
+}
+
+// Test that requests are sent through the configured proxy
+#[tokio::test]
+async fn proxy_test() {
+    let proxy = MockServer::start().await;
+    let user_agent = format!("roqoqo-aqt/{}", env!("CARGO_PKG_VERSION"));
+    mount_job(&proxy, &user_agent, Duration::ZERO).await;
+    // The remote host does not exist, only the proxy can answer the requests
+    let device =
+        AqtDevice::new(1).set_remote_host(Some("http://aqt-remote-host.invalid/mock/".to_string()));
+    let mut backend = Backend::new(device, Some("dummy".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    backend
+        .set_http_client_config(HttpClientConfig {
+            proxy: Some(proxy.uri()),
+            ..HttpClientConfig::default()
+        })
+        .unwrap();
+    let (bit_registers, _, _) = spawn_blocking(move || {
+        let job = backend.submit_circuit(&test_circuit())?;
+        backend.wait_for_job(&job)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(bit_registers.get("ro"), Some(&vec![vec![true]; 3]));
+}
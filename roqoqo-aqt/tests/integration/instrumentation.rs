@@ -0,0 +1,146 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::{AqtDevice, SimulatorDevice};
+use roqoqo_aqt::Backend;
+use serde_json::json;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::spawn_blocking;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Collects every span and event as a line of text
+#[derive(Clone, Default)]
+struct CollectingLayer {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+struct LineVisitor<'a>(&'a mut String);
+
+impl Visit for LineVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!(" {}={value:?}", field.name()));
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for CollectingLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        let mut line = format!("span {}", attrs.metadata().name());
+        attrs.record(&mut LineVisitor(&mut line));
+        self.lines.lock().unwrap().push(line);
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut line = format!("{}", event.metadata().level());
+        event.record(&mut LineVisitor(&mut line));
+        self.lines.lock().unwrap().push(line);
+    }
+}
+
+impl CollectingLayer {
+    fn contains(&self, text: &str) -> bool {
+        self.lines
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|line| line.contains(text))
+    }
+}
+
+fn test_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    circuit
+}
+
+// Test the spans and events of a job on a local device
+#[test]
+fn local_job_tracing_test() {
+    let layer = CollectingLayer::default();
+    let subscriber = tracing_subscriber::registry().with(layer.clone());
+    let backend = Backend::new(SimulatorDevice::new(1, Some(3)), None).unwrap();
+    tracing::subscriber::with_default(subscriber, || {
+        let job = backend.submit_circuit(&test_circuit()).unwrap();
+        backend.wait_for_job(&job).unwrap();
+    });
+    assert!(layer.contains("span submit resource_id=local_simulator"));
+    assert!(layer.contains("Simulated job on local device"));
+    assert!(layer.contains("span decode"));
+    assert!(!layer.contains("aqt_request"));
+}
+
+// Test the spans and events of a job sent to the AQT servers, the access token is never traced
+#[tokio::test]
+async fn remote_job_tracing_test() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "simulator_noise",
+            "status": "online",
+            "available_qubits": 12
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "dummy_test_id"},
+            "response": {"status": "queued"}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "dummy_test_id"},
+            "response": {"status": "error", "message": "Token SECRET_TOKEN is not allowed"}
+        })))
+        .mount(&server)
+        .await;
+
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("SECRET_TOKEN".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    let layer = CollectingLayer::default();
+    let layer_cloned = layer.clone();
+    let result = spawn_blocking(move || {
+        let subscriber = tracing_subscriber::registry().with(layer_cloned);
+        tracing::subscriber::with_default(subscriber, || {
+            let job = backend.submit_circuit(&test_circuit())?;
+            backend.wait_for_job(&job)
+        })
+    })
+    .await
+    .unwrap();
+    assert!(result.is_err());
+
+    assert!(layer.contains("span resource_lookup resource_id=simulator_noise"));
+    assert!(layer.contains("available_qubits=12"));
+    assert!(layer.contains("/mock/submit/qoqo-integration/simulator_noise"));
+    assert!(layer.contains("Submitted job to AQT"));
+    assert!(layer.contains("span wait_for_job job_id=dummy_test_id"));
+    assert!(layer.contains("status=error"));
+    assert!(layer.contains("Token <redacted> is not allowed"));
+    assert!(!layer.contains("SECRET_TOKEN"));
+}
@@ -10,10 +10,15 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use roqoqo::backends::EvaluatingBackend;
 use roqoqo::operations;
 use roqoqo::Circuit;
 use roqoqo::RoqoqoBackendError;
-use roqoqo_aqt::{call_circuit, call_operation, AqtInstruction};
+use roqoqo_aqt::devices::{AqtDevice, SimulatorDevice};
+use roqoqo_aqt::{
+    aqt_json_to_circuit, call_circuit, call_instruction, call_instructions, call_loop,
+    call_operation, call_operation_sequence, AqtInstruction, Backend,
+};
 use std::collections::HashMap;
 use test_case::test_case;
 
@@ -41,6 +46,14 @@ use test_case::test_case;
                 qubit: 0,
             };
             "RotateY")]
+#[test_case(
+            operations::RotateXY::new(0,1.0.into(),0.5.into()).into(),
+            AqtInstruction::R{
+                phi: 0.5 / std::f64::consts::PI,
+                theta: 1.0 / std::f64::consts::PI,
+                qubit: 0,
+            };
+            "RotateXY")]
 #[test_case(
             operations::PauliZ::new(0).into(),
             AqtInstruction::RZ{
@@ -118,3 +131,213 @@ fn test_call_circuit() {
     }];
     assert_eq!(res, res_comp)
 }
+
+// Test that VariableMSXX is split into RXX instructions within the AQT angle range
+#[test]
+fn test_variable_msxx_sequence() {
+    let operation: operations::Operation = operations::VariableMSXX::new(0, 1, 0.6.into()).into();
+    assert_eq!(
+        call_operation_sequence(&operation).unwrap(),
+        vec![AqtInstruction::RXX {
+            theta: 0.3,
+            qubits: vec![0, 1],
+        }]
+    );
+    let operation: operations::Operation = operations::VariableMSXX::new(0, 1, 1.6.into()).into();
+    let instructions = call_operation_sequence(&operation).unwrap();
+    assert_eq!(instructions.len(), 2);
+    for instruction in instructions {
+        match instruction {
+            AqtInstruction::RXX { theta, qubits } => {
+                assert!(theta > 0.0 && theta <= 0.5);
+                assert_eq!(qubits, vec![0, 1]);
+            }
+            _ => panic!("VariableMSXX is not translated to RXX instructions"),
+        }
+    }
+    let operation: operations::Operation = operations::VariableMSXX::new(0, 1, 0.0.into()).into();
+    assert!(call_operation_sequence(&operation).unwrap().is_empty());
+}
+
+#[test_case(0.3; "small")]
+#[test_case(1.0; "one")]
+#[test_case(std::f64::consts::FRAC_PI_2; "MS")]
+fn test_multi_qubit_ms_two_qubits(theta: f64) {
+    let operation: operations::Operation =
+        operations::MultiQubitMS::new(vec![1, 0], theta.into()).into();
+    assert_eq!(
+        call_operation_sequence(&operation).unwrap(),
+        vec![AqtInstruction::RXX {
+            theta: theta / std::f64::consts::PI,
+            qubits: vec![1, 0],
+        }]
+    );
+    assert!(call_operation(&operation).is_err());
+}
+
+#[test]
+fn test_call_loop() {
+    let mut inner = Circuit::new();
+    inner += operations::PauliX::new(1);
+    let mut body = Circuit::new();
+    body += operations::RotateZ::new(0, std::f64::consts::PI.into());
+    body += operations::PragmaLoop::new(2.0.into(), inner);
+    let operation = operations::PragmaLoop::new(3.0.into(), body);
+
+    let rz = AqtInstruction::RZ { phi: 1.0, qubit: 0 };
+    let r = AqtInstruction::R {
+        phi: 0.0,
+        theta: 1.0,
+        qubit: 1,
+    };
+    let expected: Vec<AqtInstruction> =
+        [rz, r.clone(), r].iter().cycle().take(9).cloned().collect();
+    assert_eq!(call_loop(&operation, 9).unwrap(), expected);
+    let mut circuit = Circuit::new();
+    circuit += operation.clone();
+    assert_eq!(call_circuit(&circuit).unwrap(), expected);
+
+    assert!(call_loop(&operation, 8).is_err());
+    assert!(
+        call_loop(&operations::PragmaLoop::new(0.0.into(), Circuit::new()), 0)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(call_loop(&operations::PragmaLoop::new(1.5.into(), Circuit::new()), 10).is_err());
+    assert!(call_loop(&operations::PragmaLoop::new("n".into(), Circuit::new()), 10).is_err());
+}
+
+#[test]
+fn test_loop_exceeding_device() {
+    let backend = Backend::new(AqtDevice::new(1), Some("DummyToken".to_string())).unwrap();
+    let mut body = Circuit::new();
+    body += operations::PauliX::new(0);
+    let mut circuit = Circuit::new();
+    circuit += operations::PragmaLoop::new(2000.0.into(), body.clone());
+    assert!(backend.to_aqt_json(circuit.iter()).is_ok());
+    assert!(backend
+        .convert_circuits_to_aqt_instructions(&[circuit.clone()])
+        .is_ok());
+    circuit += operations::PauliX::new(0);
+    assert!(backend
+        .convert_circuits_to_aqt_instructions(&[circuit])
+        .is_err());
+    let mut circuit = Circuit::new();
+    circuit += operations::PragmaLoop::new(1e18.into(), body);
+    assert!(backend
+        .convert_circuits_to_aqt_instructions(&[circuit])
+        .is_err());
+}
+
+// The qubits beyond the first two are prepared in eigenstates of X (Z) with eigenvalue +1, so the
+// multi-qubit gate acts like the two-qubit gate on the first two qubits and is undone by its inverse
+#[test_case(3; "three")]
+#[test_case(4; "four")]
+#[test_case(5; "five")]
+#[test_case(6; "six")]
+fn test_multi_qubit_decomposition(number_qubits: usize) {
+    let theta = std::f64::consts::FRAC_PI_4;
+    let qubits: Vec<usize> = (0..number_qubits).collect();
+    let backend = Backend::new(SimulatorDevice::new(number_qubits, Some(3)), None).unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), number_qubits, true);
+    for qubit in 2..number_qubits {
+        circuit += operations::RotateY::new(qubit, std::f64::consts::FRAC_PI_2.into());
+    }
+    circuit += operations::MultiQubitMS::new(qubits.clone(), theta.into());
+    circuit += operations::MultiQubitMS::new(vec![0, 1], (-theta).into());
+    for qubit in 2..number_qubits {
+        circuit += operations::RotateY::new(qubit, (-std::f64::consts::FRAC_PI_2).into());
+    }
+    circuit += operations::MultiQubitZZ::new(qubits.clone(), theta.into());
+    circuit += operations::MultiQubitZZ::new(vec![0, 1], (-theta).into());
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 20, None);
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    for shot in bit_registers["ro"].iter() {
+        assert!(shot.iter().all(|bit| !bit));
+    }
+
+    // MultiQubitMS with theta = pi flips all qubits and MultiQubitZZ with theta = pi only changes the phase
+    let mut circuit = Circuit::new();
+    circuit += operations::DefinitionBit::new("ro".to_string(), number_qubits, true);
+    circuit += operations::MultiQubitZZ::new(qubits.clone(), std::f64::consts::PI.into());
+    circuit += operations::MultiQubitMS::new(qubits, std::f64::consts::PI.into());
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 20, None);
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    for shot in bit_registers["ro"].iter() {
+        assert!(shot.iter().all(|bit| *bit));
+    }
+}
+
+#[test_case(
+    AqtInstruction::RZ{phi: 0.5, qubit: 1},
+    operations::RotateZ::new(1, (0.5 * std::f64::consts::PI).into()).into();
+    "RZ")]
+#[test_case(
+    AqtInstruction::R{phi: 0.5, theta: 1.0, qubit: 0},
+    operations::RotateXY::new(0, std::f64::consts::PI.into(), (0.5 * std::f64::consts::PI).into()).into();
+    "R")]
+#[test_case(
+    AqtInstruction::RXX{theta: 0.5, qubits: vec![0, 2]},
+    operations::VariableMSXX::new(0, 2, 1.0.into()).into();
+    "RXX")]
+#[test_case(
+    AqtInstruction::MEASURE,
+    operations::PragmaRepeatedMeasurement::new("ro".to_string(), 10, None).into();
+    "MEASURE")]
+fn test_call_instruction(instruction: AqtInstruction, operation: operations::Operation) {
+    let called = call_instruction(&instruction, 10, "ro").unwrap();
+    assert_eq!(operation, called);
+}
+
+#[test]
+fn test_call_instruction_failure() {
+    let called = call_instruction(
+        &AqtInstruction::RXX {
+            theta: 0.5,
+            qubits: vec![1, 1],
+        },
+        10,
+        "ro",
+    );
+    assert!(called.is_err());
+    let called = call_instructions(
+        &[AqtInstruction::RZ { phi: 0.5, qubit: 3 }],
+        Some(2),
+        10,
+        "ro",
+    );
+    assert!(called.is_err());
+}
+
+// Test that translating the reconstructed circuit reproduces the instructions
+#[test]
+fn test_round_trip() {
+    let mut circuit = Circuit::new();
+    circuit += operations::RotateZ::new(0, 0.3.into());
+    circuit += operations::RotateX::new(1, 0.7.into());
+    circuit += operations::PauliY::new(2);
+    circuit += operations::MolmerSorensenXX::new(0, 2);
+    circuit += operations::VariableMSXX::new(1, 0, 0.4.into());
+    circuit += operations::PragmaRepeatedMeasurement::new("ro".to_string(), 50, None);
+    let instructions = call_circuit(&circuit).unwrap();
+
+    let reconstructed = call_instructions(&instructions, None, 50, "ro").unwrap();
+    let mut expected = Circuit::new();
+    expected += operations::DefinitionBit::new("ro".to_string(), 3, true);
+    assert_eq!(reconstructed.iter().next(), expected.iter().next());
+    assert_eq!(call_circuit(&reconstructed).unwrap(), instructions);
+
+    let backend = Backend::new(AqtDevice::new(3), Some("DummyToken".to_string())).unwrap();
+    let json = backend.to_aqt_json(circuit.iter()).unwrap();
+    let from_json = aqt_json_to_circuit(&json, Some(3), 50, "ro").unwrap();
+    assert_eq!(from_json, reconstructed);
+    assert!(aqt_json_to_circuit("not json", None, 50, "ro").is_err());
+
+    let (run_data, _, _) = backend
+        .convert_circuits_to_aqt_instructions(&[circuit.clone(), circuit])
+        .unwrap();
+    let from_run_data = run_data.to_circuits("ro").unwrap();
+    assert_eq!(from_run_data, vec![from_json.clone(), from_json]);
+}
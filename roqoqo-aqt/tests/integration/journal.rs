@@ -0,0 +1,241 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::AqtDevice;
+use roqoqo_aqt::journal::{JobJournal, JobStatus};
+use roqoqo_aqt::{AqtJobHandle, Backend};
+use serde_json::json;
+use std::time::Duration;
+use tokio::task::spawn_blocking;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 3, None);
+    circuit
+}
+
+#[test]
+fn test_missing_journal() {
+    let journal = JobJournal::new(&std::env::temp_dir().join("roqoqo_aqt_missing_journal.jsonl"));
+    assert_eq!(journal.entries().unwrap(), Vec::new());
+    let backend = Backend::new(AqtDevice::new(1), Some("dummy".to_string())).unwrap();
+    assert_eq!(backend.unfinished_jobs().unwrap(), Vec::new());
+}
+
+// Test resuming a journaled job with a new backend after the submitting backend is gone
+#[tokio::test]
+async fn resume_journaled_job_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "simulator_noise",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({
+      "job": {"job_id": "dummy_test_id"},
+      "response": {"status": "queued"}
+    });
+    let aqt_run_response_finished = json!({
+      "job": {"job_id": "dummy_test_id"},
+      "response": {
+        "status": "finished",
+        "result": {"0": [[1], [1], [1]]}
+      }
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .mount(&server)
+        .await;
+
+    let journal_path =
+        std::env::temp_dir().join(format!("roqoqo_aqt_journal_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&journal_path);
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    backend.set_job_journal(Some(JobJournal::new(&journal_path)));
+
+    let backend_cloned = backend.clone();
+    let submitted = spawn_blocking(move || backend_cloned.submit_circuit(&test_circuit()))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // A new backend finds the unfinished job in the journal
+    let mut resumed_backend = backend.clone();
+    resumed_backend.set_job_journal(Some(JobJournal::new(&journal_path)));
+    let unfinished = resumed_backend.unfinished_jobs().unwrap();
+    assert_eq!(unfinished, vec![submitted.clone()]);
+    let entries = JobJournal::new(&journal_path).entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].status, JobStatus::Submitted);
+    assert_eq!(entries[0].fingerprint.len(), 64);
+
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+    let (bit_registers, _, _) = spawn_blocking(move || {
+        let job = &resumed_backend.unfinished_jobs()?[0];
+        resumed_backend.wait_for_job(job)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(bit_registers.get("ro"), Some(&vec![vec![true]; 3]));
+    assert_eq!(backend.unfinished_jobs().unwrap(), Vec::new());
+    let entries = JobJournal::new(&journal_path).entries().unwrap();
+    assert_eq!(entries[0].status, JobStatus::Finished);
+
+    // Cancelled jobs are not unfinished
+    Mock::given(method("DELETE"))
+        .and(path("/mock/jobs/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let backend_cloned = backend.clone();
+    spawn_blocking(move || {
+        let job = backend_cloned.submit_circuit(&test_circuit())?;
+        backend_cloned.cancel_job(&job)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    let entries = JobJournal::new(&journal_path).entries().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].status, JobStatus::Cancelled);
+    assert_eq!(backend.unfinished_jobs().unwrap(), Vec::new());
+    std::fs::remove_file(&journal_path).unwrap();
+}
+
+// Test that a record cut off by a crash while it was written does not break the journal
+#[test]
+fn test_torn_journal_record() {
+    let journal_path = std::env::temp_dir().join(format!(
+        "roqoqo_aqt_torn_journal_{}.jsonl",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&journal_path);
+    let journal = JobJournal::new(&journal_path);
+    let job: AqtJobHandle = serde_json::from_value(json!({
+        "job_id": "journaled_job",
+        "readouts": ["ro"],
+        "bit_registers": ["ro"],
+        "float_registers": [],
+        "complex_registers": []
+    }))
+    .unwrap();
+    journal.record_submitted(&job, "fingerprint").unwrap();
+
+    // The process dies while the status record is written
+    let content = std::fs::read_to_string(&journal_path).unwrap();
+    journal
+        .record_status(job.job_id(), JobStatus::Finished)
+        .unwrap();
+    let record = std::fs::read_to_string(&journal_path).unwrap()[content.len()..].to_string();
+    std::fs::write(
+        &journal_path,
+        format!("{content}{}", &record[..record.len() / 2]),
+    )
+    .unwrap();
+    let entries = journal.entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].status, JobStatus::Submitted);
+    assert_eq!(journal.unfinished().unwrap().len(), 1);
+
+    // Records appended after the cut off record start on a new line
+    journal
+        .record_status(job.job_id(), JobStatus::Cancelled)
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&journal_path)
+            .unwrap()
+            .lines()
+            .count(),
+        3
+    );
+    assert_eq!(journal.entries().unwrap()[0].status, JobStatus::Cancelled);
+
+    // Other invalid records are still reported
+    std::fs::write(&journal_path, "not a record\n").unwrap();
+    assert!(journal.entries().is_err());
+    std::fs::remove_file(&journal_path).unwrap();
+}
+
+// Test that a job stays unfinished in the journal when its result can not be decoded
+#[tokio::test]
+async fn journal_undecodable_result_test() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "simulator_noise",
+            "status": "online",
+            "available_qubits": 12
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "dummy_test_id"},
+            "response": {"status": "queued"}
+        })))
+        .mount(&server)
+        .await;
+    // Sample 7 is out of range for a single qubit
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "dummy_test_id"},
+            "response": {"status": "finished", "result": {"0": [[7], [7], [7]]}}
+        })))
+        .mount(&server)
+        .await;
+
+    let journal_path = std::env::temp_dir().join(format!(
+        "roqoqo_aqt_undecodable_journal_{}.jsonl",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&journal_path);
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    backend.set_job_journal(Some(JobJournal::new(&journal_path)));
+    let backend_cloned = backend.clone();
+    let result = spawn_blocking(move || {
+        let job = backend_cloned.submit_circuit(&test_circuit())?;
+        backend_cloned.wait_for_job(&job)
+    })
+    .await
+    .unwrap();
+    assert!(result.is_err());
+    let entries = JobJournal::new(&journal_path).entries().unwrap();
+    assert_eq!(entries[0].status, JobStatus::Submitted);
+    assert_eq!(backend.unfinished_jobs().unwrap().len(), 1);
+    std::fs::remove_file(&journal_path).unwrap();
+}
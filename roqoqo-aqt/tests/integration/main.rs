@@ -12,5 +12,17 @@
 
 #[cfg(test)]
 mod backend;
+mod cassette;
+mod counts;
 mod devices;
+mod http_client;
+#[cfg(feature = "tracing")]
+mod instrumentation;
 mod interface;
+mod journal;
+#[cfg(feature = "mock-server")]
+mod mock_server;
+mod noise_amplification;
+mod optimisation;
+mod readout_mitigation;
+mod result_cache;
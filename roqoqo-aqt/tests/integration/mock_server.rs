@@ -0,0 +1,110 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::prelude::*;
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::AqtDevice;
+use roqoqo_aqt::mock_server::{MockAqtServer, MockServerConfig};
+use roqoqo_aqt::{AqtApi, Backend};
+use std::time::{Duration, Instant};
+
+fn mock_backend(server: &MockAqtServer) -> Backend<AqtDevice> {
+    let mut backend = Backend::new(server.device(2), Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    backend
+}
+
+fn measured_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 5, None);
+    circuit
+}
+
+// Test running a circuit and the job states of the mock server
+#[test]
+fn test_mock_server_job_states() {
+    let server = MockAqtServer::start(MockServerConfig {
+        seed: Some(1),
+        ..MockServerConfig::default()
+    })
+    .unwrap();
+    let backend = mock_backend(&server);
+    assert!(!backend.device.remote_host().starts_with("https"));
+
+    let (bit_registers, _, _) = backend.run_circuit(&measured_circuit()).unwrap();
+    assert_eq!(bit_registers.get("ro"), Some(&vec![vec![true, false]; 5]));
+    assert_eq!(server.job_ids(), vec!["mock_job_0".to_string()]);
+    assert_eq!(
+        server.job_status("mock_job_0"),
+        Some("finished".to_string())
+    );
+
+    let job = backend.submit_circuit(&measured_circuit()).unwrap();
+    assert_eq!(server.job_status(job.job_id()), Some("queued".to_string()));
+    assert_eq!(backend.poll_job(&job), Ok(None));
+    assert_eq!(server.job_status(job.job_id()), Some("ongoing".to_string()));
+    assert_eq!(backend.poll_job(&job), Ok(None));
+    assert!(backend.poll_job(&job).unwrap().is_some());
+    assert_eq!(server.job_status("unknown"), None);
+}
+
+// Test failure injection and cancellation with the mock server
+#[test]
+fn test_mock_server_failures() {
+    let server = MockAqtServer::start(MockServerConfig::default()).unwrap();
+    let backend = mock_backend(&server);
+
+    server.fail_next_submissions(1, 500);
+    assert!(backend.submit_circuit(&measured_circuit()).is_err());
+    assert!(server.job_ids().is_empty());
+
+    server.fail_next_jobs(1, "Injected job failure");
+    let job = backend.submit_circuit(&measured_circuit()).unwrap();
+    let err = backend.wait_for_job(&job).unwrap_err();
+    assert!(format!("{err:?}").contains("Injected job failure"));
+    assert_eq!(server.job_status(job.job_id()), Some("error".to_string()));
+
+    let job = backend.submit_circuit(&measured_circuit()).unwrap();
+    backend.cancel_job(&job).unwrap();
+    assert_eq!(
+        server.job_status(job.job_id()),
+        Some("cancelled".to_string())
+    );
+    assert!(backend.wait_for_job(&job).is_err());
+    assert!(backend.cancel_job(&job).is_err());
+
+    server.set_online(false);
+    assert!(backend.submit_circuit(&measured_circuit()).is_err());
+    server.set_online(true);
+
+    let too_large = Backend::new(server.device(30), Some("DummyAccessToken".to_string())).unwrap();
+    assert!(too_large.submit_circuit(&measured_circuit()).is_err());
+}
+
+// Test the latency of the mock server
+#[test]
+fn test_mock_server_latency() {
+    let server = MockAqtServer::start(MockServerConfig {
+        latency: Duration::from_millis(100),
+        ..MockServerConfig::default()
+    })
+    .unwrap();
+    let backend = mock_backend(&server);
+    let start = Instant::now();
+    let job = backend.submit_circuit(&measured_circuit()).unwrap();
+    // The resource details and the submission are both delayed
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    server.set_latency(Duration::ZERO);
+    assert!(backend.wait_for_job(&job).is_ok());
+}
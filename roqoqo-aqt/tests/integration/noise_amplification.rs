@@ -0,0 +1,168 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::measurements::{PauliZProduct, PauliZProductInput};
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::{AqtDevice, AqtNoiseModel, EmulatorDevice};
+use roqoqo_aqt::noise_amplification::{extrapolate_to_zero, fold_instructions};
+use roqoqo_aqt::optimisation::verify_equivalence;
+use roqoqo_aqt::{AqtInstruction, AqtRunData, Backend};
+use std::collections::HashMap;
+use test_case::test_case;
+
+fn instructions() -> Vec<AqtInstruction> {
+    vec![
+        AqtInstruction::R {
+            phi: 0.3,
+            theta: 0.4,
+            qubit: 0,
+        },
+        AqtInstruction::RZ { phi: 0.7, qubit: 1 },
+        AqtInstruction::RXX {
+            qubits: vec![1, 0],
+            theta: 0.25,
+        },
+        AqtInstruction::MEASURE,
+    ]
+}
+
+// Test that folding keeps the unitary and multiplies the number of noisy gates
+#[test_case(1.0, 1, 1; "no folding")]
+#[test_case(2.0, 3, 1; "partial folding")]
+#[test_case(3.0, 3, 3; "odd factor")]
+#[test_case(5.0, 5, 5; "five")]
+fn test_fold_instructions(scale_factor: f64, number_r: usize, number_rxx: usize) {
+    let folded = fold_instructions(&instructions(), scale_factor).unwrap();
+    let count = |name: &str| {
+        folded
+            .iter()
+            .filter(|instruction| match instruction {
+                AqtInstruction::R { .. } => name == "R",
+                AqtInstruction::RXX { .. } => name == "RXX",
+                _ => false,
+            })
+            .count()
+    };
+    assert_eq!(count("R"), number_r);
+    assert_eq!(count("RXX"), number_rxx);
+    assert_eq!(folded.last(), Some(&AqtInstruction::MEASURE));
+    assert!(verify_equivalence(&instructions(), &folded).unwrap());
+    let without_measurement = &instructions()[..3];
+    assert!(verify_equivalence(
+        without_measurement,
+        &fold_instructions(without_measurement, scale_factor).unwrap()
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_fold_instructions_failure() {
+    assert!(fold_instructions(&instructions(), 0.5).is_err());
+    assert!(fold_instructions(&instructions(), f64::NAN).is_err());
+}
+
+#[test]
+fn test_extrapolate_to_zero() {
+    let linear = extrapolate_to_zero(&[1.0, 2.0, 3.0], &[0.9, 0.8, 0.7], 1).unwrap();
+    assert!((linear - 1.0).abs() < 1e-10);
+    let quadratic = extrapolate_to_zero(
+        &[1.0, 3.0, 5.0],
+        &[0.5 + 0.1 - 0.01, 0.5 + 0.3 - 0.09, 0.5 + 0.5 - 0.25],
+        2,
+    )
+    .unwrap();
+    assert!((quadratic - 0.5).abs() < 1e-10);
+    // Least squares fit of noisy values
+    let fitted = extrapolate_to_zero(&[1.0, 1.0, 3.0, 3.0], &[0.81, 0.79, 0.41, 0.39], 1).unwrap();
+    assert!((fitted - 1.0).abs() < 1e-10);
+
+    assert!(extrapolate_to_zero(&[1.0, 3.0], &[0.5], 1).is_err());
+    assert!(extrapolate_to_zero(&[1.0, 1.0, 1.0], &[0.5, 0.5, 0.5], 1).is_err());
+    assert!(extrapolate_to_zero(&[1.0, 3.0], &[0.5, 0.4], 2).is_err());
+}
+
+// Test that PragmaBoostNoise folds the translated instructions, also when they are optimised
+#[test]
+fn test_boost_noise_translation() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PragmaBoostNoise::new(3.0.into());
+    circuit += RotateX::new(0, 0.5.into());
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let mut backend = Backend::new(AqtDevice::new(2), Some("DummyToken".to_string())).unwrap();
+    for optimise in [false, true] {
+        backend.set_optimise_instructions(optimise);
+        let (run_data, _, _) = backend
+            .convert_circuit_to_aqt_instructions(circuit.iter())
+            .unwrap();
+        let run_data = AqtRunData::from_json(&serde_json::to_string(&run_data).unwrap()).unwrap();
+        let circuits = run_data.to_circuits("ro").unwrap();
+        let rotations = circuits[0]
+            .iter()
+            .filter(|operation| matches!(operation, Operation::RotateXY(_)))
+            .count();
+        let entangling = circuits[0]
+            .iter()
+            .filter(|operation| matches!(operation, Operation::VariableMSXX(_)))
+            .count();
+        assert_eq!((rotations, entangling), (3, 3));
+    }
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaBoostNoise::new(0.5.into());
+    circuit += RotateX::new(0, 0.5.into());
+    assert!(backend
+        .convert_circuit_to_aqt_instructions(circuit.iter())
+        .is_err());
+}
+
+// Test that zero-noise extrapolation on the emulator recovers the noiseless expectation value better than the noisy run
+#[test]
+fn test_zero_noise_extrapolation() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += RotateX::new(0, std::f64::consts::PI.into());
+    circuit += RotateX::new(0, std::f64::consts::PI.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 4000, None);
+    let mut input = PauliZProductInput::new(1, false);
+    let index = input.add_pauliz_product("ro".to_string(), vec![0]).unwrap();
+    input
+        .add_linear_exp_val("z".to_string(), HashMap::from([(index, 1.0)]))
+        .unwrap();
+    let measurement = PauliZProduct {
+        constant_circuit: None,
+        circuits: vec![circuit],
+        input,
+    };
+    let device = EmulatorDevice::new(
+        1,
+        AqtNoiseModel::new(0.03, 0.0, 0.0, 0.0, 0.0).unwrap(),
+        Some(5),
+    );
+    let backend = Backend::new(device, None).unwrap();
+
+    let result = backend
+        .run_measurement_zero_noise_extrapolation(&measurement, &[1.0, 3.0, 5.0], 2)
+        .unwrap();
+    assert_eq!(result.scale_factors, vec![1.0, 3.0, 5.0]);
+    let values = &result.values["z"];
+    assert_eq!(values.len(), 3);
+    assert!(values[0] > values[1] && values[1] > values[2]);
+    let extrapolated = result.extrapolated["z"];
+    assert!((extrapolated - 1.0).abs() < (values[0] - 1.0).abs());
+    assert!((extrapolated - 1.0).abs() < 0.05);
+
+    assert!(backend
+        .run_measurement_zero_noise_extrapolation(&measurement, &[1.0], 1)
+        .is_err());
+}
@@ -0,0 +1,285 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::prelude::*;
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::SimulatorDevice;
+use roqoqo_aqt::optimisation::{
+    fuse_single_qubit_gates, merge_two_qubit_gates, optimise_instructions, verify_equivalence,
+};
+use roqoqo_aqt::{call_circuit, AqtInstruction, Backend};
+
+// Test that a sequence of rotations is fused into a single R instruction before a measurement
+#[test]
+fn test_fusion_before_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 0.3.into());
+    circuit += RotateX::new(0, 0.7.into());
+    circuit += RotateZ::new(0, 1.1.into());
+    circuit += RotateY::new(0, 0.2.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let instructions = call_circuit(&circuit).unwrap();
+    let (optimised, report) = fuse_single_qubit_gates(&instructions);
+
+    assert_eq!(optimised.len(), 2);
+    assert!(matches!(optimised[0], AqtInstruction::R { qubit: 0, .. }));
+    assert_eq!(optimised[1], AqtInstruction::MEASURE);
+    assert_eq!(report.instructions_before, 5);
+    assert_eq!(report.instructions_after, 2);
+    assert_eq!(report.single_qubit_instructions_before, 4);
+    assert_eq!(report.single_qubit_instructions_after, 1);
+    assert_eq!(report.removed_instructions(), 3);
+}
+
+// Test that identity rotations are removed and Z rotations are kept without a measurement
+#[test]
+fn test_identities_and_trailing_rz() {
+    let instructions = vec![
+        AqtInstruction::R {
+            phi: 0.3,
+            theta: 0.0,
+            qubit: 0,
+        },
+        AqtInstruction::RZ { phi: 2.0, qubit: 1 },
+        AqtInstruction::RZ { phi: 0.5, qubit: 2 },
+        AqtInstruction::RZ { phi: 0.5, qubit: 2 },
+    ];
+    let (optimised, _) = fuse_single_qubit_gates(&instructions);
+    assert_eq!(optimised.len(), 1);
+    match &optimised[0] {
+        AqtInstruction::RZ { phi, qubit } => {
+            assert_eq!(*qubit, 2);
+            assert!((phi - 1.0).abs() < 1e-9);
+        }
+        _ => panic!("Expected a single RZ instruction"),
+    }
+}
+
+// Test that Z rotations by pi on both qubits are commuted through an RXX instruction
+#[test]
+fn test_commuting_z_through_rxx() {
+    let instructions = vec![
+        AqtInstruction::RZ { phi: 1.0, qubit: 0 },
+        AqtInstruction::RZ { phi: 1.0, qubit: 1 },
+        AqtInstruction::RXX {
+            qubits: vec![0, 1],
+            theta: 0.5,
+        },
+        AqtInstruction::MEASURE,
+    ];
+    let (optimised, _) = fuse_single_qubit_gates(&instructions);
+    assert_eq!(
+        optimised,
+        vec![
+            AqtInstruction::RXX {
+                qubits: vec![0, 1],
+                theta: 0.5,
+            },
+            AqtInstruction::MEASURE
+        ]
+    );
+
+    // A Z rotation on a single qubit does not commute with the RXX instruction
+    let instructions = vec![
+        AqtInstruction::RZ { phi: 1.0, qubit: 0 },
+        AqtInstruction::RXX {
+            qubits: vec![0, 1],
+            theta: 0.5,
+        },
+        AqtInstruction::MEASURE,
+    ];
+    let (optimised, _) = fuse_single_qubit_gates(&instructions);
+    assert_eq!(optimised, instructions);
+}
+
+// Test that the optimised circuit produces the same measurement results as the original circuit
+#[test]
+fn test_optimised_backend_equivalence() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += RotateY::new(0, 0.4.into());
+    circuit += RotateZ::new(0, 0.9.into());
+    circuit += RotateX::new(1, 1.3.into());
+    circuit += PauliY::new(1);
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += RotateZ::new(1, 0.6.into());
+    circuit += RotateXY::new(1, 0.8.into(), 0.2.into());
+    circuit += RotateX::new(2, 2.1.into());
+    circuit += VariableMSXX::new(1, 2, 0.7.into());
+    circuit += PauliZ::new(2);
+    circuit += RotateY::new(2, 0.5.into());
+    circuit += RotateZ::new(0, 1.7.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 500, None);
+
+    let backend = Backend::new(SimulatorDevice::new(3, Some(5)), None).unwrap();
+    let (expected, _, _) = backend.run_circuit(&circuit).unwrap();
+    let mut optimising_backend = backend.clone();
+    optimising_backend.set_optimise_instructions(true);
+    assert!(optimising_backend.optimise_instructions());
+    let (optimised, _, _) = optimising_backend.run_circuit(&circuit).unwrap();
+    assert_eq!(optimised, expected);
+
+    let (run_data, _, _) = optimising_backend
+        .convert_circuit_to_aqt_instructions(circuit.iter())
+        .unwrap();
+    let report = optimising_backend.translation_report(&circuit);
+    assert!(report.native_gate_counts.get("R") < Some(&8));
+    let optimisation_report = backend.optimisation_report(&circuit).unwrap();
+    assert_eq!(optimisation_report.instructions_before, 13);
+    assert_eq!(
+        optimisation_report.instructions_after,
+        report.native_gate_counts.values().sum::<usize>()
+    );
+    assert_eq!(run_data.to_circuits("ro").unwrap().len(), 1);
+}
+
+fn rxx(first: u32, second: u32, theta: f64) -> AqtInstruction {
+    AqtInstruction::RXX {
+        qubits: vec![first, second],
+        theta,
+    }
+}
+
+// Test merging adjacent RXX instructions on the same pair of qubits
+#[test]
+fn test_merging_rxx() {
+    let rotation = AqtInstruction::R {
+        phi: 0.0,
+        theta: 0.5,
+        qubit: 2,
+    };
+    let instructions = vec![rxx(0, 1, 0.2), rotation.clone(), rxx(1, 0, 0.2)];
+    let (optimised, report) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised.len(), 2);
+    assert_eq!(optimised[1], rotation);
+    match &optimised[0] {
+        AqtInstruction::RXX { qubits, theta } => {
+            assert_eq!(qubits, &vec![0, 1]);
+            assert!((theta - 0.4).abs() < 1e-9);
+        }
+        _ => panic!("Expected a merged RXX instruction"),
+    }
+    assert_eq!(report.two_qubit_instructions_before, 2);
+    assert_eq!(report.two_qubit_instructions_after, 1);
+    assert!(verify_equivalence(&instructions, &optimised).unwrap());
+
+    // A rotation on one of the qubits separates the RXX instructions
+    let instructions = vec![
+        rxx(0, 1, 0.2),
+        AqtInstruction::R {
+            phi: 0.0,
+            theta: 0.5,
+            qubit: 1,
+        },
+        rxx(0, 1, 0.2),
+    ];
+    let (optimised, _) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised, instructions);
+}
+
+// Test that RXX instructions composing to the identity cancel, also when nested
+#[test]
+fn test_cancelling_rxx() {
+    let instructions = vec![
+        rxx(0, 1, 0.5),
+        rxx(1, 2, 0.25),
+        rxx(1, 2, -0.25),
+        rxx(0, 1, 1.5),
+        AqtInstruction::MEASURE,
+    ];
+    let (optimised, report) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised, vec![AqtInstruction::MEASURE]);
+    assert_eq!(report.removed_instructions(), 4);
+    assert!(verify_equivalence(&instructions, &optimised).unwrap());
+
+    // A measurement separates the RXX instructions
+    let instructions = vec![rxx(0, 1, 0.5), AqtInstruction::MEASURE, rxx(0, 1, -0.5)];
+    let (optimised, _) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised, instructions);
+}
+
+// Test that merged angles respect the RXX angle range
+#[test]
+fn test_rxx_angle_range() {
+    // Two MS gates are kept as two instructions within the range
+    let instructions = vec![rxx(0, 1, 0.5), rxx(0, 1, 0.5)];
+    let (optimised, _) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised, instructions);
+
+    let instructions = vec![rxx(0, 1, 0.4), rxx(0, 1, 0.3), rxx(0, 1, 0.1)];
+    let (optimised, _) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised.len(), 2);
+    for instruction in optimised.iter() {
+        match instruction {
+            AqtInstruction::RXX { theta, .. } => assert!(*theta >= 0.0 && *theta <= 0.5),
+            _ => panic!("Expected RXX instructions"),
+        }
+    }
+    assert!(verify_equivalence(&instructions, &optimised).unwrap());
+
+    // Negative angles would need additional Z rotations and are not merged into more instructions
+    let instructions = vec![rxx(0, 1, -0.2), rxx(0, 1, -0.1)];
+    let (optimised, _) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised, instructions);
+    let instructions = vec![rxx(0, 1, -0.2), rxx(0, 1, -0.1), rxx(0, 1, -0.1)];
+    let (optimised, _) = merge_two_qubit_gates(&instructions);
+    assert_eq!(optimised.len(), 3);
+    assert!(matches!(optimised[1], AqtInstruction::RXX { .. }));
+    assert!(verify_equivalence(&instructions, &optimised).unwrap());
+}
+
+// Test the verification of the equivalence of instructions
+#[test]
+fn test_verify_equivalence() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, 0.3.into());
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += RotateZ::new(1, 0.4.into());
+    circuit += MolmerSorensenXX::new(1, 0);
+    circuit += VariableMSXX::new(1, 2, 0.6.into());
+    circuit += RotateY::new(2, 1.2.into());
+    let instructions = call_circuit(&circuit).unwrap();
+    let (optimised, report) = optimise_instructions(&instructions);
+    assert!(report.instructions_after <= report.instructions_before);
+    assert!(verify_equivalence(&instructions, &optimised).unwrap());
+
+    // Z rotations can only be dropped before a measurement
+    let rz = vec![AqtInstruction::RZ { phi: 0.5, qubit: 0 }];
+    assert!(!verify_equivalence(&rz, &[]).unwrap());
+    let mut measured_rz = rz.clone();
+    measured_rz.push(AqtInstruction::MEASURE);
+    assert!(verify_equivalence(&measured_rz, &[AqtInstruction::MEASURE]).unwrap());
+
+    let different = vec![rxx(0, 1, 0.3)];
+    assert!(!verify_equivalence(&different, &[rxx(0, 1, 0.2)]).unwrap());
+    assert!(verify_equivalence(&[rxx(0, 11, 0.3)], &[]).is_err());
+}
+
+// Test that a backend in verification mode runs optimised circuits
+#[test]
+fn test_backend_verify_optimisation() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PauliX::new(0);
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += MolmerSorensenXX::new(1, 0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 20, None);
+    let mut backend = Backend::new(SimulatorDevice::new(2, Some(1)), None).unwrap();
+    backend.set_optimise_instructions(true);
+    backend.set_verify_optimisation(true);
+    assert!(backend.verify_optimisation());
+    let (bits, _, _) = backend.run_circuit(&circuit).unwrap();
+    // Two MS gates implement X on both qubits up to a phase
+    assert_eq!(bits["ro"], vec![vec![false, true]; 20]);
+    let report = backend.optimisation_report(&circuit).unwrap();
+    assert_eq!(report.two_qubit_instructions_before, 2);
+}
@@ -0,0 +1,216 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::backends::EvaluatingBackend;
+use roqoqo::measurements::{PauliZProduct, PauliZProductInput};
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::{AqtDevice, AqtNoiseModel, EmulatorDevice};
+use roqoqo_aqt::readout_mitigation::{MitigationMethod, ReadoutCalibration};
+use roqoqo_aqt::result_cache::{CachePolicy, ResultCache};
+use roqoqo_aqt::Backend;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::task::spawn_blocking;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn calibration() -> ReadoutCalibration {
+    // Qubit 0 flips 0 -> 1 in 1 of 10 shots and 1 -> 0 in 2 of 10 shots, qubit 1 has no errors
+    let mut prepared_zero = vec![vec![false, false]; 9];
+    prepared_zero.push(vec![true, false]);
+    let mut prepared_one = vec![vec![true, true]; 8];
+    prepared_one.extend(vec![vec![false, true]; 2]);
+    ReadoutCalibration::from_shots("device", &prepared_zero, &prepared_one).unwrap()
+}
+
+#[test]
+fn test_calibration() {
+    let calibration = calibration();
+    let [[a, b], [c, d]] = calibration.confusion_matrices()[0];
+    assert!((a - 0.9).abs() < 1e-12 && (b - 0.2).abs() < 1e-12);
+    assert!((c - 0.1).abs() < 1e-12 && (d - 0.8).abs() < 1e-12);
+    assert_eq!(
+        calibration.confusion_matrices()[1],
+        [[1.0, 0.0], [0.0, 1.0]]
+    );
+
+    let tensored = calibration.tensored_confusion_matrix(&[0, 1]).unwrap();
+    assert_eq!(tensored.len(), 4);
+    for column in 0..4 {
+        let sum: f64 = tensored.iter().map(|row| row[column]).sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+    }
+    // Prepared |01> (qubit 0 in 1) is measured as |00> with the error of qubit 0
+    assert!((tensored[0][1] - 0.2).abs() < 1e-12);
+    assert_eq!(tensored[2][1], 0.0);
+    assert!(calibration.tensored_confusion_matrix(&[2]).is_err());
+
+    assert!(ReadoutCalibration::from_shots("device", &[], &[vec![true]]).is_err());
+    // Indistinguishable states can not be corrected
+    assert!(ReadoutCalibration::from_shots("device", &[vec![false]], &[vec![false]]).is_err());
+
+    // Only confusion matrices of probabilities are accepted, also when deserializing
+    let valid = ReadoutCalibration::new("device", vec![[[0.9, 0.2], [0.1, 0.8]]]).unwrap();
+    assert_eq!(valid.device_id(), "device");
+    assert!(ReadoutCalibration::new("device", vec![[[f64::NAN, 0.2], [0.1, 0.8]]]).is_err());
+    assert!(ReadoutCalibration::new("device", vec![[[0.9, 0.2], [0.2, 0.8]]]).is_err());
+    assert!(ReadoutCalibration::new("device", vec![[[1.1, 0.0], [-0.1, 1.0]]]).is_err());
+    let serialized = serde_json::to_string(&valid).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ReadoutCalibration>(&serialized).unwrap(),
+        valid
+    );
+    let invalid = serialized.replace("0.9", "0.7");
+    assert!(serde_json::from_str::<ReadoutCalibration>(&invalid).is_err());
+}
+
+#[test]
+fn test_correct_distribution() {
+    let calibration = calibration();
+    let mut shots = vec![vec![true, true]; 8];
+    shots.extend(vec![vec![false, true]; 2]);
+    let corrected = calibration
+        .correct_distribution(&shots, MitigationMethod::Inverse)
+        .unwrap();
+    assert_eq!(corrected.len(), 1);
+    assert!((corrected[&vec![true, true]] - 1.0).abs() < 1e-12);
+
+    // The inverse produces a negative quasi-probability, the least squares correction does not
+    let shots = vec![vec![false, false]; 10];
+    let inverse = calibration
+        .correct_distribution(&shots, MitigationMethod::Inverse)
+        .unwrap();
+    assert!(inverse[&vec![true, false]] < 0.0);
+    assert!((inverse.values().sum::<f64>() - 1.0).abs() < 1e-12);
+    let least_squares = calibration
+        .correct_distribution(&shots, MitigationMethod::LeastSquares)
+        .unwrap();
+    assert_eq!(least_squares.len(), 1);
+    assert!((least_squares[&vec![false, false]] - 1.0).abs() < 1e-12);
+
+    assert!(calibration
+        .correct_distribution(&[vec![false; 3]], MitigationMethod::Inverse)
+        .is_err());
+}
+
+// Test that the mitigated expectation value of an emulator with measurement errors is closer to the ideal value
+#[test]
+fn test_mitigated_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 4000, None);
+    let mut input = PauliZProductInput::new(2, false);
+    let index = input
+        .add_pauliz_product("ro".to_string(), vec![0, 1])
+        .unwrap();
+    input
+        .add_linear_exp_val("zz".to_string(), HashMap::from([(index, 1.0)]))
+        .unwrap();
+    input
+        .add_symbolic_exp_val("minus_zz".to_string(), "-pauli_product_0".into())
+        .unwrap();
+    let measurement = PauliZProduct {
+        constant_circuit: None,
+        circuits: vec![circuit],
+        input,
+    };
+    let device = EmulatorDevice::new(
+        2,
+        AqtNoiseModel::new(0.0, 0.0, 0.0, 0.0, 0.05).unwrap(),
+        Some(7),
+    );
+    let mut backend = Backend::new(device, None).unwrap();
+
+    assert!(backend.run_measurement_mitigated(&measurement).is_err());
+    backend.set_readout_mitigation(Some(MitigationMethod::LeastSquares));
+    assert_eq!(
+        backend.readout_mitigation(),
+        Some(MitigationMethod::LeastSquares)
+    );
+    assert!(backend.run_measurement_mitigated(&measurement).is_err());
+
+    let calibration = backend.calibrate_readout(4000).unwrap().clone();
+    assert_eq!(backend.readout_calibration(), Some(&calibration));
+    for [[_, error_one], [error_zero, _]] in calibration.confusion_matrices().iter() {
+        assert!(*error_zero > 0.0 && *error_zero < 0.1);
+        assert!(*error_one > 0.0 && *error_one < 0.1);
+    }
+
+    let raw = backend.run_measurement(&measurement).unwrap().unwrap();
+    let mitigated = backend
+        .run_measurement_mitigated(&measurement)
+        .unwrap()
+        .unwrap();
+    assert!((mitigated["zz"] + 1.0).abs() < (raw["zz"] + 1.0).abs());
+    assert!((mitigated["zz"] + 1.0).abs() < 0.05);
+    assert!((mitigated["minus_zz"] + mitigated["zz"]).abs() < 1e-12);
+
+    backend.set_readout_calibration(Some(
+        ReadoutCalibration::new("other", calibration.confusion_matrices().to_vec()).unwrap(),
+    ));
+    assert!(backend.run_measurement_mitigated(&measurement).is_err());
+}
+
+// Test that calibrations are always measured on the device instead of being read from the result cache
+#[tokio::test]
+async fn calibration_bypasses_result_cache_test() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "simulator_noise",
+            "status": "online",
+            "available_qubits": 12
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "calibration_id"},
+            "response": {"status": "queued"}
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/calibration_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "job": {"job_id": "calibration_id"},
+            "response": {"status": "finished", "result": {"0": [[0], [1]], "1": [[1], [1]]}}
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let directory = std::env::temp_dir().join(format!(
+        "roqoqo_aqt_calibration_cache_{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    backend.set_result_cache(Some(ResultCache::new(&directory, CachePolicy::ReadWrite)));
+    let calibration = spawn_blocking(move || {
+        backend.calibrate_readout(2)?;
+        backend.calibrate_readout(2).cloned()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(calibration.confusion_matrices(), [[[0.5, 0.0], [0.5, 1.0]]]);
+    assert!(!directory.exists());
+    server.verify().await;
+}
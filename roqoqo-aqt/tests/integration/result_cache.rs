@@ -0,0 +1,263 @@
+// Copyright © 2021-2025 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::{operations::*, Circuit};
+use roqoqo_aqt::devices::AqtDevice;
+use roqoqo_aqt::result_cache::{CacheEntry, CachePolicy, ResultCache};
+use roqoqo_aqt::Backend;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::task::spawn_blocking;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_circuit(repetitions: usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), repetitions, None);
+    circuit
+}
+
+fn cache_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "roqoqo_aqt_result_cache_{name}_{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+}
+
+#[test]
+fn test_fingerprint() {
+    let backend = Backend::new(AqtDevice::new(1), Some("dummy".to_string())).unwrap();
+    let fingerprint = |circuit: &Circuit, remote_host: &str, resource_id: &str| {
+        let (run_data, _, _) = backend
+            .convert_circuit_to_aqt_instructions(circuit.iter())
+            .unwrap();
+        ResultCache::fingerprint(&run_data, remote_host, resource_id).unwrap()
+    };
+    let host = "https://arnica.aqt.eu/api/v1/";
+    let reference = fingerprint(&test_circuit(3), host, "simulator_noise");
+    assert_eq!(reference.len(), 64);
+    assert_eq!(
+        fingerprint(&test_circuit(3), host, "simulator_noise"),
+        reference
+    );
+    assert_ne!(
+        fingerprint(&test_circuit(4), host, "simulator_noise"),
+        reference
+    );
+    assert_ne!(
+        fingerprint(&test_circuit(3), host, "other_resource"),
+        reference
+    );
+    assert_ne!(
+        fingerprint(
+            &test_circuit(3),
+            "http://localhost:8080/",
+            "simulator_noise"
+        ),
+        reference
+    );
+}
+
+#[test]
+fn test_cache_policies() {
+    let directory = cache_directory("policies");
+    let result: HashMap<u32, Vec<Vec<u32>>> = HashMap::from([(0, vec![vec![1], vec![0]])]);
+
+    let read_only = ResultCache::new(&directory, CachePolicy::ReadOnly);
+    read_only.store("fingerprint", "job", &result).unwrap();
+    assert!(!directory.exists());
+
+    let read_write = ResultCache::new(&directory, CachePolicy::ReadWrite);
+    read_write.store("fingerprint", "job", &result).unwrap();
+    // No temporary files are left behind
+    let files: Vec<_> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|file| file.unwrap().file_name())
+        .collect();
+    assert_eq!(files, vec!["fingerprint.json"]);
+    let entry = read_write.load("fingerprint").unwrap().unwrap();
+    assert_eq!(entry.job_id, "job");
+    assert_eq!(entry.result, result);
+    assert_eq!(read_only.load("fingerprint").unwrap(), Some(entry));
+    assert_eq!(read_write.load("unknown").unwrap(), None);
+    let never = ResultCache::new(&directory, CachePolicy::Never);
+    assert_eq!(never.load("fingerprint").unwrap(), None);
+
+    // Entries older than the maximal age are ignored
+    let expiring = ResultCache::new(&directory, CachePolicy::Expiring(Duration::from_secs(3600)));
+    assert!(expiring.load("fingerprint").unwrap().is_some());
+    let old_entry = CacheEntry {
+        job_id: "old_job".to_string(),
+        created: 0,
+        result: result.clone(),
+    };
+    std::fs::write(
+        directory.join("old.json"),
+        serde_json::to_string(&old_entry).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(expiring.load("old").unwrap(), None);
+    assert_eq!(read_write.load("old").unwrap(), Some(old_entry));
+
+    read_write.clear().unwrap();
+    assert_eq!(read_write.load("fingerprint").unwrap(), None);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+// Test that a job found in the cache is not sent to the server again
+#[tokio::test]
+async fn cached_job_test() {
+    let aqt_resouce_details_online = json!({
+      "id": "simulator_noise",
+      "name": "Noisy Simulator",
+      "type": "simulator",
+      "status": "online",
+      "available_qubits": 12
+    });
+    let aqt_run_response_queued = json!({
+      "job": {"job_id": "dummy_test_id"},
+      "response": {"status": "queued"}
+    });
+    let aqt_run_response_finished = json!({
+      "job": {"job_id": "dummy_test_id"},
+      "response": {
+        "status": "finished",
+        "result": {"0": [[1], [1], [1]]}
+      }
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let directory = cache_directory("job");
+    let device = AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", server.uri())));
+    let mut backend = Backend::new(device, Some("DummyAccessToken".to_string())).unwrap();
+    backend.set_poll_interval(Duration::from_millis(10));
+    backend.set_result_cache(Some(ResultCache::new(&directory, CachePolicy::ReadWrite)));
+
+    let backend_cloned = backend.clone();
+    let (first, second) = spawn_blocking(move || {
+        let job = backend_cloned.submit_circuit(&test_circuit(3))?;
+        let first = backend_cloned.wait_for_job(&job)?;
+        // The result of the identical job is read from the cache
+        let job = backend_cloned.submit_circuit(&test_circuit(3))?;
+        assert_eq!(job.job_id(), "dummy_test_id");
+        let second = backend_cloned.wait_for_job(&job)?;
+        Ok::<_, roqoqo::RoqoqoBackendError>((first, second))
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(first.0.get("ro"), Some(&vec![vec![true]; 3]));
+    assert_eq!(first, second);
+    server.verify().await;
+
+    // The same resource on a different host does not share the cached result
+    let other_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&other_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .expect(1)
+        .mount(&other_server)
+        .await;
+    let other_device =
+        AqtDevice::new(1).set_remote_host(Some(format!("{}/mock/", other_server.uri())));
+    let mut other_backend =
+        Backend::new(other_device, Some("DummyAccessToken".to_string())).unwrap();
+    other_backend.set_result_cache(Some(ResultCache::new(&directory, CachePolicy::ReadWrite)));
+    let job = spawn_blocking(move || other_backend.submit_circuit(&test_circuit(3)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.job_id(), "dummy_test_id");
+    other_server.verify().await;
+
+    // Without reading the cache the job is sent to the server again
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .expect(1)
+        .mount(&server)
+        .await;
+    backend.set_result_cache(Some(ResultCache::new(&directory, CachePolicy::Never)));
+    let backend_cloned = backend.clone();
+    let job = spawn_blocking(move || backend_cloned.submit_circuit(&test_circuit(3)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.job_id(), "dummy_test_id");
+    server.verify().await;
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    // The result is returned when it can not be stored in the cache
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/mock/resources/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_resouce_details_online))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mock/submit/qoqo-integration/simulator_noise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_queued))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mock/result/dummy_test_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&aqt_run_response_finished))
+        .mount(&server)
+        .await;
+    let blocked = cache_directory("blocked");
+    std::fs::write(&blocked, "not a directory").unwrap();
+    backend.set_result_cache(Some(ResultCache::new(&blocked, CachePolicy::ReadWrite)));
+    let (bit_registers, _, _) = spawn_blocking(move || {
+        let job = backend.submit_circuit(&test_circuit(3))?;
+        backend.wait_for_job(&job)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(bit_registers.get("ro"), Some(&vec![vec![true]; 3]));
+    std::fs::remove_file(&blocked).unwrap();
+}